@@ -0,0 +1,300 @@
+use crate::batch::BatchItem;
+use crate::error::Error;
+use crate::types::{Commitment, FieldElement, Proof, COMMITMENT_SIZE, FIELD_ELEMENT_SIZE};
+use blstrs::{G1Affine, G1Projective};
+use std::cell::OnceCell;
+use std::collections::HashMap;
+
+// A storage format for large batches of openings, e.g. a DA archive keeping every sample proof. Many openings share
+// a commitment, so every distinct point (commitment or witness) is stored once, compressed, in a table that entries
+// refer to by index. Evaluation points are often small domain indices, so scalars are stored little-endian without
+// their trailing zero bytes. Reading validates nothing up front: points are decompressed and subgroup checked the
+// first time an entry needs them, and entries are decoded as the iterator reaches them.
+//
+// Layout, with varints as unsigned LEB128:
+//
+// - `KZGA`, then the version byte 1
+// - the 16 metadata bytes every commitment shares (see `Commitment::to_bytes`), all zero for an empty archive
+// - the number of table points and then of entries, as varints
+// - the table, 48 bytes per compressed point
+// - per entry, the commitment's and the proof's table indices as varints, then the point and the evaluation each as
+//   a length byte of at most 32 followed by that many bytes
+const MAGIC: &[u8; 4] = b"KZGA";
+const VERSION: u8 = 1;
+const METADATA_SIZE: usize = COMMITMENT_SIZE - 48;
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_scalar(out: &mut Vec<u8>, value: &FieldElement) {
+    let bytes = value.to_bytes();
+    let len = bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1);
+    out.push(len as u8);
+    out.extend_from_slice(&bytes[..len]);
+}
+
+// Fails if the openings aren't all for commitments with the same max degree and domain
+pub fn encode_archive(items: &[BatchItem]) -> Result<Vec<u8>, Error> {
+    let metadata = items.first().map(|item| item.commitment);
+    let mut table: Vec<[u8; 48]> = vec![];
+    let mut indices: HashMap<[u8; 48], u64> = HashMap::new();
+    let mut entries = vec![];
+    for item in items {
+        if let Some(first) = metadata {
+            if item.commitment.max_degree() != first.max_degree() {
+                return Err(Error::DegreeMismatch {
+                    expected: first.max_degree().get(),
+                    actual: item.commitment.max_degree().get(),
+                });
+            }
+            if item.commitment.domain_size() != first.domain_size() {
+                return Err(Error::DomainMismatch {
+                    expected: first.domain_size(),
+                    actual: item.commitment.domain_size(),
+                });
+            }
+        }
+        for point in [item.commitment.point_bytes(), item.proof.to_bytes()] {
+            let index = *indices.entry(point).or_insert_with(|| {
+                table.push(point);
+                table.len() as u64 - 1
+            });
+            write_varint(&mut entries, index);
+        }
+        write_scalar(&mut entries, &item.point);
+        write_scalar(&mut entries, &item.evaluation);
+    }
+
+    let mut out = MAGIC.to_vec();
+    out.push(VERSION);
+    match metadata {
+        Some(commitment) => out.extend_from_slice(&commitment.to_bytes()[48..]),
+        None => out.extend_from_slice(&[0; METADATA_SIZE]),
+    }
+    write_varint(&mut out, table.len() as u64);
+    write_varint(&mut out, items.len() as u64);
+    for point in &table {
+        out.extend_from_slice(point);
+    }
+    out.extend_from_slice(&entries);
+    Ok(out)
+}
+
+// Reads from a byte cursor, failing on truncation
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(Error::InvalidEncoding("proof archive is truncated"))?;
+        let taken = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(taken)
+    }
+
+    fn varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::InvalidEncoding("proof archive varint is too long"))
+    }
+
+    fn scalar(&mut self, index: usize) -> Result<FieldElement, Error> {
+        let len = self.take(1)?[0] as usize;
+        if len > FIELD_ELEMENT_SIZE {
+            return Err(Error::InvalidEncoding("proof archive scalar is too long"));
+        }
+        let mut bytes = [0u8; FIELD_ELEMENT_SIZE];
+        bytes[..len].copy_from_slice(self.take(len)?);
+        FieldElement::from_bytes(&bytes).map_err(|_| Error::InvalidScalar { index })
+    }
+}
+
+pub struct ProofArchive<'a> {
+    // Carries the shared metadata; entries swap in their own points
+    template: Commitment,
+    table: &'a [u8],
+    // Each table point, once something has needed it. `None` if it failed validation
+    points: Vec<OnceCell<Option<G1Projective>>>,
+    len: usize,
+    bytes: &'a [u8],
+    entries_offset: usize,
+}
+
+impl<'a> ProofArchive<'a> {
+    // Only reads the header and finds the table; nothing is decompressed yet
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let mut cursor = Cursor { bytes, offset: 0 };
+        if cursor.take(MAGIC.len())? != MAGIC || cursor.take(1)?[0] != VERSION {
+            return Err(Error::InvalidEncoding("not a version 1 proof archive"));
+        }
+        // The compressed identity, so only the metadata is really being decoded
+        let mut template = [0u8; COMMITMENT_SIZE];
+        template[0] = 0xc0;
+        template[48..].copy_from_slice(cursor.take(METADATA_SIZE)?);
+        let template = Commitment::from_bytes(&template)?;
+        let table_len = cursor.varint()? as usize;
+        let len = cursor.varint()? as usize;
+        let table = cursor.take(
+            table_len
+                .checked_mul(48)
+                .ok_or(Error::InvalidEncoding("proof archive table is too large"))?,
+        )?;
+        Ok(ProofArchive {
+            template,
+            table,
+            points: (0..table_len).map(|_| OnceCell::new()).collect(),
+            len,
+            bytes,
+            entries_offset: cursor.offset,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn point(&self, index: u64) -> Result<G1Projective, Error> {
+        let cell = self
+            .points
+            .get(index as usize)
+            .ok_or(Error::InvalidEncoding(
+                "proof archive entry points past the table",
+            ))?;
+        let start = index as usize * 48;
+        cell.get_or_init(|| {
+            let bytes: &[u8; 48] = self.table[start..start + 48].try_into().unwrap();
+            Option::<G1Affine>::from(G1Affine::from_compressed(bytes)).map(G1Projective::from)
+        })
+        .ok_or(Error::InvalidPoint {
+            group: "G1",
+            index: index as usize,
+        })
+    }
+
+    // Decodes entries in order. Stops after the first error, since the entries after a malformed one can't be found
+    pub fn iter(&self) -> ArchiveIter<'_, 'a> {
+        ArchiveIter {
+            archive: self,
+            cursor: Cursor {
+                bytes: self.bytes,
+                offset: self.entries_offset,
+            },
+            index: 0,
+            failed: false,
+        }
+    }
+}
+
+pub struct ArchiveIter<'r, 'a> {
+    archive: &'r ProofArchive<'a>,
+    cursor: Cursor<'a>,
+    index: usize,
+    failed: bool,
+}
+
+impl<'r, 'a> ArchiveIter<'r, 'a> {
+    fn decode(&mut self) -> Result<BatchItem, Error> {
+        let commitment = self.archive.point(self.cursor.varint()?)?;
+        let proof = self.archive.point(self.cursor.varint()?)?;
+        Ok(BatchItem {
+            commitment: self.archive.template.with_point(commitment),
+            point: self.cursor.scalar(self.index)?,
+            evaluation: self.cursor.scalar(self.index)?,
+            proof: Proof::from_inner(proof),
+        })
+    }
+}
+
+impl<'r, 'a> Iterator for ArchiveIter<'r, 'a> {
+    type Item = Result<BatchItem, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.index >= self.archive.len {
+            return None;
+        }
+        let item = self.decode();
+        self.failed = item.is_err();
+        self.index += 1;
+        Some(item)
+    }
+}
+
+#[test]
+fn stores_openings_compactly_and_reads_them_lazily() {
+    use crate::degree::MaxDegree;
+    use blstrs::Scalar;
+    use group::{Curve, Group};
+
+    let point = |k: u64| {
+        (G1Projective::generator() * Scalar::from(k))
+            .to_affine()
+            .to_compressed()
+    };
+    let commitments: Vec<Commitment> = (1..=3)
+        .map(|k| Commitment::from_point_bytes(&point(k), MaxDegree::new(15)).unwrap())
+        .collect();
+    let items: Vec<BatchItem> = (0..60u64)
+        .map(|i| BatchItem {
+            commitment: commitments[(i % 3) as usize],
+            point: FieldElement::from(i),
+            evaluation: -FieldElement::from(i + 1),
+            proof: Proof::from_bytes(&point(100 + i % 20)).unwrap(),
+        })
+        .collect();
+
+    let bytes = encode_archive(&items).unwrap();
+    // 23 distinct points, and entries of a few bytes plus a full-size evaluation
+    assert!(bytes.len() < 23 * 48 + 60 * 40);
+    let archive = ProofArchive::parse(&bytes).unwrap();
+    assert_eq!(archive.len(), 60);
+    let decoded: Result<Vec<BatchItem>, Error> = archive.iter().collect();
+    assert_eq!(decoded.unwrap(), items);
+
+    // A corrupt point only fails the entries that need it, from the first one onwards
+    let mut corrupted = bytes.clone();
+    let table_start = MAGIC.len() + 1 + METADATA_SIZE + 2;
+    corrupted[table_start + 96..table_start + 144].fill(0);
+    let archive = ProofArchive::parse(&corrupted).unwrap();
+    let results: Vec<_> = archive.iter().collect();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0], Ok(items[0]));
+    assert_eq!(
+        results[1],
+        Err(Error::InvalidPoint {
+            group: "G1",
+            index: 2
+        })
+    );
+
+    assert!(ProofArchive::parse(&bytes[..20]).is_err());
+    assert_eq!(
+        ProofArchive::parse(&encode_archive(&[]).unwrap())
+            .unwrap()
+            .len(),
+        0
+    );
+}
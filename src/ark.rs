@@ -0,0 +1,60 @@
+use crate::polynomials::Polynomial;
+use ark_bls12_381::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::DenseUVPolynomial;
+use blstrs::Scalar;
+
+// Both libraries implement the same BLS12-381 scalar field, so conversion goes through the canonical little-endian
+// encoding
+pub fn scalar_to_fr(scalar: &Scalar) -> Fr {
+    Fr::from_le_bytes_mod_order(&scalar.to_bytes_le())
+}
+
+pub fn fr_to_scalar(fr: &Fr) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&fr.into_bigint().to_bytes_le());
+    // Can unwrap since arkworks keeps field elements reduced
+    Scalar::from_bytes_le(&bytes).unwrap()
+}
+
+impl From<&Polynomial> for DensePolynomial<Fr> {
+    fn from(polynomial: &Polynomial) -> Self {
+        DensePolynomial::from_coefficients_vec(polynomial.iter().map(scalar_to_fr).collect())
+    }
+}
+
+impl From<Polynomial> for DensePolynomial<Fr> {
+    fn from(polynomial: Polynomial) -> Self {
+        DensePolynomial::from(&polynomial)
+    }
+}
+
+impl From<&DensePolynomial<Fr>> for Polynomial {
+    fn from(polynomial: &DensePolynomial<Fr>) -> Self {
+        let coefficients: Vec<Scalar> = polynomial.coeffs.iter().map(fr_to_scalar).collect();
+        Polynomial::new(&coefficients)
+    }
+}
+
+impl From<DensePolynomial<Fr>> for Polynomial {
+    fn from(polynomial: DensePolynomial<Fr>) -> Self {
+        Polynomial::from(&polynomial)
+    }
+}
+
+#[test]
+fn converts_to_and_from_arkworks() {
+    use ark_poly::Polynomial as _;
+
+    let polynomial = Polynomial::new_from_i64(&[-1, 3, -4, 1]);
+    let ark_polynomial = DensePolynomial::from(&polynomial);
+    assert_eq!(ark_polynomial.degree(), 3);
+
+    let point = Scalar::from(5);
+    assert_eq!(
+        fr_to_scalar(&ark_polynomial.evaluate(&scalar_to_fr(&point))),
+        polynomial.evaluate(point)
+    );
+    assert_eq!(Polynomial::from(ark_polynomial), polynomial);
+}
@@ -0,0 +1,57 @@
+use crate::error::Error;
+use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+use crate::polynomials::Polynomial;
+use blstrs::{G1Projective, Scalar};
+use std::sync::Arc;
+use tokio::task::{spawn_blocking, JoinError};
+
+// Async versions of the heavy prover operations. The MSMs and divisions run on tokio's blocking pool, so a service
+// awaiting a multi-second proof keeps its reactor threads free. The committer is shared through an `Arc` since the
+// work outlives the call
+
+fn propagate_panic(error: JoinError) -> ! {
+    std::panic::resume_unwind(error.into_panic())
+}
+
+pub async fn commit_async(
+    committer: Arc<GenericPolynomialCommitment>,
+    polynomial: Polynomial,
+) -> Result<G1Projective, Error> {
+    spawn_blocking(move || committer.commit(&polynomial))
+        .await
+        .unwrap_or_else(|e| propagate_panic(e))
+}
+
+pub async fn create_witness_async(
+    committer: Arc<GenericPolynomialCommitment>,
+    polynomial: Polynomial,
+    point: Scalar,
+) -> Result<(G1Projective, Scalar), Error> {
+    spawn_blocking(move || committer.create_witness(&polynomial, point))
+        .await
+        .unwrap_or_else(|e| propagate_panic(e))
+}
+
+#[test]
+fn commits_and_opens_asynchronously() {
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(2, &mut test_rng());
+    let committer = Arc::new(committer);
+    let polynomial = Polynomial::new_from_bytes(&[1, 2, 3]);
+    let point = Scalar::from(5);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let (commitment, (witness, evaluation)) = runtime.block_on(async {
+        let commitment = commit_async(committer.clone(), polynomial.clone()).await;
+        let opening = create_witness_async(committer.clone(), polynomial, point).await;
+        (commitment.unwrap(), opening.unwrap())
+    });
+    assert_eq!(
+        committer.verify_evaluation(commitment, point, evaluation, witness),
+        Ok(true)
+    );
+}
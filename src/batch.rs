@@ -0,0 +1,448 @@
+use crate::constant_time::ct_eq_gt;
+use crate::error::Error;
+use crate::transcript::{opening_challenge, OpeningClaim, Transcript};
+use crate::types::{Commitment, FieldElement, Proof};
+use crate::verifier_key::VerifierKey;
+use blstrs::{pairing, G1Projective, Scalar};
+use group::ff::Field;
+use group::{Curve, Group};
+use rand_core::{CryptoRng, RngCore};
+
+// How the random coefficients combining a batch are drawn. A batch containing any false claim passes with
+// probability at most $2^{-security\_bits}$. Short coefficients make the MSMs over the commitments and proofs about half
+// the work of full-size ones (the third, over the $r_i z_i$, is full size either way), which is the usual trade when
+// 128 bits of soundness is enough
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BatchChallenges {
+    // Uniform scalars mod r
+    Full,
+    // Uniform integers below $2^{bits}$, for 1 to 128 bits
+    Short(u32),
+}
+
+impl BatchChallenges {
+    // Full scalars give $\log_2 r$, just under 255 bits
+    pub fn security_bits(&self) -> u32 {
+        match self {
+            BatchChallenges::Full => 254,
+            BatchChallenges::Short(bits) => *bits,
+        }
+    }
+}
+
+// One claimed opening: `proof` shows the polynomial committed to by `commitment` is `evaluation` at `point`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BatchItem {
+    pub commitment: Commitment,
+    pub point: FieldElement,
+    pub evaluation: FieldElement,
+    pub proof: Proof,
+}
+
+impl VerifierKey {
+    // All the openings at once, with two pairings instead of two per item. With random $r_i$ it checks
+    // $e(\sum r_i (C_i - v_i G + z_i W_i), H) = e(\sum r_i W_i, [\tau]_2)$. The coefficients come from the verifier's
+    // own rng after the claims are fixed, so a prover can't aim a false claim at them
+    pub fn verify_batch<R: RngCore + CryptoRng>(
+        &self,
+        items: &[BatchItem],
+        challenges: BatchChallenges,
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        if let BatchChallenges::Short(bits) = challenges {
+            if bits == 0 || bits > 128 {
+                return Err(Error::InvalidParameters(
+                    "short batch challenges must be 1 to 128 bits",
+                ));
+            }
+        }
+        self.check_items(items)?;
+        if items.is_empty() {
+            return Ok(true);
+        }
+
+        let commitments: Vec<G1Projective> = items.iter().map(|i| i.commitment.inner()).collect();
+        let witnesses: Vec<G1Projective> = items.iter().map(|i| i.proof.inner()).collect();
+        let (coefficients, folded_commitments, folded_witnesses) = match challenges {
+            BatchChallenges::Full => {
+                let coefficients: Vec<Scalar> =
+                    items.iter().map(|_| Scalar::random(&mut *rng)).collect();
+                (
+                    coefficients.clone(),
+                    G1Projective::multi_exp(&commitments, &coefficients),
+                    G1Projective::multi_exp(&witnesses, &coefficients),
+                )
+            }
+            BatchChallenges::Short(bits) => {
+                let mask = u128::MAX >> (128 - bits);
+                let short: Vec<u128> = items
+                    .iter()
+                    .map(|_| {
+                        let mut bytes = [0u8; 16];
+                        rng.fill_bytes(&mut bytes);
+                        u128::from_le_bytes(bytes) & mask
+                    })
+                    .collect();
+                (
+                    short.iter().map(|r| scalar_from_u128(*r)).collect(),
+                    small_msm(&commitments, &short, bits),
+                    small_msm(&witnesses, &short, bits),
+                )
+            }
+        };
+
+        Ok(self.check_folded(
+            items,
+            &coefficients,
+            folded_commitments,
+            &witnesses,
+            folded_witnesses,
+        ))
+    }
+
+    // The same check with $r_i = r^i$ for one challenge r from the transcript, for protocols that batch
+    // non-interactively. The challenge goes through `opening_challenge`, so it depends on every claim in full
+    pub fn verify_batch_with_transcript<T: Transcript>(
+        &self,
+        items: &[BatchItem],
+        transcript: &mut T,
+    ) -> Result<bool, Error> {
+        self.check_items(items)?;
+        if items.is_empty() {
+            return Ok(true);
+        }
+        let claims: Vec<OpeningClaim> = items
+            .iter()
+            .map(|item| OpeningClaim {
+                commitment: item.commitment.inner(),
+                point: item.point.inner(),
+                evaluation: item.evaluation.inner(),
+            })
+            .collect();
+        let challenge = opening_challenge(transcript, &claims);
+        let coefficients: Vec<Scalar> =
+            std::iter::successors(Some(Scalar::ONE), |power| Some(power * challenge))
+                .take(items.len())
+                .collect();
+
+        let commitments: Vec<G1Projective> = items.iter().map(|i| i.commitment.inner()).collect();
+        let witnesses: Vec<G1Projective> = items.iter().map(|i| i.proof.inner()).collect();
+        Ok(self.check_folded(
+            items,
+            &coefficients,
+            G1Projective::multi_exp(&commitments, &coefficients),
+            &witnesses,
+            G1Projective::multi_exp(&witnesses, &coefficients),
+        ))
+    }
+
+    fn check_items(&self, items: &[BatchItem]) -> Result<(), Error> {
+        for item in items {
+            self.check_compatible(&item.commitment)?;
+            if bool::from(item.commitment.inner().is_identity()) {
+                return Err(Error::IdentityCommitment);
+            }
+            if bool::from(item.proof.inner().is_identity()) {
+                return Err(Error::IdentityWitness);
+            }
+        }
+        Ok(())
+    }
+
+    // The pairing check, given the $r_i$ and the two sums already folded with them
+    fn check_folded(
+        &self,
+        items: &[BatchItem],
+        coefficients: &[Scalar],
+        folded_commitments: G1Projective,
+        witnesses: &[G1Projective],
+        folded_witnesses: G1Projective,
+    ) -> bool {
+        // The $r_i z_i$ are full size whichever way the $r_i$ were drawn
+        let shifted: Vec<Scalar> = items
+            .iter()
+            .zip(coefficients)
+            .map(|(item, r)| item.point.inner() * r)
+            .collect();
+        let evaluation: Scalar = items
+            .iter()
+            .zip(coefficients)
+            .map(|(item, r)| item.evaluation.inner() * r)
+            .sum();
+
+        let left = folded_commitments - self.g() * evaluation
+            + G1Projective::multi_exp(witnesses, &shifted);
+        let lhs = pairing(&left.to_affine(), &self.h().to_affine());
+        let rhs = pairing(&folded_witnesses.to_affine(), &self.h_tau().to_affine());
+        ct_eq_gt(&lhs, &rhs).into()
+    }
+}
+
+// Verifies openings as they arrive, e.g. from a block sync pipeline, without collecting them first. Each item is folded
+// into a running sum with its own random coefficient, the same combination `verify_batch` checks, and the pairing check
+// runs every `flush_interval` items and at the end. A flush that fails means some item since the previous flush was
+// invalid, and everything after it is rejected
+pub struct StreamingVerifier<'a, R> {
+    key: &'a VerifierKey,
+    rng: R,
+    flush_interval: usize,
+    pending: usize,
+    // $\sum r_i (C_i - v_i G + z_i W_i)$ and $\sum r_i W_i$ since the last flush
+    left: G1Projective,
+    witnesses: G1Projective,
+    failed: bool,
+}
+
+impl<'a, R: RngCore + CryptoRng> StreamingVerifier<'a, R> {
+    // A `flush_interval` of 0 only checks in `finish`
+    pub fn new(key: &'a VerifierKey, flush_interval: usize, rng: R) -> Self {
+        StreamingVerifier {
+            key,
+            rng,
+            flush_interval,
+            pending: 0,
+            left: G1Projective::identity(),
+            witnesses: G1Projective::identity(),
+            failed: false,
+        }
+    }
+
+    // Folds in one item. `Ok(false)` once any flush has failed
+    pub fn push(&mut self, item: &BatchItem) -> Result<bool, Error> {
+        if self.failed {
+            return Ok(false);
+        }
+        self.key.check_compatible(&item.commitment)?;
+        if bool::from(item.commitment.inner().is_identity()) {
+            return Err(Error::IdentityCommitment);
+        }
+        if bool::from(item.proof.inner().is_identity()) {
+            return Err(Error::IdentityWitness);
+        }
+
+        let r = Scalar::random(&mut self.rng);
+        let witness = item.proof.inner();
+        self.left += (item.commitment.inner() - self.key.g() * item.evaluation.inner()
+            + witness * item.point.inner())
+            * r;
+        self.witnesses += witness * r;
+        self.pending += 1;
+        if self.pending == self.flush_interval {
+            return Ok(self.flush());
+        }
+        Ok(true)
+    }
+
+    // Checks everything folded in since the last flush and starts a new accumulator
+    pub fn flush(&mut self) -> bool {
+        if self.failed {
+            return false;
+        }
+        if self.pending > 0 {
+            let lhs = pairing(&self.left.to_affine(), &self.key.h().to_affine());
+            let rhs = pairing(&self.witnesses.to_affine(), &self.key.h_tau().to_affine());
+            self.failed = !bool::from(ct_eq_gt(&lhs, &rhs));
+        }
+        self.left = G1Projective::identity();
+        self.witnesses = G1Projective::identity();
+        self.pending = 0;
+        !self.failed
+    }
+
+    pub fn finish(mut self) -> bool {
+        self.flush()
+    }
+}
+
+impl VerifierKey {
+    // Verifies every item from an iterator (a channel's `iter()`, say), stopping at the first failed flush
+    pub fn verify_stream<R: RngCore + CryptoRng>(
+        &self,
+        items: impl IntoIterator<Item = BatchItem>,
+        flush_interval: usize,
+        rng: R,
+    ) -> Result<bool, Error> {
+        let mut verifier = StreamingVerifier::new(self, flush_interval, rng);
+        for item in items {
+            if !verifier.push(&item)? {
+                return Ok(false);
+            }
+        }
+        Ok(verifier.finish())
+    }
+}
+
+fn scalar_from_u128(value: u128) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(&value.to_le_bytes());
+    // Can unwrap since anything below 2^128 is reduced
+    Scalar::from_bytes_le(&bytes).unwrap()
+}
+
+// Pippenger's bucket method over only the low `bits` bits of each scalar, so 128-bit scalars take half the doublings
+// and bucket passes of full ones
+fn small_msm(points: &[G1Projective], scalars: &[u128], bits: u32) -> G1Projective {
+    let window = match points.len() {
+        0..=31 => 3,
+        n => (usize::BITS - n.leading_zeros()) as u32 - 2,
+    };
+    let mask = (1u128 << window) - 1;
+    let windows = (bits + window - 1) / window;
+
+    let mut result = G1Projective::identity();
+    for w in (0..windows).rev() {
+        for _ in 0..window {
+            result = result.double();
+        }
+        let mut buckets = vec![G1Projective::identity(); (1 << window) - 1];
+        for (point, scalar) in points.iter().zip(scalars) {
+            let digit = ((scalar >> (w * window)) & mask) as usize;
+            if digit != 0 {
+                buckets[digit - 1] += point;
+            }
+        }
+        // $\sum_d d \cdot B_d$ as a running sum from the top bucket down
+        let mut running = G1Projective::identity();
+        for bucket in buckets.iter().rev() {
+            running += bucket;
+            result += running;
+        }
+    }
+    result
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn small_msm_matches_multi_exp() {
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    // Window counts that do and don't divide the bits, and a window wider than them
+    for (n, bits) in [(1, 128), (5, 128), (40, 128), (40, 64), (40, 7), (5, 1)] {
+        let mask = u128::MAX >> (128 - bits);
+        let points: Vec<G1Projective> = (0..n).map(|_| G1Projective::random(&mut rng)).collect();
+        let scalars: Vec<u128> = (0..n)
+            .map(|_| (((rng.next_u64() as u128) << 64) | rng.next_u64() as u128) & mask)
+            .collect();
+        let expected = G1Projective::multi_exp(
+            &points,
+            &scalars
+                .iter()
+                .map(|s| scalar_from_u128(*s))
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(small_msm(&points, &scalars, bits), expected);
+    }
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn verifies_batches_of_openings() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+    use crate::transcript::Sha256Transcript;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let key = committer
+        .setup_with_rng(5, &mut test_rng())
+        .verifier_key()
+        .unwrap();
+    let mut rng = test_rng();
+    let mut items: Vec<BatchItem> = (0..4)
+        .map(|i| {
+            let polynomial = Polynomial::rand(5, &mut rng);
+            let point = FieldElement::from(i + 2);
+            let (proof, evaluation) = committer.prove(&polynomial, point).unwrap();
+            BatchItem {
+                commitment: committer.commit_opaque(&polynomial).unwrap(),
+                point,
+                evaluation,
+                proof,
+            }
+        })
+        .collect();
+
+    for challenges in [
+        BatchChallenges::Full,
+        BatchChallenges::Short(128),
+        BatchChallenges::Short(64),
+    ] {
+        assert!(key
+            .verify_batch(&items, challenges, &mut test_rng())
+            .unwrap());
+    }
+    assert_eq!(BatchChallenges::Short(64).security_bits(), 64);
+    assert!(key
+        .verify_batch(&items, BatchChallenges::Short(129), &mut test_rng())
+        .is_err());
+    let transcript = || Sha256Transcript::new(b"batch test");
+    assert_eq!(
+        key.verify_batch_with_transcript(&items, &mut transcript()),
+        Ok(true)
+    );
+
+    items[2].evaluation = items[2].evaluation + FieldElement::one();
+    for challenges in [BatchChallenges::Full, BatchChallenges::Short(128)] {
+        assert!(!key
+            .verify_batch(&items, challenges, &mut test_rng())
+            .unwrap());
+    }
+    assert_eq!(
+        key.verify_batch_with_transcript(&items, &mut transcript()),
+        Ok(false)
+    );
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn verifies_streams_of_openings() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+    use std::sync::mpsc::channel;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let key = committer
+        .setup_with_rng(3, &mut test_rng())
+        .verifier_key()
+        .unwrap();
+    let mut rng = test_rng();
+    let items: Vec<BatchItem> = (0..7)
+        .map(|i| {
+            let polynomial = Polynomial::rand(3, &mut rng);
+            let point = FieldElement::from(i + 1);
+            let (proof, evaluation) = committer.prove(&polynomial, point).unwrap();
+            BatchItem {
+                commitment: committer.commit_opaque(&polynomial).unwrap(),
+                point,
+                evaluation,
+                proof,
+            }
+        })
+        .collect();
+
+    let (sender, receiver) = channel();
+    for item in &items {
+        sender.send(*item).unwrap();
+    }
+    drop(sender);
+    assert!(key.verify_stream(receiver.iter(), 3, test_rng()).unwrap());
+
+    let mut bad = items.clone();
+    bad[4].evaluation = bad[4].evaluation + FieldElement::one();
+    for flush_interval in [0, 1, 3] {
+        assert!(!key
+            .verify_stream(bad.clone(), flush_interval, test_rng())
+            .unwrap());
+    }
+
+    // The flush right after the bad item is the one that catches it
+    let mut verifier = StreamingVerifier::new(&key, 5, test_rng());
+    let results: Vec<bool> = bad
+        .iter()
+        .map(|item| verifier.push(item).unwrap())
+        .collect();
+    assert_eq!(results, vec![true, true, true, true, false, false, false]);
+    assert!(!verifier.finish());
+}
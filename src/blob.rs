@@ -0,0 +1,191 @@
+use crate::domain::{bit_reverse, EvaluationDomain};
+use crate::domain_polynomial::DomainPolynomial;
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::types::{Commitment, FieldElement, Proof};
+use blstrs::Scalar;
+
+// `FIELD_ELEMENTS_PER_BLOB` and `BYTES_PER_FIELD_ELEMENT` from EIP-4844
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+
+// An EIP-4844 blob: 4096 field elements, each the evaluation of the blob's polynomial at a 4096th root of unity.
+// As in the spec, elements are 32 bytes big-endian and the roots are taken in bit-reversed order, so element i is
+// the value at $\omega^{rev(i)}$. A blob is data rather than a proof, so the zero and constant blobs commit and open
+// like any other
+#[derive(Clone, Debug)]
+pub struct Blob {
+    polynomial: DomainPolynomial,
+}
+
+impl Blob {
+    // Errs on any length but `BYTES_PER_BLOB`, or with the index of the first element that isn't reduced mod r
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != BYTES_PER_BLOB {
+            return Err(Error::InvalidEncoding("a blob is 131072 bytes"));
+        }
+        let elements = bytes
+            .chunks_exact(BYTES_PER_FIELD_ELEMENT)
+            .enumerate()
+            .map(|(index, chunk)| {
+                Option::<Scalar>::from(Scalar::from_bytes_be(chunk.try_into().unwrap()))
+                    .ok_or(Error::InvalidScalar { index })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Blob::from_scalars(&elements))
+    }
+
+    // Errs unless there are exactly `FIELD_ELEMENTS_PER_BLOB`
+    pub fn from_field_elements(elements: &[FieldElement]) -> Result<Self, Error> {
+        if elements.len() != FIELD_ELEMENTS_PER_BLOB {
+            return Err(Error::InvalidEncoding("a blob is 4096 field elements"));
+        }
+        let elements: Vec<Scalar> = elements.iter().map(FieldElement::inner).collect();
+        Ok(Blob::from_scalars(&elements))
+    }
+
+    fn from_scalars(elements: &[Scalar]) -> Self {
+        let domain = EvaluationDomain::new(FIELD_ELEMENTS_PER_BLOB).unwrap();
+        let log_size = domain.log_size();
+        let evaluations = (0..FIELD_ELEMENTS_PER_BLOB)
+            .map(|i| elements[bit_reverse(i, log_size)])
+            .collect();
+        // The domain is exactly as large as the evaluations
+        Blob {
+            polynomial: DomainPolynomial::from_evaluations(domain, evaluations).unwrap(),
+        }
+    }
+
+    // The elements in blob order
+    pub fn field_elements(&self) -> Vec<FieldElement> {
+        let evaluations = self.polynomial.evaluations();
+        let log_size = self.polynomial.domain().log_size();
+        (0..FIELD_ELEMENTS_PER_BLOB)
+            .map(|i| FieldElement::from_inner(evaluations[bit_reverse(i, log_size)]))
+            .collect()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.field_elements()
+            .iter()
+            .flat_map(|element| element.inner().to_bytes_be())
+            .collect()
+    }
+
+    // `blob_to_kzg_commitment`. Needs a setup of degree at least 4095
+    pub fn commit(&self, committer: &GenericPolynomialCommitment) -> Result<Commitment, Error> {
+        let point = committer.msm(self.polynomial.coefficients().coeffs())?;
+        Ok(Commitment::from_parts(
+            point,
+            committer.max_degree()?,
+            Some(FIELD_ELEMENTS_PER_BLOB),
+        ))
+    }
+
+    // `compute_kzg_proof`: the proof and the value of the blob's polynomial at `point`
+    pub fn prove(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        point: FieldElement,
+    ) -> Result<(Proof, FieldElement), Error> {
+        let (witness, evaluation) =
+            committer.open(self.polynomial.coefficients(), point.inner())?;
+        Ok((
+            Proof::from_inner(witness),
+            FieldElement::from_inner(evaluation),
+        ))
+    }
+
+    // `verify_kzg_proof`, for a commitment that came from a blob with the committer's parameters
+    pub fn verify(
+        committer: &GenericPolynomialCommitment,
+        commitment: &Commitment,
+        point: FieldElement,
+        evaluation: FieldElement,
+        proof: &Proof,
+    ) -> Result<bool, Error> {
+        committer.max_degree()?.check(commitment.max_degree())?;
+        if commitment.domain_size() != Some(FIELD_ELEMENTS_PER_BLOB) {
+            return Err(Error::DomainMismatch {
+                expected: Some(FIELD_ELEMENTS_PER_BLOB),
+                actual: commitment.domain_size(),
+            });
+        }
+        committer.check_opening(
+            commitment.inner(),
+            point.inner(),
+            evaluation.inner(),
+            proof.inner(),
+        )
+    }
+}
+
+#[test]
+fn commits_proves_and_verifies_blobs() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(FIELD_ELEMENTS_PER_BLOB - 1, &mut test_rng());
+
+    let mut bytes = vec![0u8; BYTES_PER_BLOB];
+    for (i, chunk) in bytes.chunks_exact_mut(BYTES_PER_FIELD_ELEMENT).enumerate() {
+        chunk[30..].copy_from_slice(&(i as u16 * 3).to_be_bytes());
+    }
+    let blob = Blob::from_bytes(&bytes).unwrap();
+    assert_eq!(blob.to_bytes(), bytes);
+    let commitment = blob.commit(&committer).unwrap();
+
+    // Element i is the value at the bit-reversed i-th root of unity
+    let domain = EvaluationDomain::new(FIELD_ELEMENTS_PER_BLOB).unwrap();
+    let root = FieldElement::from_inner(domain.element(bit_reverse(5, domain.log_size())));
+    let (proof, evaluation) = blob.prove(&committer, root).unwrap();
+    assert_eq!(evaluation, FieldElement::from(15));
+
+    let point = FieldElement::from(123456789);
+    let (proof_outside, evaluation_outside) = blob.prove(&committer, point).unwrap();
+    for (point, evaluation, proof) in [
+        (root, evaluation, proof),
+        (point, evaluation_outside, proof_outside),
+    ] {
+        assert_eq!(
+            Blob::verify(&committer, &commitment, point, evaluation, &proof),
+            Ok(true)
+        );
+        assert_eq!(
+            Blob::verify(
+                &committer,
+                &commitment,
+                point,
+                evaluation + FieldElement::one(),
+                &proof
+            ),
+            Ok(false)
+        );
+    }
+
+    // The empty blob is fine too
+    let empty = Blob::from_bytes(&[0; BYTES_PER_BLOB]).unwrap();
+    let (proof, evaluation) = empty.prove(&committer, point).unwrap();
+    assert_eq!(evaluation, FieldElement::zero());
+    assert_eq!(
+        Blob::verify(
+            &committer,
+            &empty.commit(&committer).unwrap(),
+            point,
+            evaluation,
+            &proof
+        ),
+        Ok(true)
+    );
+
+    // Non-canonical elements and wrong lengths
+    bytes[64..96].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        Blob::from_bytes(&bytes).err(),
+        Some(Error::InvalidScalar { index: 2 })
+    );
+    assert!(Blob::from_bytes(&bytes[1..]).is_err());
+    assert!(Blob::from_field_elements(&[FieldElement::one()]).is_err());
+}
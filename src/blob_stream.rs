@@ -0,0 +1,287 @@
+use crate::blob::{BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT, FIELD_ELEMENTS_PER_BLOB};
+use crate::constant_time::ct_eq_gt;
+use crate::domain::{bit_reverse, EvaluationDomain};
+use crate::error::Error;
+use crate::scalars::scalar_from_biguint;
+use crate::types::{Commitment, Proof};
+use crate::verifier_key::VerifierKey;
+use blstrs::{pairing, G1Projective, Scalar};
+use group::ff::{BatchInvert, Field};
+use group::{Curve, Group};
+use num_bigint::BigUint;
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+
+// `FIAT_SHAMIR_PROTOCOL_DOMAIN` from EIP-4844, the start of every `verify_blob_kzg_proof` challenge
+pub const FIAT_SHAMIR_PROTOCOL_DOMAIN: &[u8; 16] = b"FSBLOBVERIFY_V1_";
+
+// One blob's bytes as they arrive from the network. Each field element is checked as soon as its last byte is in, so
+// a non-canonical element fails the sidecar without waiting for the rest, and the bytes go into the challenge hash
+// as they come. A stream that erred shouldn't be used again
+pub struct BlobStream {
+    hasher: Sha256,
+    elements: Vec<Scalar>,
+    // The first bytes of an element whose last bytes haven't arrived yet
+    partial: Vec<u8>,
+}
+
+impl BlobStream {
+    pub fn new() -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(FIAT_SHAMIR_PROTOCOL_DOMAIN);
+        hasher.update((FIELD_ELEMENTS_PER_BLOB as u128).to_be_bytes());
+        BlobStream {
+            hasher,
+            elements: Vec::with_capacity(FIELD_ELEMENTS_PER_BLOB),
+            partial: Vec::with_capacity(BYTES_PER_FIELD_ELEMENT),
+        }
+    }
+
+    // Errs with the index of the first element that isn't reduced mod r, or when the chunks run past `BYTES_PER_BLOB`
+    pub fn update(&mut self, mut chunk: &[u8]) -> Result<(), Error> {
+        if self.received() + chunk.len() > BYTES_PER_BLOB {
+            return Err(Error::InvalidEncoding("a blob is 131072 bytes"));
+        }
+        self.hasher.update(chunk);
+        while !chunk.is_empty() {
+            let take = (BYTES_PER_FIELD_ELEMENT - self.partial.len()).min(chunk.len());
+            self.partial.extend_from_slice(&chunk[..take]);
+            chunk = &chunk[take..];
+            if self.partial.len() == BYTES_PER_FIELD_ELEMENT {
+                let index = self.elements.len();
+                let element = Scalar::from_bytes_be(self.partial.as_slice().try_into().unwrap());
+                self.elements
+                    .push(Option::<Scalar>::from(element).ok_or(Error::InvalidScalar { index })?);
+                self.partial.clear();
+            }
+        }
+        Ok(())
+    }
+
+    // Bytes taken in so far
+    pub fn received(&self) -> usize {
+        self.elements.len() * BYTES_PER_FIELD_ELEMENT + self.partial.len()
+    }
+}
+
+impl Default for BlobStream {
+    fn default() -> Self {
+        BlobStream::new()
+    }
+}
+
+// Checks blob sidecars, `verify_blob_kzg_proof` for each, with the pairings deferred. Each finished blob is folded into
+// a running sum with its own random coefficient, as `StreamingVerifier` does, and one pairing check covers every
+// sidecar since the last flush. Unlike `StreamingVerifier` it takes identity commitments and proofs, which the zero
+// blob has
+pub struct BlobSidecarVerifier<R> {
+    key: VerifierKey,
+    rng: R,
+    // The domain elements in blob order
+    roots: Vec<Scalar>,
+    pending: usize,
+    // $\sum r_i (C_i - y_i G + z_i W_i)$ and $\sum r_i W_i$ since the last flush
+    left: G1Projective,
+    witnesses: G1Projective,
+    failed: bool,
+}
+
+impl<R: RngCore + CryptoRng> BlobSidecarVerifier<R> {
+    // Commitments are checked against `key` for the blob domain
+    pub fn new(key: &VerifierKey, rng: R) -> Self {
+        let domain = EvaluationDomain::new(FIELD_ELEMENTS_PER_BLOB).unwrap();
+        let roots = (0..FIELD_ELEMENTS_PER_BLOB)
+            .map(|i| domain.element(bit_reverse(i, domain.log_size())))
+            .collect();
+        BlobSidecarVerifier {
+            key: key.with_domain_size(FIELD_ELEMENTS_PER_BLOB),
+            rng,
+            roots,
+            pending: 0,
+            left: G1Projective::identity(),
+            witnesses: G1Projective::identity(),
+            failed: false,
+        }
+    }
+
+    // Finishes the challenge hash with the commitment, evaluates the blob there and folds in the opening. Errs on a
+    // blob that isn't complete or a commitment for other parameters. `Ok(false)` once any flush has failed
+    pub fn push(
+        &mut self,
+        blob: BlobStream,
+        commitment: &Commitment,
+        proof: &Proof,
+    ) -> Result<bool, Error> {
+        if self.failed {
+            return Ok(false);
+        }
+        if blob.received() != BYTES_PER_BLOB {
+            return Err(Error::LengthMismatch {
+                expected: BYTES_PER_BLOB,
+                actual: blob.received(),
+            });
+        }
+        self.key.check_compatible(commitment)?;
+
+        let mut hasher = blob.hasher;
+        hasher.update(commitment.point_bytes());
+        let point = scalar_from_biguint(&BigUint::from_bytes_be(&hasher.finalize()));
+        let evaluation = evaluate(&self.roots, &blob.elements, point);
+
+        let r = Scalar::random(&mut self.rng);
+        let witness = proof.inner();
+        self.left += (commitment.inner() - self.key.g() * evaluation + witness * point) * r;
+        self.witnesses += witness * r;
+        self.pending += 1;
+        Ok(true)
+    }
+
+    // Checks everything folded in since the last flush, e.g. the sidecars of one block, and starts a new accumulator
+    pub fn flush(&mut self) -> bool {
+        if self.failed {
+            return false;
+        }
+        if self.pending > 0 {
+            let lhs = pairing(&self.left.to_affine(), &self.key.h().to_affine());
+            let rhs = pairing(&self.witnesses.to_affine(), &self.key.h_tau().to_affine());
+            self.failed = !bool::from(ct_eq_gt(&lhs, &rhs));
+        }
+        self.left = G1Projective::identity();
+        self.witnesses = G1Projective::identity();
+        self.pending = 0;
+        !self.failed
+    }
+
+    pub fn finish(mut self) -> bool {
+        self.flush()
+    }
+}
+
+// The blob's polynomial at `point` from its values, with the barycentric formula
+// $f(z) = \frac{z^n - 1}{n} \sum_i \frac{f_i \omega_i}{z - \omega_i}$
+fn evaluate(roots: &[Scalar], elements: &[Scalar], point: Scalar) -> Scalar {
+    if let Some(i) = roots.iter().position(|root| *root == point) {
+        return elements[i];
+    }
+    let mut denominators: Vec<Scalar> = roots.iter().map(|root| point - root).collect();
+    denominators.iter_mut().batch_invert();
+    let sum: Scalar = roots
+        .iter()
+        .zip(elements)
+        .zip(&denominators)
+        .map(|((root, element), inverse)| *element * root * inverse)
+        .sum();
+    let n = Scalar::from(FIELD_ELEMENTS_PER_BLOB as u64);
+    // Can unwrap since n isn't zero
+    sum * (point.pow_vartime([FIELD_ELEMENTS_PER_BLOB as u64]) - Scalar::ONE) * n.invert().unwrap()
+}
+
+#[test]
+fn verifies_blob_sidecars_as_they_arrive() {
+    use crate::blob::Blob;
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::rng::test_rng;
+    use crate::types::FieldElement;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let key = committer
+        .setup_with_rng(FIELD_ELEMENTS_PER_BLOB - 1, &mut test_rng())
+        .verifier_key()
+        .unwrap();
+
+    // Proofs at the spec's challenge, computed over the whole blob at once
+    let sidecar = |bytes: &[u8]| {
+        let blob = Blob::from_bytes(bytes).unwrap();
+        let commitment = blob.commit(&committer).unwrap();
+        let mut hasher = Sha256::new();
+        hasher.update(FIAT_SHAMIR_PROTOCOL_DOMAIN);
+        hasher.update((FIELD_ELEMENTS_PER_BLOB as u128).to_be_bytes());
+        hasher.update(bytes);
+        hasher.update(commitment.point_bytes());
+        let challenge = scalar_from_biguint(&BigUint::from_bytes_be(&hasher.finalize()));
+        let (proof, _) = blob
+            .prove(&committer, FieldElement::from_inner(challenge))
+            .unwrap();
+        (commitment, proof)
+    };
+    let stream = |bytes: &[u8], chunk_size: usize| {
+        let mut stream = BlobStream::new();
+        for chunk in bytes.chunks(chunk_size) {
+            stream.update(chunk).unwrap();
+        }
+        stream
+    };
+
+    let mut bytes = vec![0u8; BYTES_PER_BLOB];
+    for (i, chunk) in bytes.chunks_exact_mut(BYTES_PER_FIELD_ELEMENT).enumerate() {
+        chunk[28..].copy_from_slice(&(i as u32 * 31 + 7).to_be_bytes());
+    }
+    let zero = vec![0u8; BYTES_PER_BLOB];
+    let (commitment, proof) = sidecar(&bytes);
+    let (zero_commitment, zero_proof) = sidecar(&zero);
+
+    // Chunk sizes that split field elements
+    let mut verifier = BlobSidecarVerifier::new(&key, test_rng());
+    assert_eq!(
+        verifier.push(stream(&bytes, 1000), &commitment, &proof),
+        Ok(true)
+    );
+    assert_eq!(
+        verifier.push(stream(&zero, 4097), &zero_commitment, &zero_proof),
+        Ok(true)
+    );
+    assert!(verifier.flush());
+    assert_eq!(
+        verifier.push(stream(&bytes, BYTES_PER_BLOB), &commitment, &proof),
+        Ok(true)
+    );
+    assert!(verifier.finish());
+
+    // Another blob's proof fails the flush it's part of
+    let mut verifier = BlobSidecarVerifier::new(&key, test_rng());
+    assert_eq!(
+        verifier.push(stream(&bytes, 1000), &commitment, &zero_proof),
+        Ok(true)
+    );
+    assert!(!verifier.flush());
+    assert_eq!(
+        verifier.push(stream(&bytes, 1000), &commitment, &proof),
+        Ok(false)
+    );
+    assert!(!verifier.finish());
+
+    // A non-canonical element fails the chunk that completes it
+    let mut bad = bytes.clone();
+    bad[96..128].copy_from_slice(&[0xff; 32]);
+    let mut stream_bad = BlobStream::new();
+    assert_eq!(stream_bad.update(&bad[..100]), Ok(()));
+    assert_eq!(
+        stream_bad.update(&bad[100..200]),
+        Err(Error::InvalidScalar { index: 3 })
+    );
+
+    let mut verifier = BlobSidecarVerifier::new(&key, test_rng());
+    let mut partial = BlobStream::new();
+    partial.update(&bytes[..1000]).unwrap();
+    assert_eq!(
+        verifier.push(partial, &commitment, &proof),
+        Err(Error::LengthMismatch {
+            expected: BYTES_PER_BLOB,
+            actual: 1000
+        })
+    );
+    let mut full = stream(&bytes, 1000);
+    assert!(full.update(&[0]).is_err());
+    assert_eq!(
+        verifier.push(
+            stream(&bytes, 1000),
+            &Commitment::from_point_bytes(&commitment.point_bytes(), commitment.max_degree())
+                .unwrap(),
+            &proof
+        ),
+        Err(Error::DomainMismatch {
+            expected: Some(FIELD_ELEMENTS_PER_BLOB),
+            actual: None
+        })
+    );
+}
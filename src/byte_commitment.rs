@@ -0,0 +1,167 @@
+use crate::domain::EvaluationDomain;
+use crate::domain_polynomial::DomainPolynomial;
+use crate::encoding::{pack_bytes, BYTES_PER_SCALAR};
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::types::{Commitment, FieldElement, Proof};
+
+// Commitments to a byte string in one call. The bytes are packed 31 per scalar, padded with zero scalars to the next
+// power of two, and the scalars are taken as the polynomial's values over a domain of that size, so position i is the
+// value at the i-th domain element. The whole string has to fit in one polynomial; anything larger belongs in
+// `ChunkedData` or `commit_file`
+
+// What's needed besides the bytes to prove positions, and besides the commitment to check them
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EncodingInfo {
+    pub byte_len: usize,
+    pub domain_size: usize,
+}
+
+impl EncodingInfo {
+    // Scalars holding data, not counting the padding
+    pub fn len(&self) -> usize {
+        self.byte_len.div_ceil(BYTES_PER_SCALAR)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.byte_len == 0
+    }
+
+    // Where position `index` is opened
+    pub fn point(&self, index: usize) -> Result<FieldElement, Error> {
+        if index >= self.len() {
+            return Err(Error::IndexOutOfRange {
+                index,
+                len: self.len(),
+            });
+        }
+        Ok(FieldElement::from_inner(self.domain()?.element(index)))
+    }
+
+    // The value position `index` should hold, given the (up to) 31 bytes stored there
+    pub fn value(bytes: &[u8]) -> Result<FieldElement, Error> {
+        match pack_bytes(bytes).as_slice() {
+            [value] => Ok(FieldElement::from_inner(*value)),
+            _ => Err(Error::InvalidEncoding("a position holds 1 to 31 bytes")),
+        }
+    }
+
+    fn domain(&self) -> Result<EvaluationDomain, Error> {
+        EvaluationDomain::new(self.domain_size).ok_or(Error::InvalidEncoding(
+            "domain size is too large for the field",
+        ))
+    }
+}
+
+fn encode(bytes: &[u8], info: &EncodingInfo) -> Result<DomainPolynomial, Error> {
+    let mut scalars = pack_bytes(bytes);
+    scalars.resize(info.domain_size, Default::default());
+    // Can unwrap since there's exactly one value per domain element
+    Ok(DomainPolynomial::from_evaluations(info.domain()?, scalars).unwrap())
+}
+
+impl GenericPolynomialCommitment {
+    // Packs, pads, interpolates and commits. The commitment records the domain, so it verifies against
+    // `verifier_key.with_domain_size(info.domain_size)`
+    pub fn commit_bytes(&self, bytes: &[u8]) -> Result<(Commitment, EncodingInfo), Error> {
+        let info = EncodingInfo {
+            byte_len: bytes.len(),
+            domain_size: bytes
+                .len()
+                .div_ceil(BYTES_PER_SCALAR)
+                .max(1)
+                .next_power_of_two(),
+        };
+        let polynomial = encode(bytes, &info)?;
+        let point = self.msm(polynomial.coefficients().coeffs())?;
+        let commitment = Commitment::from_parts(point, self.max_degree()?, Some(info.domain_size));
+        Ok((commitment, info))
+    }
+
+    // The value at position `index` and a proof of it. `bytes` and `info` must be the ones `commit_bytes` was given
+    // and returned; the encoding is redone, so proving many positions is best done with `prove_positions`
+    pub fn prove_position(
+        &self,
+        bytes: &[u8],
+        info: &EncodingInfo,
+        index: usize,
+    ) -> Result<(Proof, FieldElement), Error> {
+        Ok(self.prove_positions(bytes, info, &[index])?.remove(0))
+    }
+
+    pub fn prove_positions(
+        &self,
+        bytes: &[u8],
+        info: &EncodingInfo,
+        indices: &[usize],
+    ) -> Result<Vec<(Proof, FieldElement)>, Error> {
+        if bytes.len() != info.byte_len {
+            return Err(Error::InvalidEncoding(
+                "bytes don't match the encoding info",
+            ));
+        }
+        let polynomial = encode(bytes, info)?;
+        indices
+            .iter()
+            .map(|&index| {
+                let (witness, evaluation) =
+                    self.open(polynomial.coefficients(), info.point(index)?.inner())?;
+                Ok((
+                    Proof::from_inner(witness),
+                    FieldElement::from_inner(evaluation),
+                ))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn commits_to_bytes_and_proves_positions() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let key = committer
+        .setup_with_rng(7, &mut test_rng())
+        .verifier_key()
+        .unwrap();
+
+    // 100 bytes is four scalars, the last holding 7 bytes
+    let bytes: Vec<u8> = (0..100u32).map(|i| (i * 13 % 256) as u8).collect();
+    let (commitment, info) = committer.commit_bytes(&bytes).unwrap();
+    assert_eq!(
+        info,
+        EncodingInfo {
+            byte_len: 100,
+            domain_size: 4
+        }
+    );
+    assert_eq!(info.len(), 4);
+
+    let key = key.with_domain_size(info.domain_size);
+    let openings = committer
+        .prove_positions(&bytes, &info, &[0, 1, 2, 3])
+        .unwrap();
+    for (index, (proof, value)) in openings.iter().enumerate() {
+        let stored =
+            &bytes[index * BYTES_PER_SCALAR..bytes.len().min((index + 1) * BYTES_PER_SCALAR)];
+        assert_eq!(EncodingInfo::value(stored), Ok(*value));
+        let point = info.point(index).unwrap();
+        assert_eq!(commitment.verify(&key, point, *value, proof), Ok(()));
+        assert!(commitment
+            .verify(&key, point, *value + FieldElement::one(), proof)
+            .is_err());
+    }
+    assert_eq!(
+        committer.prove_position(&bytes, &info, 2).unwrap(),
+        openings[2]
+    );
+
+    assert_eq!(
+        committer.prove_position(&bytes, &info, 4),
+        Err(Error::IndexOutOfRange { index: 4, len: 4 })
+    );
+    assert!(committer.prove_position(&bytes[1..], &info, 0).is_err());
+    // 300 bytes needs 16 scalars, more than degree 7 parameters cover
+    assert!(committer.commit_bytes(&[1; 300]).is_err());
+}
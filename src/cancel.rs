@@ -0,0 +1,136 @@
+use crate::domain::EvaluationDomain;
+use crate::error::Error;
+use crate::polynomial_commitments::{GenericPolynomialCommitment, GlobalParameters};
+use crate::polynomials::Polynomial;
+use crate::progress::MSM_CHUNK_SIZE;
+use blstrs::{G1Projective, Scalar};
+use group::ff::Field;
+use group::Group;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// Lets a service abort proving work whose request has gone away. Clones share one flag: the service keeps one and
+// cancels it, and the `_cancellable` operations holding the other check it between chunks of work and return
+// `Error::Cancelled`. A chunk already started runs to its end, so cancellation takes effect within one partial MSM or
+// FFT layer
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<(), Error> {
+        if self.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+        Ok(())
+    }
+}
+
+impl GlobalParameters {
+    // `commit_in_g1`, as a sequence of partial MSMs with a check before each
+    pub fn commit_in_g1_cancellable(
+        &self,
+        coefficients: &[Scalar],
+        cancel: &CancellationToken,
+    ) -> Result<G1Projective, Error> {
+        if coefficients.len() > self.gs().len() {
+            return Err(Error::DegreeTooLarge {
+                max: self.gs().len() - 1,
+                actual: coefficients.len() - 1,
+            });
+        }
+        let mut result = G1Projective::identity();
+        for (bases, scalars) in self
+            .gs()
+            .chunks(MSM_CHUNK_SIZE)
+            .zip(coefficients.chunks(MSM_CHUNK_SIZE))
+        {
+            cancel.check()?;
+            result += G1Projective::multi_exp(&bases[..scalars.len()], scalars);
+        }
+        Ok(result)
+    }
+}
+
+impl EvaluationDomain {
+    // `fft_in_place`, checking between butterfly layers. The values are left partially transformed if it's cancelled
+    pub fn fft_in_place_cancellable(
+        &self,
+        values: &mut [Scalar],
+        cancel: &CancellationToken,
+    ) -> Result<(), Error> {
+        self.transform_in_place(values, false, Some(cancel))
+    }
+
+    pub fn ifft_in_place_cancellable(
+        &self,
+        values: &mut [Scalar],
+        cancel: &CancellationToken,
+    ) -> Result<(), Error> {
+        self.transform_in_place(values, true, Some(cancel))
+    }
+}
+
+impl GenericPolynomialCommitment {
+    // `open`, for polynomials large enough that the witness MSM is worth interrupting
+    pub fn open_cancellable(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+        cancel: &CancellationToken,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        let global_parameters = self.global_parameters()?;
+        cancel.check()?;
+        // The remainder of dividing by $x - z$ is $p(z)$
+        let (quotient, remainder) = polynomial.div_rem(&Polynomial::new(&[-point, Scalar::ONE]))?;
+        let evaluation = remainder.coeffs().first().copied().unwrap_or(Scalar::ZERO);
+        let witness = global_parameters.commit_in_g1_cancellable(quotient.coeffs(), cancel)?;
+        Ok((witness, evaluation))
+    }
+}
+
+#[test]
+fn cancelled_operations_stop() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(7, &mut test_rng());
+    let polynomial = Polynomial::rand(7, &mut test_rng());
+    let domain = EvaluationDomain::new(8).unwrap();
+
+    let cancel = CancellationToken::new();
+    assert_eq!(
+        committer
+            .open_cancellable(&polynomial, Scalar::from(3), &cancel)
+            .unwrap(),
+        committer.open(&polynomial, Scalar::from(3)).unwrap()
+    );
+    let mut values = polynomial.coeffs().to_vec();
+    domain
+        .fft_in_place_cancellable(&mut values, &cancel)
+        .unwrap();
+    assert_eq!(values, domain.fft(polynomial.coeffs()));
+
+    // Cancelling through a clone is seen by everything sharing the token
+    cancel.clone().cancel();
+    assert_eq!(
+        committer.open_cancellable(&polynomial, Scalar::from(3), &cancel),
+        Err(Error::Cancelled)
+    );
+    assert_eq!(
+        domain.ifft_in_place_cancellable(&mut values, &cancel),
+        Err(Error::Cancelled)
+    );
+}
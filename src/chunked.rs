@@ -0,0 +1,231 @@
+use crate::domain::EvaluationDomain;
+use crate::error::Error;
+use crate::merkle::{hash_leaf, Hash, MerklePath, MerkleTree};
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::polynomials::Polynomial;
+use blstrs::{G1Projective, Scalar};
+use group::ff::Field;
+use group::Curve;
+
+// Two-level commitments for data too large for one polynomial. The data is split into chunks of `chunk_size` scalars,
+// each chunk is interpolated over an evaluation domain and KZG-committed, and the chunk commitments are Merkleized.
+// The root is the commitment to everything. Proving element i opens chunk i / chunk_size at the (i % chunk_size)-th
+// domain element and includes the Merkle path to that chunk's commitment, so the SRS only needs to cover one chunk
+pub struct ChunkedData {
+    domain: EvaluationDomain,
+    len: usize,
+    chunks: Vec<Polynomial>,
+    commitments: Vec<G1Projective>,
+    tree: MerkleTree,
+}
+
+// Everything a verifier needs to hold: the Merkle root and the layout of the data under it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChunkedCommitment {
+    pub root: Hash,
+    pub chunk_size: usize,
+    pub len: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChunkedProof {
+    pub chunk_commitment: G1Projective,
+    pub path: MerklePath,
+    pub witness: G1Projective,
+}
+
+pub(crate) fn commitment_leaf(commitment: &G1Projective) -> Hash {
+    hash_leaf(&commitment.to_affine().to_compressed())
+}
+
+// Builds a `ChunkedData` one chunk at a time, so the input can be streamed rather than held in memory all at once
+pub struct ChunkedDataBuilder<'a> {
+    committer: &'a GenericPolynomialCommitment,
+    domain: EvaluationDomain,
+    len: usize,
+    chunks: Vec<Polynomial>,
+    commitments: Vec<G1Projective>,
+}
+
+impl<'a> ChunkedDataBuilder<'a> {
+    // `chunk_size` must be a power of two no larger than the number of powers of tau
+    pub fn new(
+        committer: &'a GenericPolynomialCommitment,
+        chunk_size: usize,
+    ) -> Result<Self, Error> {
+        let global_parameters = committer.global_parameters()?;
+        if !chunk_size.is_power_of_two() || chunk_size > global_parameters.gs().len() {
+            return Err(Error::InvalidParameters(
+                "chunk size must be a power of two covered by the setup",
+            ));
+        }
+        let domain = EvaluationDomain::new(chunk_size).ok_or(Error::InvalidParameters(
+            "chunk size is too large for an evaluation domain",
+        ))?;
+        Ok(ChunkedDataBuilder {
+            committer,
+            domain,
+            len: 0,
+            chunks: vec![],
+            commitments: vec![],
+        })
+    }
+
+    // Every chunk but the last must be full. A short chunk is padded with zeros and ends the data
+    pub fn push_chunk(&mut self, chunk: &[Scalar]) -> Result<(), Error> {
+        let chunk_size = self.domain.size();
+        if chunk.len() > chunk_size || self.len % chunk_size != 0 {
+            return Err(Error::InvalidParameters(
+                "only the last chunk may be shorter than the chunk size",
+            ));
+        }
+        let mut evaluations = chunk.to_vec();
+        evaluations.resize(chunk_size, Scalar::ZERO);
+        let polynomial = Polynomial::new(&self.domain.ifft(&evaluations));
+        self.commitments
+            .push(self.committer.msm(polynomial.coeffs())?);
+        self.chunks.push(polynomial);
+        self.len += chunk.len();
+        Ok(())
+    }
+
+    pub fn finish(self) -> ChunkedData {
+        let tree = MerkleTree::new(self.commitments.iter().map(commitment_leaf).collect());
+        ChunkedData {
+            domain: self.domain,
+            len: self.len,
+            chunks: self.chunks,
+            commitments: self.commitments,
+            tree,
+        }
+    }
+}
+
+impl ChunkedData {
+    // Commit to data that's already in memory. The last chunk is padded with zeros
+    pub fn commit(
+        committer: &GenericPolynomialCommitment,
+        data: &[Scalar],
+        chunk_size: usize,
+    ) -> Result<Self, Error> {
+        let mut builder = ChunkedDataBuilder::new(committer, chunk_size)?;
+        for chunk in data.chunks(chunk_size) {
+            builder.push_chunk(chunk)?;
+        }
+        Ok(builder.finish())
+    }
+
+    pub fn commitment(&self) -> ChunkedCommitment {
+        ChunkedCommitment {
+            root: self.tree.root(),
+            chunk_size: self.domain.size(),
+            len: self.len,
+        }
+    }
+
+    // One per chunk, in order; the leaves of the Merkle tree
+    pub(crate) fn chunk_commitments(&self) -> &[G1Projective] {
+        &self.commitments
+    }
+
+    // The element at `index` and a proof that it's there
+    pub fn prove(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        index: usize,
+    ) -> Result<(Scalar, ChunkedProof), Error> {
+        if index >= self.len {
+            return Err(Error::IndexOutOfRange {
+                index,
+                len: self.len,
+            });
+        }
+        let chunk_index = index / self.domain.size();
+        let point = self.domain.element(index % self.domain.size());
+        let (witness, value) = committer.open(&self.chunks[chunk_index], point)?;
+        let proof = ChunkedProof {
+            chunk_commitment: self.commitments[chunk_index],
+            // Every chunk has a leaf
+            path: self.tree.path(chunk_index).unwrap(),
+            witness,
+        };
+        Ok((value, proof))
+    }
+}
+
+impl ChunkedCommitment {
+    pub fn verify(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        index: usize,
+        value: Scalar,
+        proof: &ChunkedProof,
+    ) -> Result<bool, Error> {
+        if index >= self.len {
+            return Err(Error::IndexOutOfRange {
+                index,
+                len: self.len,
+            });
+        }
+        let domain = EvaluationDomain::new(self.chunk_size)
+            .filter(|domain| domain.size() == self.chunk_size)
+            .ok_or(Error::InvalidParameters(
+                "chunk size must be a power of two",
+            ))?;
+        if proof.path.index != index / self.chunk_size
+            || !proof
+                .path
+                .verify(&self.root, &commitment_leaf(&proof.chunk_commitment))
+        {
+            return Ok(false);
+        }
+        committer.check_opening(
+            proof.chunk_commitment,
+            domain.element(index % self.chunk_size),
+            value,
+            proof.witness,
+        )
+    }
+}
+
+#[test]
+fn proves_elements_of_chunked_data() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(7, &mut rng);
+
+    // Three chunks of eight, the last one partial, with a run of zeros in the middle
+    let mut data: Vec<Scalar> = (0..21).map(|_| Scalar::random(&mut rng)).collect();
+    for value in data[8..16].iter_mut() {
+        *value = Scalar::ZERO;
+    }
+    let chunked = ChunkedData::commit(&committer, &data, 8).unwrap();
+    let commitment = chunked.commitment();
+
+    for index in [0, 7, 8, 12, 20] {
+        let (value, proof) = chunked.prove(&committer, index).unwrap();
+        assert_eq!(value, data[index]);
+        assert_eq!(
+            commitment.verify(&committer, index, value, &proof),
+            Ok(true)
+        );
+        assert_eq!(
+            commitment.verify(&committer, index, value + Scalar::ONE, &proof),
+            Ok(false)
+        );
+    }
+
+    // A valid proof for one position doesn't vouch for another
+    let (value, proof) = chunked.prove(&committer, 3).unwrap();
+    assert_eq!(commitment.verify(&committer, 11, value, &proof), Ok(false));
+
+    assert_eq!(
+        chunked.prove(&committer, 21).unwrap_err(),
+        Error::IndexOutOfRange { index: 21, len: 21 }
+    );
+    assert!(ChunkedData::commit(&committer, &data, 6).is_err());
+    assert!(ChunkedData::commit(&committer, &data, 16).is_err());
+}
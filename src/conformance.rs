@@ -0,0 +1,288 @@
+use crate::error::Error;
+use crate::polynomial_commitments::{GlobalParameters, PolynomialCommitment};
+use crate::polynomials::Polynomial;
+use blstrs::{G1Affine, G1Projective, Scalar};
+use group::ff::Field;
+use group::Curve;
+use rand::{CryptoRng, RngCore};
+
+// A test suite any `PolynomialCommitment` backend should pass, for third-party schemes and for refactors of this
+// one. Polynomials are drawn at exactly the setup's max degree, which every backend has to support. Checks:
+//
+// - round trips: commitments are deterministic, honest openings report the polynomial's value and verify
+// - soundness: a wrong value, a wrong point, another point's witness and another polynomial's commitment are all
+//   rejected. Rejecting with an error is as good as `Ok(false)`
+// - serialization: the parameters survive `to_bytes` and `from_bytes`, and commitments and witnesses still verify
+//   after a trip through their compressed encodings
+//
+// The round trip and soundness checks take any backend. Serialization, and so `run_conformance`, needs KZG's types:
+// global parameters from setup and commitments and witnesses in G1
+
+// Which check failed, and on which iteration
+#[derive(Debug, Eq, PartialEq)]
+pub struct ConformanceFailure {
+    pub iteration: usize,
+    pub check: &'static str,
+}
+
+// Runs setup for `max_degree` and then every check
+pub fn run_conformance<
+    P: PolynomialCommitment<
+        Parameters = GlobalParameters,
+        Commitment = G1Projective,
+        Witness = G1Projective,
+    >,
+    R: RngCore + CryptoRng,
+>(
+    scheme: &mut P,
+    max_degree: usize,
+    iterations: usize,
+    rng: &mut R,
+) -> Result<(), ConformanceFailure> {
+    let global_parameters = scheme.setup_with_rng(max_degree, &mut *rng);
+    check_round_trips(scheme, max_degree, iterations, rng)?;
+    check_soundness(scheme, max_degree, iterations, rng)?;
+    check_serialization(scheme, &global_parameters, max_degree, iterations, rng)
+}
+
+// A random polynomial and a random point to open it at
+fn instance<R: RngCore + CryptoRng>(max_degree: usize, rng: &mut R) -> (Polynomial, Scalar) {
+    (
+        Polynomial::rand(max_degree, &mut *rng),
+        Scalar::random(&mut *rng),
+    )
+}
+
+fn accepted(result: Result<bool, Error>) -> bool {
+    result == Ok(true)
+}
+
+pub fn check_round_trips<P: PolynomialCommitment, R: RngCore + CryptoRng>(
+    scheme: &P,
+    max_degree: usize,
+    iterations: usize,
+    rng: &mut R,
+) -> Result<(), ConformanceFailure>
+where
+    P::Commitment: Clone + PartialEq,
+    P::Witness: Clone,
+{
+    for iteration in 0..iterations {
+        let fail = |check| ConformanceFailure { iteration, check };
+        let (polynomial, point) = instance(max_degree, rng);
+        let commitment = scheme.commit(&polynomial).map_err(|_| fail("commit"))?;
+        if scheme.commit(&polynomial) != Ok(commitment.clone()) {
+            return Err(fail("commitment is deterministic"));
+        }
+        let (witness, evaluation) = scheme
+            .create_witness(&polynomial, point)
+            .map_err(|_| fail("open"))?;
+        if evaluation != polynomial.evaluate(point) {
+            return Err(fail("evaluation"));
+        }
+        if !accepted(scheme.verify_evaluation(commitment, point, evaluation, witness)) {
+            return Err(fail("honest opening verifies"));
+        }
+    }
+    Ok(())
+}
+
+pub fn check_soundness<P: PolynomialCommitment, R: RngCore + CryptoRng>(
+    scheme: &P,
+    max_degree: usize,
+    iterations: usize,
+    rng: &mut R,
+) -> Result<(), ConformanceFailure>
+where
+    P::Commitment: Clone,
+    P::Witness: Clone,
+{
+    for iteration in 0..iterations {
+        let fail = |check| ConformanceFailure { iteration, check };
+        let (polynomial, point) = instance(max_degree, rng);
+        let (other_polynomial, other_point) = instance(max_degree, rng);
+        let commitment = scheme.commit(&polynomial).map_err(|_| fail("commit"))?;
+        let other_commitment = scheme
+            .commit(&other_polynomial)
+            .map_err(|_| fail("commit"))?;
+        let (witness, evaluation) = scheme
+            .create_witness(&polynomial, point)
+            .map_err(|_| fail("open"))?;
+        let (other_witness, _) = scheme
+            .create_witness(&polynomial, other_point)
+            .map_err(|_| fail("open"))?;
+
+        let forgeries = [
+            (
+                "wrong value rejected",
+                scheme.verify_evaluation(
+                    commitment.clone(),
+                    point,
+                    evaluation + Scalar::ONE,
+                    witness.clone(),
+                ),
+            ),
+            (
+                "wrong point rejected",
+                scheme.verify_evaluation(
+                    commitment.clone(),
+                    other_point,
+                    evaluation,
+                    witness.clone(),
+                ),
+            ),
+            (
+                "another point's witness rejected",
+                scheme.verify_evaluation(commitment, point, evaluation, other_witness),
+            ),
+            (
+                "another polynomial's commitment rejected",
+                scheme.verify_evaluation(other_commitment, point, evaluation, witness),
+            ),
+        ];
+        for (check, result) in forgeries {
+            if accepted(result) {
+                return Err(fail(check));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn through_bytes(point: &G1Projective) -> Option<G1Projective> {
+    Option::<G1Affine>::from(G1Affine::from_compressed(
+        &point.to_affine().to_compressed(),
+    ))
+    .map(G1Projective::from)
+}
+
+// `global_parameters` must be the ones `scheme` was set up with
+pub fn check_serialization<
+    P: PolynomialCommitment<
+        Parameters = GlobalParameters,
+        Commitment = G1Projective,
+        Witness = G1Projective,
+    >,
+    R: RngCore + CryptoRng,
+>(
+    scheme: &P,
+    global_parameters: &GlobalParameters,
+    max_degree: usize,
+    iterations: usize,
+    rng: &mut R,
+) -> Result<(), ConformanceFailure> {
+    let decoded = GlobalParameters::from_bytes(&global_parameters.to_bytes()).map_err(|_| {
+        ConformanceFailure {
+            iteration: 0,
+            check: "parameters decode",
+        }
+    })?;
+    if decoded.fingerprint() != global_parameters.fingerprint() {
+        return Err(ConformanceFailure {
+            iteration: 0,
+            check: "parameters round trip",
+        });
+    }
+
+    for iteration in 0..iterations {
+        let fail = |check| ConformanceFailure { iteration, check };
+        let (polynomial, point) = instance(max_degree, rng);
+        let commitment = scheme.commit(&polynomial).map_err(|_| fail("commit"))?;
+        let (witness, evaluation) = scheme
+            .create_witness(&polynomial, point)
+            .map_err(|_| fail("open"))?;
+        let (commitment, witness) = through_bytes(&commitment)
+            .zip(through_bytes(&witness))
+            .ok_or_else(|| fail("points decode"))?;
+        let evaluation = Option::<Scalar>::from(Scalar::from_bytes_le(&evaluation.to_bytes_le()))
+            .ok_or_else(|| fail("evaluation decodes"))?;
+        if !accepted(scheme.verify_evaluation(commitment, point, evaluation, witness)) {
+            return Err(fail("decoded opening verifies"));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn the_kzg_backend_conforms() {
+    use crate::polynomial_commitments::GenericPolynomialCommitment;
+    use crate::rng::test_rng;
+
+    assert_eq!(
+        run_conformance(
+            &mut GenericPolynomialCommitment::new(),
+            6,
+            3,
+            &mut test_rng()
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn the_linear_code_backend_conforms() {
+    use crate::linear_code::{LinearCodeCommitment, ReedSolomon};
+    use crate::rng::test_rng;
+
+    let mut scheme = LinearCodeCommitment::new(ReedSolomon::new(4, 4).unwrap(), 1, 16);
+    scheme.setup_with_rng(10, &mut test_rng());
+    assert_eq!(check_round_trips(&scheme, 10, 3, &mut test_rng()), Ok(()));
+    assert_eq!(check_soundness(&scheme, 10, 3, &mut test_rng()), Ok(()));
+}
+
+#[test]
+fn catches_a_backend_that_accepts_anything() {
+    use crate::polynomial_commitments::GenericPolynomialCommitment;
+    use crate::rng::test_rng;
+
+    struct Credulous(GenericPolynomialCommitment);
+
+    impl PolynomialCommitment for Credulous {
+        type Parameters = GlobalParameters;
+        type Commitment = G1Projective;
+        type Witness = G1Projective;
+
+        fn setup_with_rng<R: RngCore + CryptoRng>(
+            &mut self,
+            d: usize,
+            rng: &mut R,
+        ) -> GlobalParameters {
+            self.0.setup_with_rng(d, rng)
+        }
+
+        fn commit(&self, polynomial: &Polynomial) -> Result<G1Projective, Error> {
+            self.0.commit(polynomial)
+        }
+
+        fn create_witness(
+            &self,
+            polynomial: &Polynomial,
+            point: Scalar,
+        ) -> Result<(G1Projective, Scalar), Error> {
+            self.0.create_witness(polynomial, point)
+        }
+
+        fn verify_evaluation(
+            &self,
+            _: G1Projective,
+            _: Scalar,
+            _: Scalar,
+            _: G1Projective,
+        ) -> Result<bool, Error> {
+            Ok(true)
+        }
+    }
+
+    assert_eq!(
+        run_conformance(
+            &mut Credulous(GenericPolynomialCommitment::new()),
+            4,
+            2,
+            &mut test_rng()
+        ),
+        Err(ConformanceFailure {
+            iteration: 0,
+            check: "wrong value rejected"
+        })
+    );
+}
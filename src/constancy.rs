@@ -0,0 +1,84 @@
+use crate::constant_time::ct_eq_g1;
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::polynomials::Polynomial;
+use blstrs::{G1Projective, Scalar};
+use group::Group;
+
+// Checks that a commitment is to the zero polynomial or to a given constant. A constant c commits to $c [\tau^0]_1$,
+// and binding means no other polynomial in range of the parameters commits to the same point, so the commitment is
+// its own proof: the verifier recomputes $[c]_1$ and compares, with no witness and no pairing. This replaces opening
+// at a random point and checking the value, which only catches a non-constant polynomial with high probability
+impl GenericPolynomialCommitment {
+    // The constant, for the prover to send along. Errs unless the polynomial has degree 0 (or is zero)
+    pub fn prove_constant(&self, polynomial: &Polynomial) -> Result<Scalar, Error> {
+        self.global_parameters()?;
+        match polynomial.degree() {
+            None | Some(0) => Ok(polynomial.coeff(0)),
+            Some(degree) => Err(Error::IncorrectDegree {
+                expected: 0,
+                actual: degree,
+            }),
+        }
+    }
+
+    pub fn verify_constant(&self, commitment: G1Projective, value: Scalar) -> Result<bool, Error> {
+        let g = self.global_parameters()?.gs()[0];
+        Ok(ct_eq_g1(&commitment, &(g * value)).into())
+    }
+
+    // The zero polynomial commits to the identity, which `verify_evaluation` rejects outright
+    pub fn verify_zero(&self, commitment: G1Projective) -> Result<bool, Error> {
+        self.global_parameters()?;
+        Ok(bool::from(commitment.is_identity()))
+    }
+}
+
+#[test]
+fn checks_zero_and_constant_commitments() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let gp = committer.setup_with_rng(3, &mut test_rng());
+    let constant = Polynomial::new_from_i64(&[7]);
+    let commitment = gp.commit_in_g1(constant.coeffs()).unwrap();
+
+    let value = committer.prove_constant(&constant).unwrap();
+    assert_eq!(value, Scalar::from(7));
+    assert_eq!(committer.verify_constant(commitment, value), Ok(true));
+    assert_eq!(
+        committer.verify_constant(commitment, Scalar::from(8)),
+        Ok(false)
+    );
+    assert_eq!(committer.verify_zero(commitment), Ok(false));
+
+    // A polynomial that's 7 at the point a careless check might pick
+    let linear = Polynomial::new_from_i64(&[7, 1]);
+    let linear_commitment = gp.commit_in_g1(linear.coeffs()).unwrap();
+    assert_eq!(linear.evaluate(Scalar::from(0)), value);
+    assert_eq!(
+        committer.verify_constant(linear_commitment, value),
+        Ok(false)
+    );
+    assert_eq!(
+        committer.prove_constant(&linear),
+        Err(Error::IncorrectDegree {
+            expected: 0,
+            actual: 1
+        })
+    );
+
+    let zero = Polynomial::new(&[]);
+    assert_eq!(committer.prove_constant(&zero), Ok(Scalar::from(0)));
+    let zero_commitment = gp.commit_in_g1(zero.coeffs()).unwrap();
+    assert_eq!(committer.verify_zero(zero_commitment), Ok(true));
+    assert_eq!(
+        committer.verify_constant(zero_commitment, Scalar::from(0)),
+        Ok(true)
+    );
+    assert_eq!(
+        GenericPolynomialCommitment::new().verify_zero(zero_commitment),
+        Err(Error::SetupIncomplete)
+    );
+}
@@ -0,0 +1,32 @@
+use blstrs::{G1Projective, G2Projective, Gt};
+use group::Group;
+use subtle::Choice;
+
+// blstrs' `==` on group elements isn't documented to be constant time, so compare via the difference instead:
+// subtraction and the identity check don't branch on the values involved
+pub fn ct_eq_g1(a: &G1Projective, b: &G1Projective) -> Choice {
+    (a - b).is_identity()
+}
+
+pub fn ct_eq_g2(a: &G2Projective, b: &G2Projective) -> Choice {
+    (a - b).is_identity()
+}
+
+pub fn ct_eq_gt(a: &Gt, b: &Gt) -> Choice {
+    (*a - *b).is_identity()
+}
+
+#[test]
+fn compares_group_elements() {
+    use blstrs::Scalar;
+
+    let a = G1Projective::generator() * Scalar::from(5);
+    let b =
+        G1Projective::generator() * Scalar::from(2) + G1Projective::generator() * Scalar::from(3);
+    assert!(bool::from(ct_eq_g1(&a, &b)));
+    assert!(!bool::from(ct_eq_g1(&a, &G1Projective::generator())));
+
+    let c = G2Projective::generator() * Scalar::from(5);
+    assert!(bool::from(ct_eq_g2(&c, &c)));
+    assert!(!bool::from(ct_eq_g2(&c, &G2Projective::generator())));
+}
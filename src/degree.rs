@@ -0,0 +1,146 @@
+use crate::error::Error;
+#[cfg(feature = "prover")]
+use crate::polynomial_commitments::{GenericPolynomialCommitment, GlobalParameters};
+#[cfg(feature = "prover")]
+use crate::polynomials::Polynomial;
+#[cfg(feature = "prover")]
+use crate::types::{Commitment, FieldElement, Proof};
+
+// The degree a set of global parameters commits to. Keys, polynomials, commitments and proofs that carry one can be
+// checked against each other when they meet, instead of a polynomial of the wrong size surfacing as `IncorrectDegree`
+// deep inside `commit`
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct MaxDegree(usize);
+
+impl MaxDegree {
+    pub fn new(degree: usize) -> Self {
+        MaxDegree(degree)
+    }
+
+    pub fn get(self) -> usize {
+        self.0
+    }
+
+    pub(crate) fn check(self, actual: MaxDegree) -> Result<(), Error> {
+        if self != actual {
+            return Err(Error::DegreeMismatch {
+                expected: self.0,
+                actual: actual.0,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "prover")]
+impl GlobalParameters {
+    pub fn max_degree(&self) -> MaxDegree {
+        MaxDegree(self.gs().len() - 1)
+    }
+}
+
+// A polynomial whose degree was checked against a bound when it was built
+#[cfg(feature = "prover")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoundedPolynomial {
+    polynomial: Polynomial,
+    max_degree: MaxDegree,
+}
+
+#[cfg(feature = "prover")]
+impl BoundedPolynomial {
+    // The polynomial must have exactly the bound's degree, which is what `commit` requires
+    pub fn new(polynomial: Polynomial, max_degree: MaxDegree) -> Result<Self, Error> {
+        match polynomial.degree() {
+            None => Err(Error::ZeroPolynomial),
+            Some(degree) if degree != max_degree.0 => Err(Error::IncorrectDegree {
+                expected: max_degree.0,
+                actual: degree,
+            }),
+            Some(_) => Ok(BoundedPolynomial {
+                polynomial,
+                max_degree,
+            }),
+        }
+    }
+
+    pub fn polynomial(&self) -> &Polynomial {
+        &self.polynomial
+    }
+
+    pub fn max_degree(&self) -> MaxDegree {
+        self.max_degree
+    }
+}
+
+// Degree-checked versions of the scheme's operations. Each one errs with `DegreeMismatch` as soon as a polynomial
+// or commitment made for one degree meets parameters for another
+#[cfg(feature = "prover")]
+impl GenericPolynomialCommitment {
+    pub fn max_degree(&self) -> Result<MaxDegree, Error> {
+        Ok(self.global_parameters()?.max_degree())
+    }
+
+    // The commitment carries the degree, so `verify` checks it against the verifier's parameters
+    pub fn commit_bounded(&self, polynomial: &BoundedPolynomial) -> Result<Commitment, Error> {
+        self.max_degree()?.check(polynomial.max_degree)?;
+        self.commit_opaque(&polynomial.polynomial)
+    }
+
+    pub fn open_bounded(
+        &self,
+        polynomial: &BoundedPolynomial,
+        point: FieldElement,
+    ) -> Result<(Proof, FieldElement), Error> {
+        self.max_degree()?.check(polynomial.max_degree)?;
+        self.prove(&polynomial.polynomial, point)
+    }
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn catches_degree_mismatches_early() {
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut small = GenericPolynomialCommitment::new();
+    small.setup_with_rng(2, &mut rng);
+    let mut large = GenericPolynomialCommitment::new();
+    large.setup_with_rng(4, &mut rng);
+    assert_eq!(small.max_degree(), Ok(MaxDegree::new(2)));
+
+    let polynomial =
+        BoundedPolynomial::new(Polynomial::new_from_bytes(&[1, 2, 3]), MaxDegree::new(2)).unwrap();
+    assert_eq!(
+        BoundedPolynomial::new(Polynomial::new_from_bytes(&[1, 2]), MaxDegree::new(2)),
+        Err(Error::IncorrectDegree {
+            expected: 2,
+            actual: 1
+        })
+    );
+
+    let point = FieldElement::from(5);
+    let commitment = small.commit_bounded(&polynomial).unwrap();
+    let (proof, evaluation) = small.open_bounded(&polynomial, point).unwrap();
+    assert_eq!(commitment.max_degree(), MaxDegree::new(2));
+    assert_eq!(
+        small.verify(&commitment, point, evaluation, &proof),
+        Ok(true)
+    );
+
+    let mismatch = Error::DegreeMismatch {
+        expected: 4,
+        actual: 2,
+    };
+    assert_eq!(large.commit_bounded(&polynomial).unwrap_err(), mismatch);
+    assert_eq!(
+        large.open_bounded(&polynomial, point).unwrap_err(),
+        mismatch
+    );
+    assert_eq!(
+        large
+            .verify(&commitment, point, evaluation, &proof)
+            .unwrap_err(),
+        mismatch
+    );
+}
@@ -0,0 +1,126 @@
+use crate::ark::scalar_to_fr;
+use crate::polynomial_commitments::{
+    GenericPolynomialCommitment, GlobalParameters, PolynomialCommitment,
+};
+use crate::polynomials::Polynomial;
+use ark_bls12_381::{Bls12_381, Fr, G1Affine as ArkG1Affine, G2Affine as ArkG2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::Polynomial as _;
+use ark_poly_commit::kzg10::{Commitment, Powers, Proof, VerifierKey, KZG10};
+use ark_serialize::CanonicalSerialize;
+use blstrs::{G1Projective, G2Projective, Scalar};
+use group::ff::Field;
+use group::Curve;
+use rand::{CryptoRng, RngCore};
+use std::borrow::Cow;
+
+type ArkKzg = KZG10<Bls12_381, DensePolynomial<Fr>>;
+
+// Cross-checks this crate against arkworks' KZG10 on random polynomials and points: commitments and witnesses must be
+// byte-for-byte identical, evaluations equal, and both verifiers must agree on honest and tampered proofs. Both sides
+// are set up from the same tau, which is why this only exists for testing. c-kzg-4844 isn't covered: it only works
+// with blobs under the Ethereum setup, which this crate has no path for
+
+// Which check failed, and on which iteration
+#[derive(Debug, Eq, PartialEq)]
+pub struct Mismatch {
+    pub iteration: usize,
+    pub check: &'static str,
+}
+
+fn ark_bytes(point: &impl CanonicalSerialize) -> Vec<u8> {
+    let mut bytes = vec![];
+    // Can unwrap since writing to a Vec can't fail
+    point.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+pub fn run_differential<R: RngCore + CryptoRng>(
+    iterations: usize,
+    degree: usize,
+    rng: &mut R,
+) -> Result<(), Mismatch> {
+    let tau = Scalar::random(&mut *rng);
+    let tau_fr = scalar_to_fr(&tau);
+
+    let mut gs = vec![G1Projective::generator()];
+    let mut ark_gs = vec![ArkG1Affine::generator()];
+    for _ in 0..degree {
+        gs.push(gs[gs.len() - 1] * tau);
+        ark_gs.push((ark_gs[ark_gs.len() - 1] * tau_fr).into_affine());
+    }
+    let hs = vec![G2Projective::generator(), G2Projective::generator() * tau];
+    let committer = GenericPolynomialCommitment::with_global_parameters(
+        GlobalParameters::try_new(gs, hs).expect("parameters built from a tau are valid"),
+    );
+
+    let powers = Powers::<Bls12_381> {
+        powers_of_g: Cow::Owned(ark_gs.clone()),
+        powers_of_gamma_g: Cow::Owned(vec![]),
+    };
+    let h = ArkG2Affine::generator();
+    let beta_h = (h * tau_fr).into_affine();
+    let verifier_key = VerifierKey::<Bls12_381> {
+        g: ark_gs[0],
+        gamma_g: ark_gs[0],
+        h,
+        beta_h,
+        prepared_h: h.into(),
+        prepared_beta_h: beta_h.into(),
+    };
+
+    for iteration in 0..iterations {
+        let fail = |check| Mismatch { iteration, check };
+        let polynomial = Polynomial::rand(degree, &mut *rng);
+        let ark_polynomial = DensePolynomial::from(&polynomial);
+        let point = Scalar::random(&mut *rng);
+
+        let commitment = committer.commit(&polynomial).map_err(|_| fail("commit"))?;
+        let (ark_commitment, randomness) =
+            ArkKzg::commit(&powers, &ark_polynomial, None, None).map_err(|_| fail("ark commit"))?;
+        if commitment.to_affine().to_compressed().to_vec() != ark_bytes(&ark_commitment.0) {
+            return Err(fail("commitment bytes"));
+        }
+
+        let (witness, evaluation) = committer
+            .create_witness(&polynomial, point)
+            .map_err(|_| fail("open"))?;
+        let ark_proof = ArkKzg::open(&powers, &ark_polynomial, scalar_to_fr(&point), &randomness)
+            .map_err(|_| fail("ark open"))?;
+        if witness.to_affine().to_compressed().to_vec() != ark_bytes(&ark_proof.w) {
+            return Err(fail("witness bytes"));
+        }
+        if scalar_to_fr(&evaluation) != ark_polynomial.evaluate(&scalar_to_fr(&point)) {
+            return Err(fail("evaluation"));
+        }
+
+        for (claimed, expected) in [(evaluation, true), (evaluation + Scalar::ONE, false)] {
+            let ours = committer
+                .verify_evaluation(commitment, point, claimed, witness)
+                .map_err(|_| fail("verify"))?;
+            let theirs = ArkKzg::check(
+                &verifier_key,
+                &Commitment(ark_commitment.0),
+                scalar_to_fr(&point),
+                scalar_to_fr(&claimed),
+                &Proof {
+                    w: ark_proof.w,
+                    random_v: None,
+                },
+            )
+            .map_err(|_| fail("ark verify"))?;
+            if ours != expected || theirs != expected {
+                return Err(fail("verification outcome"));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn agrees_with_arkworks() {
+    use crate::rng::test_rng;
+
+    assert_eq!(run_differential(4, 7, &mut test_rng()), Ok(()));
+}
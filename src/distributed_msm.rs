@@ -0,0 +1,185 @@
+use crate::error::Error;
+use crate::polynomial_commitments::GlobalParameters;
+use blstrs::{G1Affine, G1Projective, Scalar};
+use group::{Curve, Group};
+
+// Splits the MSM behind a commitment or witness into independent tasks over disjoint ranges of the G1 powers of tau,
+// so a coordinator can hand them to other processes or machines that hold the same parameters. Each task and result
+// has a byte encoding for shipping it around; results are summed back together once every range is accounted for
+
+// The coefficients for `gs[start..start + scalars.len()]`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MsmTask {
+    pub start: usize,
+    pub scalars: Vec<Scalar>,
+}
+
+// The MSM over `gs[start..start + len]`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MsmPartialResult {
+    pub start: usize,
+    pub len: usize,
+    pub point: G1Projective,
+}
+
+// Split `coefficients` into at most `parts` tasks of nearly equal size
+pub fn split_msm(coefficients: &[Scalar], parts: usize) -> Vec<MsmTask> {
+    if coefficients.is_empty() {
+        return vec![];
+    }
+    let task_size = (coefficients.len() + parts.max(1) - 1) / parts.max(1);
+    coefficients
+        .chunks(task_size)
+        .enumerate()
+        .map(|(i, chunk)| MsmTask {
+            start: i * task_size,
+            scalars: chunk.to_vec(),
+        })
+        .collect()
+}
+
+impl MsmTask {
+    pub fn execute(&self, global_parameters: &GlobalParameters) -> Result<MsmPartialResult, Error> {
+        let gs = global_parameters.gs();
+        let end = self.start + self.scalars.len();
+        if end > gs.len() {
+            return Err(Error::IndexOutOfRange {
+                index: end - 1,
+                len: gs.len(),
+            });
+        }
+        let point = if self.scalars.is_empty() {
+            G1Projective::identity()
+        } else {
+            G1Projective::multi_exp(&gs[self.start..end], &self.scalars)
+        };
+        Ok(MsmPartialResult {
+            start: self.start,
+            len: self.scalars.len(),
+            point,
+        })
+    }
+
+    // The start as a little-endian u64, the number of scalars as a little-endian u32, then the scalars as 32
+    // little-endian bytes each
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(12 + 32 * self.scalars.len());
+        bytes.extend_from_slice(&(self.start as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.scalars.len() as u32).to_le_bytes());
+        for scalar in &self.scalars {
+            bytes.extend_from_slice(&scalar.to_bytes_le());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 12 {
+            return Err(Error::InvalidEncoding("too short for an MSM task"));
+        }
+        let start = u64::from_le_bytes(bytes[0..8].try_into().unwrap()) as usize;
+        let len = u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize;
+        let data = &bytes[12..];
+        if data.len() != len * 32 {
+            return Err(Error::InvalidEncoding(
+                "scalar count doesn't match the data length",
+            ));
+        }
+        let scalars = data
+            .chunks_exact(32)
+            .enumerate()
+            .map(|(index, chunk)| {
+                Option::<Scalar>::from(Scalar::from_bytes_le(chunk.try_into().unwrap()))
+                    .ok_or(Error::InvalidScalar { index })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(MsmTask { start, scalars })
+    }
+}
+
+const PARTIAL_RESULT_SIZE: usize = 16 + 48;
+
+impl MsmPartialResult {
+    // Start and length as little-endian u64s, then the compressed point
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(PARTIAL_RESULT_SIZE);
+        bytes.extend_from_slice(&(self.start as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.len as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.point.to_affine().to_compressed());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != PARTIAL_RESULT_SIZE {
+            return Err(Error::InvalidEncoding(
+                "wrong length for an MSM partial result",
+            ));
+        }
+        let start = u64::from_le_bytes(bytes[0..8].try_into().unwrap()) as usize;
+        let len = u64::from_le_bytes(bytes[8..16].try_into().unwrap()) as usize;
+        let point =
+            Option::<G1Affine>::from(G1Affine::from_compressed(bytes[16..].try_into().unwrap()))
+                .map(G1Projective::from)
+                .ok_or(Error::InvalidPoint {
+                    group: "G1",
+                    index: 0,
+                })?;
+        Ok(MsmPartialResult { start, len, point })
+    }
+}
+
+// Sum partial results, checking that together they cover `0..total_len` exactly once
+pub fn combine_msm_results(
+    results: &[MsmPartialResult],
+    total_len: usize,
+) -> Result<G1Projective, Error> {
+    let mut ranges: Vec<(usize, usize)> = results.iter().map(|r| (r.start, r.len)).collect();
+    ranges.sort_unstable();
+    let mut covered = 0;
+    for (start, len) in ranges {
+        if start != covered {
+            return Err(Error::InvalidShares(
+                "partial results overlap or leave a gap",
+            ));
+        }
+        covered += len;
+    }
+    if covered != total_len {
+        return Err(Error::InvalidShares(
+            "partial results don't cover every coefficient",
+        ));
+    }
+    Ok(results.iter().map(|r| r.point).sum())
+}
+
+#[test]
+fn distributed_msm_matches_commitment() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut committer = GenericPolynomialCommitment::new();
+    let global_parameters = committer.setup_with_rng(9, &mut rng);
+    let polynomial = Polynomial::rand(9, &mut rng);
+
+    let tasks = split_msm(polynomial.coeffs(), 3);
+    assert_eq!(tasks.len(), 3);
+
+    // Ship every task and result through bytes, as if they went to another machine and back
+    let results: Vec<MsmPartialResult> = tasks
+        .iter()
+        .map(|task| {
+            let task = MsmTask::from_bytes(&task.to_bytes()).unwrap();
+            let result = task.execute(&global_parameters).unwrap();
+            MsmPartialResult::from_bytes(&result.to_bytes()).unwrap()
+        })
+        .collect();
+    assert_eq!(
+        combine_msm_results(&results, 10),
+        Ok(committer.commit(&polynomial).unwrap())
+    );
+
+    assert!(combine_msm_results(&results[..2], 10).is_err());
+    let duplicated = [results[0], results[0], results[1], results[2]];
+    assert!(combine_msm_results(&duplicated, 10).is_err());
+}
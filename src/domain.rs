@@ -0,0 +1,392 @@
+use crate::cancel::CancellationToken;
+use crate::error::Error;
+use blstrs::{G1Projective, Scalar};
+use group::ff::{Field, PrimeField};
+use group::Group;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Below this many elements threading costs more than it saves
+#[cfg(feature = "parallel")]
+const PARALLEL_FFT_CUTOFF: usize = 1 << 10;
+
+// A multiplicative subgroup of the scalar field of power-of-two size, generated by a primitive root of unity. Used to
+// move polynomials between coefficient form and evaluation form with FFTs. The twiddle factors are computed once when
+// the domain is created, so transforms don't allocate
+#[derive(Clone, Debug)]
+pub struct EvaluationDomain {
+    size: usize,
+    log_size: u32,
+    generator: Scalar,
+    size_inv: Scalar,
+    // $\omega^0, \ldots, \omega^{n/2 - 1}$ and the same powers of $\omega^{-1}$
+    twiddles: Vec<Scalar>,
+    inverse_twiddles: Vec<Scalar>,
+}
+
+// A domain is determined by its size
+impl PartialEq for EvaluationDomain {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size
+    }
+}
+
+impl Eq for EvaluationDomain {}
+
+impl EvaluationDomain {
+    // The smallest domain with at least `min_size` elements, or `None` if that's larger than the field's 2-adicity
+    // allows
+    pub fn new(min_size: usize) -> Option<Self> {
+        let size = min_size.max(1).checked_next_power_of_two()?;
+        let log_size = size.trailing_zeros();
+        if log_size > Scalar::S {
+            return None;
+        }
+
+        // ROOT_OF_UNITY generates the subgroup of order 2^S; squaring it S - log_size times gives order 2^log_size
+        let mut generator = Scalar::ROOT_OF_UNITY;
+        for _ in log_size..Scalar::S {
+            generator = generator.square();
+        }
+
+        Some(EvaluationDomain {
+            size,
+            log_size,
+            generator,
+            size_inv: Scalar::from(size as u64).invert().unwrap(),
+            twiddles: powers(generator, size / 2),
+            inverse_twiddles: powers(generator.invert().unwrap(), size / 2),
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    // The primitive root of unity $\omega$ generating the domain
+    pub fn generator(&self) -> Scalar {
+        self.generator
+    }
+
+    // The i-th element of the domain, $\omega^i$
+    pub fn element(&self, i: usize) -> Scalar {
+        self.generator.pow_vartime([i as u64])
+    }
+
+    // All elements $1, \omega, \omega^2, \ldots$ in order
+    pub fn elements(&self) -> Vec<Scalar> {
+        let mut elements = Vec::with_capacity(self.size);
+        let mut element = Scalar::ONE;
+        for _ in 0..self.size {
+            elements.push(element);
+            element *= self.generator;
+        }
+        elements
+    }
+
+    // Coefficients to evaluations over the domain, in place. The slice must be exactly the domain's size
+    pub fn fft_in_place(&self, values: &mut [Scalar]) {
+        // Can unwrap since there's no token to cancel it
+        self.transform_in_place(values, false, None).unwrap();
+    }
+
+    // Evaluations over the domain back to coefficients, in place
+    pub fn ifft_in_place(&self, values: &mut [Scalar]) {
+        self.transform_in_place(values, true, None).unwrap();
+    }
+
+    pub(crate) fn transform_in_place(
+        &self,
+        values: &mut [Scalar],
+        inverse: bool,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<(), Error> {
+        assert_eq!(
+            values.len(),
+            self.size,
+            "slice length must match the domain size"
+        );
+        if !inverse {
+            return butterflies(values, &self.twiddles, cancel);
+        }
+        butterflies(values, &self.inverse_twiddles, cancel)?;
+        for value in values.iter_mut() {
+            *value *= self.size_inv;
+        }
+        Ok(())
+    }
+
+    // Coefficients to evaluations over the domain. Inputs shorter than the domain are zero padded; longer inputs
+    // are rejected since they'd silently wrap around
+    pub fn fft(&self, coefficients: &[Scalar]) -> Vec<Scalar> {
+        assert!(
+            coefficients.len() <= self.size,
+            "more coefficients than domain elements"
+        );
+        let mut values = coefficients.to_vec();
+        values.resize(self.size, Scalar::ZERO);
+        self.fft_in_place(&mut values);
+        values
+    }
+
+    // Evaluations over the domain back to coefficients
+    pub fn ifft(&self, evaluations: &[Scalar]) -> Vec<Scalar> {
+        assert!(
+            evaluations.len() <= self.size,
+            "more evaluations than domain elements"
+        );
+        let mut values = evaluations.to_vec();
+        values.resize(self.size, Scalar::ZERO);
+        self.ifft_in_place(&mut values);
+        values
+    }
+
+    // The same transforms over G1: the "coefficients" are points and the scalars multiply them. Moves SRS points and
+    // commitments between the monomial and Lagrange bases. Single-threaded, since each layer is group additions
+    pub fn fft_g1(&self, points: &[G1Projective]) -> Vec<G1Projective> {
+        self.transform_g1(points, false)
+    }
+
+    pub fn ifft_g1(&self, points: &[G1Projective]) -> Vec<G1Projective> {
+        self.transform_g1(points, true)
+    }
+
+    fn transform_g1(&self, points: &[G1Projective], inverse: bool) -> Vec<G1Projective> {
+        assert!(
+            points.len() <= self.size,
+            "more points than domain elements"
+        );
+        let mut values = points.to_vec();
+        values.resize(self.size, G1Projective::identity());
+        let log_n = self.log_size;
+        for i in 0..values.len() {
+            let j = bit_reverse(i, log_n);
+            if i < j {
+                values.swap(i, j);
+            }
+        }
+        let twiddles = if inverse {
+            &self.inverse_twiddles
+        } else {
+            &self.twiddles
+        };
+        let mut half = 1;
+        while half < self.size {
+            let stride = self.size / (2 * half);
+            for block in values.chunks_exact_mut(2 * half) {
+                let (low, high) = block.split_at_mut(half);
+                for (j, (a, b)) in low.iter_mut().zip(high.iter_mut()).enumerate() {
+                    let t = *b * twiddles[j * stride];
+                    *b = *a - t;
+                    *a += t;
+                }
+            }
+            half *= 2;
+        }
+        if inverse {
+            for value in values.iter_mut() {
+                *value *= self.size_inv;
+            }
+        }
+        values
+    }
+}
+
+fn powers(base: Scalar, count: usize) -> Vec<Scalar> {
+    let mut powers = Vec::with_capacity(count);
+    let mut power = Scalar::ONE;
+    for _ in 0..count {
+        powers.push(power);
+        power *= base;
+    }
+    powers
+}
+
+pub(crate) fn bit_reverse(i: usize, log_n: u32) -> usize {
+    // A shift by the full width would overflow, and the only index of a size-1 domain is 0
+    if log_n == 0 {
+        return 0;
+    }
+    i.reverse_bits() >> (usize::BITS - log_n)
+}
+
+// Iterative radix-2 Cooley-Tukey over a slice whose length n is a power of two. `twiddles` holds the first n/2 powers
+// of an n-th root of unity; a layer with blocks of size 2·half uses every (n / 2·half)-th one. A cancellation token is
+// checked before each layer
+fn butterflies(
+    values: &mut [Scalar],
+    twiddles: &[Scalar],
+    cancel: Option<&CancellationToken>,
+) -> Result<(), Error> {
+    let n = values.len();
+    if n <= 1 {
+        return Ok(());
+    }
+    let log_n = n.trailing_zeros();
+    for i in 0..n {
+        let j = bit_reverse(i, log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut half = 1;
+    while half < n {
+        if let Some(cancel) = cancel {
+            cancel.check()?;
+        }
+        butterfly_layer(values, twiddles, half);
+        half *= 2;
+    }
+    Ok(())
+}
+
+fn butterfly_block(block: &mut [Scalar], twiddles: &[Scalar], half: usize, stride: usize) {
+    let (low, high) = block.split_at_mut(half);
+    for (j, (a, b)) in low.iter_mut().zip(high.iter_mut()).enumerate() {
+        let t = *b * twiddles[j * stride];
+        *b = *a - t;
+        *a += t;
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn butterfly_layer(values: &mut [Scalar], twiddles: &[Scalar], half: usize) {
+    let stride = values.len() / (2 * half);
+    for block in values.chunks_exact_mut(2 * half) {
+        butterfly_block(block, twiddles, half, stride);
+    }
+}
+
+// Early layers have many small blocks, which are spread across threads. Late layers have only a few large blocks, so
+// the butterflies inside each block are split up instead
+#[cfg(feature = "parallel")]
+fn butterfly_layer(values: &mut [Scalar], twiddles: &[Scalar], half: usize) {
+    let n = values.len();
+    let stride = n / (2 * half);
+    if n < PARALLEL_FFT_CUTOFF {
+        for block in values.chunks_exact_mut(2 * half) {
+            butterfly_block(block, twiddles, half, stride);
+        }
+    } else if stride >= rayon::current_num_threads() {
+        values
+            .par_chunks_exact_mut(2 * half)
+            .for_each(|block| butterfly_block(block, twiddles, half, stride));
+    } else {
+        for block in values.chunks_exact_mut(2 * half) {
+            let (low, high) = block.split_at_mut(half);
+            low.par_iter_mut()
+                .zip(high.par_iter_mut())
+                .enumerate()
+                .for_each(|(j, (a, b))| {
+                    let t = *b * twiddles[j * stride];
+                    *b = *a - t;
+                    *a += t;
+                });
+        }
+    }
+}
+
+#[test]
+fn creates_domains_of_power_of_two_size() {
+    let domain = EvaluationDomain::new(5).unwrap();
+    assert_eq!(domain.size(), 8);
+    assert_eq!(domain.log_size(), 3);
+    assert_eq!(domain.generator().pow_vartime([8]), Scalar::ONE);
+    assert_ne!(domain.generator().pow_vartime([4]), Scalar::ONE);
+    assert_eq!(domain.element(3), domain.elements()[3]);
+
+    assert_eq!(EvaluationDomain::new(0).unwrap().size(), 1);
+    assert!(EvaluationDomain::new((1 << 32) + 1).is_none());
+}
+
+#[test]
+fn fft_matches_direct_evaluation() {
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+
+    let domain = EvaluationDomain::new(16).unwrap();
+    let polynomial = Polynomial::rand(11, &mut test_rng());
+
+    let evaluations = domain.fft(polynomial.coeffs());
+    let expected: Vec<Scalar> = domain
+        .elements()
+        .iter()
+        .map(|x| polynomial.evaluate(*x))
+        .collect();
+    assert_eq!(evaluations, expected);
+
+    let coefficients = domain.ifft(&evaluations);
+    assert_eq!(Polynomial::new(&coefficients), polynomial);
+}
+
+#[test]
+fn transforms_in_place() {
+    let domain = EvaluationDomain::new(8).unwrap();
+    let coefficients: Vec<Scalar> = (1..=8).map(|i| Scalar::from(i as u64)).collect();
+
+    let mut values = coefficients.clone();
+    domain.fft_in_place(&mut values);
+    assert_eq!(values, domain.fft(&coefficients));
+    domain.ifft_in_place(&mut values);
+    assert_eq!(values, coefficients);
+}
+
+#[test]
+fn transforms_large_domains() {
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+
+    // Large enough to take the threaded path when the parallel feature is on
+    let domain = EvaluationDomain::new(1 << 12).unwrap();
+    let polynomial = Polynomial::rand((1 << 12) - 1, &mut test_rng());
+
+    let evaluations = domain.fft(polynomial.coeffs());
+    for i in [0, 1, 1000, (1 << 12) - 1] {
+        assert_eq!(evaluations[i], polynomial.evaluate(domain.element(i)));
+    }
+    assert_eq!(Polynomial::new(&domain.ifft(&evaluations)), polynomial);
+}
+
+#[test]
+fn group_fft_matches_the_scalar_fft() {
+    use crate::rng::test_rng;
+
+    // Points that are known multiples of the generator, so the transforms can be compared in the exponent
+    let mut rng = test_rng();
+    let domain = EvaluationDomain::new(8).unwrap();
+    let scalars: Vec<Scalar> = (0..6).map(|_| Scalar::random(&mut rng)).collect();
+    let points: Vec<G1Projective> = scalars
+        .iter()
+        .map(|scalar| G1Projective::generator() * scalar)
+        .collect();
+    let expected: Vec<G1Projective> = domain
+        .fft(&scalars)
+        .iter()
+        .map(|scalar| G1Projective::generator() * scalar)
+        .collect();
+    assert_eq!(domain.fft_g1(&points), expected);
+
+    let mut padded = points.clone();
+    padded.resize(8, G1Projective::identity());
+    assert_eq!(domain.ifft_g1(&domain.fft_g1(&points)), padded);
+}
+
+#[test]
+fn transforms_size_one_domains() {
+    let domain = EvaluationDomain::new(1).unwrap();
+    assert_eq!(domain.size(), 1);
+    assert_eq!(bit_reverse(0, 0), 0);
+
+    let values = vec![Scalar::from(7)];
+    assert_eq!(domain.fft(&values), values);
+    assert_eq!(domain.ifft(&domain.fft(&values)), values);
+
+    let points = vec![G1Projective::generator()];
+    assert_eq!(domain.fft_g1(&points), points);
+    assert_eq!(domain.ifft_g1(&domain.fft_g1(&points)), points);
+}
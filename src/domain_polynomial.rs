@@ -0,0 +1,139 @@
+use crate::domain::EvaluationDomain;
+use crate::polynomials::Polynomial;
+use blstrs::Scalar;
+use std::cell::OnceCell;
+
+// A polynomial over an evaluation domain that keeps both its coefficient form and its evaluations over the domain.
+// Whichever form it was built from is stored; the other is computed with an FFT the first time it's asked for and
+// cached, so pipelines that alternate between pointwise arithmetic and committing only pay for each conversion once
+#[derive(Clone, Debug)]
+pub struct DomainPolynomial {
+    domain: EvaluationDomain,
+    coefficients: OnceCell<Polynomial>,
+    evaluations: OnceCell<Vec<Scalar>>,
+}
+
+impl DomainPolynomial {
+    // `None` if the polynomial has too many coefficients to be determined by its evaluations over the domain
+    pub fn from_coefficients(domain: EvaluationDomain, polynomial: Polynomial) -> Option<Self> {
+        if polynomial.len() > domain.size() {
+            return None;
+        }
+        Some(DomainPolynomial {
+            domain,
+            coefficients: OnceCell::from(polynomial),
+            evaluations: OnceCell::new(),
+        })
+    }
+
+    // `evaluations[i]` is the value at the i-th domain element. `None` unless there's exactly one per element
+    pub fn from_evaluations(domain: EvaluationDomain, evaluations: Vec<Scalar>) -> Option<Self> {
+        if evaluations.len() != domain.size() {
+            return None;
+        }
+        Some(DomainPolynomial {
+            domain,
+            coefficients: OnceCell::new(),
+            evaluations: OnceCell::from(evaluations),
+        })
+    }
+
+    pub fn domain(&self) -> &EvaluationDomain {
+        &self.domain
+    }
+
+    pub fn coefficients(&self) -> &Polynomial {
+        self.coefficients.get_or_init(|| {
+            // One of the two forms is always present
+            let evaluations = self.evaluations.get().unwrap();
+            Polynomial::new(&self.domain.ifft(evaluations))
+        })
+    }
+
+    pub fn evaluations(&self) -> &[Scalar] {
+        self.evaluations.get_or_init(|| {
+            let coefficients = self.coefficients.get().unwrap();
+            self.domain.fft(coefficients.coeffs())
+        })
+    }
+
+    pub fn into_coefficients(self) -> Polynomial {
+        self.coefficients();
+        self.coefficients.into_inner().unwrap()
+    }
+
+    // Pointwise sum over the domain. `None` if the two live on different domains
+    pub fn add_pointwise(&self, other: &DomainPolynomial) -> Option<DomainPolynomial> {
+        self.pointwise(other, |a, b| a + b)
+    }
+
+    // Pointwise product over the domain. This is the product modulo $x^n - 1$, so it only equals the true product
+    // when the degrees add up to less than the domain size
+    pub fn mul_pointwise(&self, other: &DomainPolynomial) -> Option<DomainPolynomial> {
+        self.pointwise(other, |a, b| a * b)
+    }
+
+    fn pointwise(
+        &self,
+        other: &DomainPolynomial,
+        op: impl Fn(Scalar, Scalar) -> Scalar,
+    ) -> Option<DomainPolynomial> {
+        if self.domain != other.domain {
+            return None;
+        }
+        let evaluations = self
+            .evaluations()
+            .iter()
+            .zip(other.evaluations())
+            .map(|(a, b)| op(*a, *b))
+            .collect();
+        DomainPolynomial::from_evaluations(self.domain.clone(), evaluations)
+    }
+}
+
+#[test]
+fn converts_lazily_between_forms() {
+    let domain = EvaluationDomain::new(4).unwrap();
+    let polynomial = Polynomial::new_from_i64(&[1, 2, 3]);
+
+    let from_coefficients =
+        DomainPolynomial::from_coefficients(domain.clone(), polynomial.clone()).unwrap();
+    assert!(from_coefficients.evaluations.get().is_none());
+    let evaluations = from_coefficients.evaluations().to_vec();
+    assert!(from_coefficients.evaluations.get().is_some());
+    assert_eq!(evaluations[1], polynomial.evaluate(domain.element(1)));
+
+    let from_evaluations = DomainPolynomial::from_evaluations(domain.clone(), evaluations).unwrap();
+    assert_eq!(from_evaluations.coefficients(), &polynomial);
+    assert_eq!(from_evaluations.into_coefficients(), polynomial);
+
+    assert!(DomainPolynomial::from_coefficients(
+        domain.clone(),
+        Polynomial::new_from_bytes(&[1; 5])
+    )
+    .is_none());
+    assert!(DomainPolynomial::from_evaluations(domain, vec![Scalar::from(1); 3]).is_none());
+}
+
+#[test]
+fn multiplies_pointwise() {
+    let domain = EvaluationDomain::new(4).unwrap();
+    let a = DomainPolynomial::from_coefficients(domain.clone(), Polynomial::new_from_i64(&[1, 1]))
+        .unwrap();
+    let b = DomainPolynomial::from_coefficients(domain.clone(), Polynomial::new_from_i64(&[3, 2]))
+        .unwrap();
+
+    assert_eq!(
+        a.mul_pointwise(&b).unwrap().coefficients(),
+        &Polynomial::new_from_i64(&[3, 5, 2])
+    );
+    assert_eq!(
+        a.add_pointwise(&b).unwrap().coefficients(),
+        &Polynomial::new_from_i64(&[4, 3])
+    );
+
+    let other_domain = EvaluationDomain::new(8).unwrap();
+    let c =
+        DomainPolynomial::from_coefficients(other_domain, Polynomial::new_from_i64(&[1])).unwrap();
+    assert!(a.mul_pointwise(&c).is_none());
+}
@@ -0,0 +1,90 @@
+use crate::error::Error;
+use crate::polynomial_commitments::{GlobalParameters, PolynomialCommitment};
+use crate::polynomials::Polynomial;
+use blstrs::{G1Projective, Scalar};
+
+// Object-safe counterpart of `PolynomialCommitment`, so the scheme can be picked at runtime and passed around as a
+// `Box<dyn DynPolynomialCommitment>`. Every `PolynomialCommitment` with KZG's types implements it. Setup can't take
+// a generic rng here, so it always draws from the OS; schemes are usually configured with parameters before being
+// boxed anyway
+pub trait DynPolynomialCommitment: Send + Sync {
+    fn setup(&mut self, d: usize) -> GlobalParameters;
+    fn commit(&self, polynomial: &Polynomial) -> Result<G1Projective, Error>;
+    fn create_witness(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+    ) -> Result<(G1Projective, Scalar), Error>;
+    fn verify_evaluation(
+        &self,
+        committed_polynomial: G1Projective,
+        point: Scalar,
+        evaluation: Scalar,
+        witness: G1Projective,
+    ) -> Result<bool, Error>;
+}
+
+impl<T> DynPolynomialCommitment for T
+where
+    T: PolynomialCommitment<
+            Parameters = GlobalParameters,
+            Commitment = G1Projective,
+            Witness = G1Projective,
+        > + Send
+        + Sync,
+{
+    fn setup(&mut self, d: usize) -> GlobalParameters {
+        PolynomialCommitment::setup(self, d)
+    }
+
+    fn commit(&self, polynomial: &Polynomial) -> Result<G1Projective, Error> {
+        PolynomialCommitment::commit(self, polynomial)
+    }
+
+    fn create_witness(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        PolynomialCommitment::create_witness(self, polynomial, point)
+    }
+
+    fn verify_evaluation(
+        &self,
+        committed_polynomial: G1Projective,
+        point: Scalar,
+        evaluation: Scalar,
+        witness: G1Projective,
+    ) -> Result<bool, Error> {
+        PolynomialCommitment::verify_evaluation(
+            self,
+            committed_polynomial,
+            point,
+            evaluation,
+            witness,
+        )
+    }
+}
+
+#[test]
+fn works_as_a_trait_object() {
+    use crate::polynomial_commitments::GenericPolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let gp = PolynomialCommitment::setup_with_rng(
+        &mut GenericPolynomialCommitment::new(),
+        2,
+        &mut test_rng(),
+    );
+    let scheme: Box<dyn DynPolynomialCommitment> =
+        Box::new(GenericPolynomialCommitment::with_global_parameters(gp));
+
+    let polynomial = Polynomial::new_from_bytes(&[1, 2, 3]);
+    let point = Scalar::from(5);
+    let commitment = scheme.commit(&polynomial).unwrap();
+    let (witness, evaluation) = scheme.create_witness(&polynomial, point).unwrap();
+    assert_eq!(
+        scheme.verify_evaluation(commitment, point, evaluation, witness),
+        Ok(true)
+    );
+}
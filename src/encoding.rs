@@ -0,0 +1,49 @@
+use blstrs::Scalar;
+
+// Bytes packed into each scalar. 31 bytes are always below the modulus, so any byte string packs without reduction
+pub const BYTES_PER_SCALAR: usize = 31;
+
+// Packs bytes into scalars, 31 little-endian bytes at a time. The last scalar is padded with zero bytes, so the
+// original length has to be kept alongside to undo it
+pub fn pack_bytes(bytes: &[u8]) -> Vec<Scalar> {
+    bytes
+        .chunks(BYTES_PER_SCALAR)
+        .map(|chunk| {
+            let mut le = [0u8; 32];
+            le[..chunk.len()].copy_from_slice(chunk);
+            // Can unwrap since the top byte is zero
+            Scalar::from_bytes_le(&le).unwrap()
+        })
+        .collect()
+}
+
+// The 31 data bytes of each scalar, concatenated. Includes any zero padding
+pub fn unpack_bytes(scalars: &[Scalar]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(scalars.len() * BYTES_PER_SCALAR);
+    for scalar in scalars {
+        bytes.extend_from_slice(&scalar.to_bytes_le()[..BYTES_PER_SCALAR]);
+    }
+    bytes
+}
+
+// Hex digits, with or without a 0x prefix, as they appear in published setups and test vectors
+pub(crate) fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[test]
+fn packs_and_unpacks_bytes() {
+    let bytes: Vec<u8> = (0..=255).cycle().take(100).collect();
+    let scalars = pack_bytes(&bytes);
+    assert_eq!(scalars.len(), 4);
+    assert_eq!(&unpack_bytes(&scalars)[..bytes.len()], &bytes[..]);
+    assert!(pack_bytes(&[]).is_empty());
+    assert_eq!(pack_bytes(&[0xff; 31])[0].to_bytes_le()[31], 0);
+}
@@ -0,0 +1,177 @@
+use thiserror::Error as ThisError;
+
+#[derive(Debug, Eq, PartialEq, ThisError)]
+pub enum Error {
+    // Tried to use a polynomial of an inappropriate degree
+    #[error("expected a polynomial of degree {expected}, got degree {actual}")]
+    IncorrectDegree { expected: usize, actual: usize },
+    // Values made for parameters of one degree were used with parameters of another
+    #[error("made for degree {actual}, but the parameters are for degree {expected}")]
+    DegreeMismatch { expected: usize, actual: usize },
+    // A list of values doesn't have the length the operation needs, e.g. one per domain element
+    #[error("expected {expected} values, got {actual}")]
+    LengthMismatch { expected: usize, actual: usize },
+    // A commitment made over one evaluation domain (or none) met a key for another
+    #[error("made for domain size {actual:?}, but the key is for domain size {expected:?}")]
+    DomainMismatch {
+        expected: Option<usize>,
+        actual: Option<usize>,
+    },
+    // A polynomial (or quotient) needs more powers of tau than the parameters have
+    #[error("degree {actual} exceeds the maximum supported degree {max}")]
+    DegreeTooLarge { max: usize, actual: usize },
+    // Setup not complete; tried to use commitment scheme prior to setup
+    #[error("setup has not been run, so there are no global parameters")]
+    SetupIncomplete,
+    // Bytes have the wrong length or layout
+    #[error("invalid encoding: {0}")]
+    InvalidEncoding(&'static str),
+    // A serialized point isn't on the curve or isn't in the prime order subgroup
+    #[error("{group} point at index {index} is not a valid subgroup element")]
+    InvalidPoint { group: &'static str, index: usize },
+    // A serialized scalar isn't reduced mod the field order
+    #[error("scalar at index {index} is not a canonical field element")]
+    InvalidScalar { index: usize },
+    // Saved parameters don't match the fingerprint stored alongside them
+    #[error("global parameters don't match their fingerprint")]
+    FingerprintMismatch,
+    // Reading or writing saved parameters failed
+    #[error("i/o error: {0}")]
+    Io(std::io::ErrorKind),
+    // Downloading parameters failed
+    #[error("download failed: {0}")]
+    Download(String),
+    // Global parameters are structurally invalid
+    #[error("invalid global parameters: {0}")]
+    InvalidParameters(&'static str),
+    // The zero polynomial can't be committed to or opened; its commitment is the identity
+    #[error("the zero polynomial can't be committed to or opened")]
+    ZeroPolynomial,
+    // The commitment is the identity point
+    #[error("commitment is the identity point")]
+    IdentityCommitment,
+    // The witness is (or would be) the identity point, which happens exactly when the polynomial is constant
+    #[error("witness is the identity point; constant polynomials can't be opened")]
+    IdentityWitness,
+    // Tried to divide by the zero polynomial
+    #[error("division by the zero polynomial")]
+    DivisionByZero,
+    // Asked for a position past the end of the data
+    #[error("index {index} is out of range for {len} elements")]
+    IndexOutOfRange { index: usize, len: usize },
+    // Shares or partial results from a distributed protocol can't be combined
+    #[error("invalid shares: {0}")]
+    InvalidShares(&'static str),
+    // A division that was expected to be exact left a remainder
+    #[error("division left a nonzero remainder of degree {remainder_degree}")]
+    NonZeroRemainder { remainder_degree: usize },
+    // Asked to prove a vector position holds the default value when it doesn't
+    #[error("position {index} holds a value other than the default")]
+    NotDefault { index: usize },
+    // The caller cancelled the operation before it finished
+    #[error("operation was cancelled")]
+    Cancelled,
+    // An extension-field opening was asked for at a point of the scalar field itself
+    #[error("point is in the scalar field, so it needs a regular opening")]
+    PointInBaseField,
+}
+
+// Why `Commitment::verify` didn't accept an opening: either the inputs couldn't be checked at all, or they could and
+// the pairing check failed
+#[derive(Debug, Eq, PartialEq, ThisError)]
+pub enum VerifyError {
+    // The commitment doesn't fit the key, or the commitment or witness is the identity
+    #[error("opening can't be checked: {0}")]
+    Invalid(#[from] Error),
+    // Everything was well-formed, but the proof doesn't show the polynomial takes that value at that point
+    #[error("proof doesn't show the claimed evaluation")]
+    Rejected,
+}
+
+// Only the kind is kept, so errors stay comparable and cheap to clone into reports
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error.kind())
+    }
+}
+
+impl From<std::io::ErrorKind> for Error {
+    fn from(kind: std::io::ErrorKind) -> Self {
+        Error::Io(kind)
+    }
+}
+
+// From `<[u8; N]>::try_from(slice)` on input that should have held a fixed-size value
+impl From<std::array::TryFromSliceError> for Error {
+    fn from(_: std::array::TryFromSliceError) -> Self {
+        Error::InvalidEncoding("byte slice has the wrong length")
+    }
+}
+
+#[cfg(feature = "fetch")]
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Download(error.to_string())
+    }
+}
+
+#[cfg(feature = "fetch")]
+impl From<serde_json::Error> for Error {
+    fn from(_: serde_json::Error) -> Self {
+        Error::InvalidEncoding("not a JSON document")
+    }
+}
+
+#[cfg(feature = "conformance")]
+impl From<serde_yaml::Error> for Error {
+    fn from(_: serde_yaml::Error) -> Self {
+        Error::InvalidEncoding("not a YAML or JSON document")
+    }
+}
+
+#[test]
+fn errors_describe_their_context() {
+    let error = Error::IncorrectDegree {
+        expected: 24,
+        actual: 2,
+    };
+    assert_eq!(
+        error.to_string(),
+        "expected a polynomial of degree 24, got degree 2"
+    );
+
+    let error = Error::InvalidPoint {
+        group: "G2",
+        index: 3,
+    };
+    assert_eq!(
+        error.to_string(),
+        "G2 point at index 3 is not a valid subgroup element"
+    );
+
+    let boxed: Box<dyn std::error::Error> = Box::new(Error::SetupIncomplete);
+    assert!(boxed.to_string().contains("setup"));
+}
+
+#[test]
+fn composes_with_application_errors() {
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<Error>();
+
+    fn read_missing() -> Result<Vec<u8>, Error> {
+        Ok(std::fs::read("/nonexistent/kzg-error")?)
+    }
+    assert_eq!(read_missing(), Err(Error::Io(std::io::ErrorKind::NotFound)));
+
+    fn fixed(bytes: &[u8]) -> Result<[u8; 4], Error> {
+        Ok(bytes.try_into()?)
+    }
+    assert!(matches!(fixed(&[0; 3]), Err(Error::InvalidEncoding(_))));
+
+    // Into the boxed errors that application error crates build on
+    fn application() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        read_missing()?;
+        Ok(())
+    }
+    assert!(application().unwrap_err().is::<Error>());
+}
@@ -0,0 +1,208 @@
+use crate::constant_time::ct_eq_gt;
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::polynomials::Polynomial;
+use blstrs::{pairing, G1Projective, Scalar};
+use group::ff::{Field, PrimeField};
+use group::{Curve, Group};
+use std::ops::{Add, Mul};
+
+// Openings at points of the quadratic extension of the scalar field, $F_r[u] / (u^2 - \beta)$, for protocols that draw
+// challenges from a bigger field than the one the polynomial lives in. The commitment is the usual one in G1. A point
+// z outside $F_r$ has minimal polynomial $m(x) = (x - z)(x - \bar z) = x^2 - 2 z_0 x + z_0^2 - \beta z_1^2$ over $F_r$,
+// so dividing by it leaves $p(x) = q(x) m(x) + r(x)$ with r linear and $r(z) = p(z)$. The verifier recovers r from the
+// claimed $p(z)$ and checks $e(C - [r(\tau)]_1, h) = e([q(\tau)]_1, [m(\tau)]_2)$. That needs $\tau^2$ in G2
+
+// $\beta$ generates the multiplicative group, so it isn't a square and $u^2 = \beta$ has no root in $F_r$
+const NON_RESIDUE: Scalar = Scalar::MULTIPLICATIVE_GENERATOR;
+
+// $c_0 + c_1 u$
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ExtensionScalar {
+    pub c0: Scalar,
+    pub c1: Scalar,
+}
+
+impl ExtensionScalar {
+    pub fn new(c0: Scalar, c1: Scalar) -> Self {
+        ExtensionScalar { c0, c1 }
+    }
+
+    // Whether this is an element of the scalar field itself
+    pub fn is_base(&self) -> bool {
+        bool::from(self.c1.is_zero())
+    }
+
+    // The monic polynomial over $F_r$ with roots z and its conjugate
+    pub fn minimal_polynomial(&self) -> Polynomial {
+        let norm = self.c0.square() - NON_RESIDUE * self.c1.square();
+        Polynomial::new(&[norm, -self.c0.double(), Scalar::ONE])
+    }
+}
+
+impl From<Scalar> for ExtensionScalar {
+    fn from(c0: Scalar) -> Self {
+        ExtensionScalar::new(c0, Scalar::ZERO)
+    }
+}
+
+impl Add for ExtensionScalar {
+    type Output = ExtensionScalar;
+
+    fn add(self, other: ExtensionScalar) -> ExtensionScalar {
+        ExtensionScalar::new(self.c0 + other.c0, self.c1 + other.c1)
+    }
+}
+
+impl Mul for ExtensionScalar {
+    type Output = ExtensionScalar;
+
+    fn mul(self, other: ExtensionScalar) -> ExtensionScalar {
+        ExtensionScalar::new(
+            self.c0 * other.c0 + NON_RESIDUE * self.c1 * other.c1,
+            self.c0 * other.c1 + self.c1 * other.c0,
+        )
+    }
+}
+
+impl Polynomial {
+    pub fn evaluate_extension(&self, point: ExtensionScalar) -> ExtensionScalar {
+        self.iter()
+            .rev()
+            .fold(ExtensionScalar::default(), |acc, coefficient| {
+                acc * point + ExtensionScalar::from(*coefficient)
+            })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExtensionOpening {
+    pub witness: G1Projective,
+    pub evaluation: ExtensionScalar,
+}
+
+impl GenericPolynomialCommitment {
+    // Points in $F_r$ itself are rejected; `create_witness` is the opening for those
+    pub fn create_extension_witness(
+        &self,
+        polynomial: &Polynomial,
+        point: ExtensionScalar,
+    ) -> Result<ExtensionOpening, Error> {
+        self.global_parameters()?;
+        if point.is_base() {
+            return Err(Error::PointInBaseField);
+        }
+        if polynomial.is_zero() {
+            return Err(Error::ZeroPolynomial);
+        }
+        let evaluation = polynomial.evaluate_extension(point);
+
+        let (quotient, remainder) = polynomial.div_rem(&point.minimal_polynomial())?;
+        // Sanity check the remainder takes the same value at the point
+        debug_assert_eq!(remainder.evaluate_extension(point), evaluation);
+        if quotient.is_zero() {
+            return Err(Error::IdentityWitness);
+        }
+        Ok(ExtensionOpening {
+            witness: self.msm(quotient.coeffs())?,
+            evaluation,
+        })
+    }
+
+    pub fn verify_extension(
+        &self,
+        committed_polynomial: G1Projective,
+        point: ExtensionScalar,
+        opening: &ExtensionOpening,
+    ) -> Result<bool, Error> {
+        let global_parameters = self.global_parameters()?;
+        if global_parameters.gs().len() < 2 || global_parameters.hs().len() < 3 {
+            return Err(Error::InvalidParameters(
+                "extension verification needs tau^2 in G2",
+            ));
+        }
+        if point.is_base() {
+            return Err(Error::PointInBaseField);
+        }
+        if bool::from(committed_polynomial.is_identity()) {
+            return Err(Error::IdentityCommitment);
+        }
+        if bool::from(opening.witness.is_identity()) {
+            return Err(Error::IdentityWitness);
+        }
+
+        // $r(x) = r_0 + r_1 x$ with $r(z) = (r_0 + r_1 z_0) + r_1 z_1 u$ equal to the claimed evaluation. Can unwrap
+        // since $z_1$ isn't zero
+        let slope = opening.evaluation.c1 * point.c1.invert().unwrap();
+        let remainder = self.msm(&[opening.evaluation.c0 - slope * point.c0, slope])?;
+        let minimal = global_parameters.commit_in_g2(point.minimal_polynomial().coeffs())?;
+
+        let lhs = pairing(
+            &(committed_polynomial - remainder).to_affine(),
+            &global_parameters.hs()[0].to_affine(),
+        );
+        let rhs = pairing(&opening.witness.to_affine(), &minimal.to_affine());
+        Ok(ct_eq_gt(&lhs, &rhs).into())
+    }
+}
+
+#[test]
+fn multiplies_in_the_extension() {
+    let u = ExtensionScalar::new(Scalar::ZERO, Scalar::ONE);
+    assert_eq!(u * u, ExtensionScalar::from(NON_RESIDUE));
+    // The point is a root of its own minimal polynomial, and so is its conjugate
+    let z = ExtensionScalar::new(Scalar::from(3), Scalar::from(5));
+    let conjugate = ExtensionScalar::new(Scalar::from(3), -Scalar::from(5));
+    let minimal = z.minimal_polynomial();
+    assert_eq!(minimal.evaluate_extension(z), ExtensionScalar::default());
+    assert_eq!(
+        minimal.evaluate_extension(conjugate),
+        ExtensionScalar::default()
+    );
+    // Base field points evaluate as usual
+    let polynomial = Polynomial::new_from_i64(&[2, -1, 3]);
+    assert_eq!(
+        polynomial.evaluate_extension(Scalar::from(4).into()),
+        polynomial.evaluate(Scalar::from(4)).into()
+    );
+}
+
+#[test]
+fn opens_at_extension_points() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(4, &mut test_rng());
+    let polynomial = Polynomial::new_from_i64(&[2, -1, 3, -4, 1]);
+    let commitment = committer.commit(&polynomial).unwrap();
+    let point = ExtensionScalar::new(Scalar::from(3), Scalar::from(5));
+
+    let opening = committer
+        .create_extension_witness(&polynomial, point)
+        .unwrap();
+    assert_eq!(opening.evaluation, polynomial.evaluate_extension(point));
+    assert_eq!(
+        committer.verify_extension(commitment, point, &opening),
+        Ok(true)
+    );
+
+    let wrong = ExtensionOpening {
+        evaluation: opening.evaluation + ExtensionScalar::new(Scalar::ZERO, Scalar::ONE),
+        ..opening
+    };
+    assert_eq!(
+        committer.verify_extension(commitment, point, &wrong),
+        Ok(false)
+    );
+    // The conjugate has the same minimal polynomial but a different value
+    let conjugate = ExtensionScalar::new(point.c0, -point.c1);
+    assert_eq!(
+        committer.verify_extension(commitment, conjugate, &opening),
+        Ok(false)
+    );
+    assert_eq!(
+        committer.create_extension_witness(&polynomial, Scalar::from(3).into()),
+        Err(Error::PointInBaseField)
+    );
+}
@@ -0,0 +1,172 @@
+use crate::encoding::decode_hex;
+use crate::error::Error;
+use crate::polynomial_commitments::GlobalParameters;
+use crate::srs_cache::SrsCache;
+use blstrs::{G1Affine, G1Projective, G2Affine, G2Projective};
+use rand::rngs::OsRng;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+// Final state of the Ethereum KZG ceremony as served by its sequencer: a JSON document with one transcript per setup
+// size, each holding the monomial powers of tau in G1 and G2 as 0x-prefixed compressed hex
+pub const ETHEREUM_CEREMONY_URL: &str = "https://seq.ceremony.ethereum.org/info/current_state";
+
+// Where to download a setup from and the SHA-256 of the document published alongside it. The checksum isn't built in:
+// take it from a source you trust independently of the server you download from
+#[derive(Clone, Debug)]
+pub struct SrsSource {
+    pub url: String,
+    pub sha256: [u8; 32],
+}
+
+impl SrsSource {
+    pub fn ethereum_ceremony(sha256: [u8; 32]) -> Self {
+        SrsSource {
+            url: ETHEREUM_CEREMONY_URL.to_string(),
+            sha256,
+        }
+    }
+}
+
+// Downloaded parameters for `max_degree`, from the cache when they've been fetched before. A fresh download must match
+// the checksum, parse as a ceremony transcript, and pass `verify_powers` before it's cached
+pub async fn fetch_global_parameters(
+    source: &SrsSource,
+    cache: &SrsCache,
+    max_degree: usize,
+) -> Result<GlobalParameters, Error> {
+    if let Some(global_parameters) = cache.load(max_degree)? {
+        return Ok(global_parameters);
+    }
+
+    let body = reqwest::get(&source.url)
+        .await
+        .and_then(|response| response.error_for_status())?
+        .bytes()
+        .await?;
+    if Sha256::digest(&body).as_slice() != source.sha256 {
+        return Err(Error::FingerprintMismatch);
+    }
+
+    let global_parameters = parse_ceremony_transcript(&body, max_degree)?;
+    if !global_parameters.verify_powers(&mut OsRng) {
+        return Err(Error::InvalidParameters("points aren't powers of one tau"));
+    }
+    cache.store(max_degree, &global_parameters)?;
+    Ok(global_parameters)
+}
+
+// Picks the smallest transcript that supports `max_degree`, i.e. has at least `max_degree + 1` G1 powers, and keeps
+// the first `max_degree + 1` of each group
+pub fn parse_ceremony_transcript(
+    json: &[u8],
+    max_degree: usize,
+) -> Result<GlobalParameters, Error> {
+    let length = max_degree + 1;
+    let document: Value = serde_json::from_slice(json)?;
+    let powers_of = |transcript: &Value, group: &str| -> Option<Vec<String>> {
+        transcript["powersOfTau"][group]
+            .as_array()?
+            .iter()
+            .map(|power| power.as_str().map(str::to_string))
+            .collect()
+    };
+
+    let transcripts = document["transcripts"]
+        .as_array()
+        .ok_or(Error::InvalidEncoding("no transcripts in the document"))?;
+    let candidates: Vec<(Vec<String>, Vec<String>)> = transcripts
+        .iter()
+        .filter_map(|transcript| {
+            Some((
+                powers_of(transcript, "G1Powers")?,
+                powers_of(transcript, "G2Powers")?,
+            ))
+        })
+        .collect();
+    let largest = candidates
+        .iter()
+        .map(|(g1_powers, _)| g1_powers.len())
+        .max()
+        .ok_or(Error::InvalidEncoding(
+            "no transcript lists its powers of tau",
+        ))?;
+    let (g1_powers, g2_powers) = candidates
+        .into_iter()
+        .filter(|(g1_powers, _)| g1_powers.len() >= length)
+        .min_by_key(|(g1_powers, _)| g1_powers.len())
+        .ok_or(Error::DegreeTooLarge {
+            max: largest.saturating_sub(1),
+            actual: max_degree,
+        })?;
+
+    let gs = g1_powers[..length]
+        .iter()
+        .enumerate()
+        .map(|(index, hex)| {
+            decode_hex(hex)
+                .and_then(|bytes| <[u8; 48]>::try_from(bytes).ok())
+                .and_then(|bytes| Option::<G1Affine>::from(G1Affine::from_compressed(&bytes)))
+                .map(G1Projective::from)
+                .ok_or(Error::InvalidPoint { group: "G1", index })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let hs = g2_powers[..g2_powers.len().min(length)]
+        .iter()
+        .enumerate()
+        .map(|(index, hex)| {
+            decode_hex(hex)
+                .and_then(|bytes| <[u8; 96]>::try_from(bytes).ok())
+                .and_then(|bytes| Option::<G2Affine>::from(G2Affine::from_compressed(&bytes)))
+                .map(G2Projective::from)
+                .ok_or(Error::InvalidPoint { group: "G2", index })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    GlobalParameters::try_new(gs, hs)
+}
+
+#[test]
+fn parses_ceremony_transcripts() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::rng::test_rng;
+    use group::Curve;
+
+    let to_hex = |bytes: &[u8]| {
+        let digits: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        format!("0x{}", digits)
+    };
+    let transcript = |gp: &GlobalParameters| {
+        let g1: Vec<String> = gp
+            .gs()
+            .iter()
+            .map(|g| to_hex(&g.to_affine().to_compressed()))
+            .collect();
+        let g2: Vec<String> = gp
+            .hs()
+            .iter()
+            .map(|h| to_hex(&h.to_affine().to_compressed()))
+            .collect();
+        serde_json::json!({ "powersOfTau": { "G1Powers": g1, "G2Powers": g2 } })
+    };
+
+    let small = GenericPolynomialCommitment::new().setup_with_rng(3, &mut test_rng());
+    let large = GenericPolynomialCommitment::new().setup_with_rng(7, &mut test_rng());
+    let document = serde_json::json!({ "transcripts": [transcript(&large), transcript(&small)] });
+    let json = serde_json::to_vec(&document).unwrap();
+
+    let parsed = parse_ceremony_transcript(&json, 2).unwrap();
+    assert_eq!(parsed.gs(), &small.gs()[..3]);
+    assert_eq!(parsed.hs(), &small.hs()[..3]);
+    assert!(parsed.verify_powers(&mut test_rng()));
+
+    assert_eq!(
+        parse_ceremony_transcript(&json, 5).unwrap().gs(),
+        &large.gs()[..6]
+    );
+    // The error names the largest transcript there is
+    assert_eq!(
+        parse_ceremony_transcript(&json, 8).unwrap_err(),
+        Error::DegreeTooLarge { max: 7, actual: 8 }
+    );
+    assert!(parse_ceremony_transcript(b"not json", 3).is_err());
+}
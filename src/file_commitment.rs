@@ -0,0 +1,228 @@
+use crate::chunked::{ChunkedCommitment, ChunkedData, ChunkedDataBuilder, ChunkedProof};
+use crate::encoding::{pack_bytes, unpack_bytes, BYTES_PER_SCALAR};
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::progress::{NoProgress, Operation, Progress};
+use blstrs::Scalar;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+// End-to-end commitments to files. The file is read a chunk at a time and packed 31 bytes per scalar; the final
+// scalar and chunk are padded with zeros, and the byte length is kept in the commitment so the padding is unambiguous.
+// Byte ranges are proven by opening every scalar that overlaps them
+pub struct FileCommitment {
+    data: ChunkedData,
+    byte_len: u64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FileCommitmentInfo {
+    pub chunks: ChunkedCommitment,
+    pub byte_len: u64,
+}
+
+// Openings of the scalars covering a byte range, starting with the scalar at `first_scalar`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RangeProof {
+    pub first_scalar: usize,
+    pub values: Vec<Scalar>,
+    pub proofs: Vec<ChunkedProof>,
+}
+
+// Reads until `buffer` is full or the reader is exhausted, returning how much was read
+fn read_full(reader: &mut impl Read, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+// Bytes `offset..offset + len` must be non-empty and inside a file of `byte_len` bytes. An end past `u64::MAX` is
+// reported as `usize::MAX`
+fn check_range(offset: u64, len: usize, byte_len: u64) -> Result<(), Error> {
+    match offset.checked_add(len as u64) {
+        Some(end) if len > 0 && end <= byte_len => Ok(()),
+        end => Err(Error::IndexOutOfRange {
+            index: end.map_or(usize::MAX, |end| usize::try_from(end).unwrap_or(usize::MAX)),
+            len: byte_len as usize,
+        }),
+    }
+}
+
+// The scalars overlapping bytes `offset..offset + len`, which must be non-empty
+fn covering_scalars(offset: u64, len: usize) -> std::ops::Range<usize> {
+    let per_scalar = BYTES_PER_SCALAR as u64;
+    let first = offset / per_scalar;
+    let last = (offset + len as u64 - 1) / per_scalar;
+    first as usize..last as usize + 1
+}
+
+pub fn commit_file(
+    committer: &GenericPolynomialCommitment,
+    path: impl AsRef<Path>,
+    chunk_size: usize,
+) -> Result<FileCommitment, Error> {
+    commit_file_with_progress(committer, path, chunk_size, &NoProgress)
+}
+
+// Reports after each chunk, against the file's size when it was opened
+pub fn commit_file_with_progress(
+    committer: &GenericPolynomialCommitment,
+    path: impl AsRef<Path>,
+    chunk_size: usize,
+    progress: &dyn Progress,
+) -> Result<FileCommitment, Error> {
+    let file = File::open(path)?;
+    let total = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+    let mut builder = ChunkedDataBuilder::new(committer, chunk_size)?;
+    let mut buffer = vec![0u8; chunk_size * BYTES_PER_SCALAR];
+    let mut byte_len = 0;
+    loop {
+        let read = read_full(&mut reader, &mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        builder.push_chunk(&pack_bytes(&buffer[..read]))?;
+        byte_len += read as u64;
+        progress.report(Operation::FileCommitment, byte_len, total.max(byte_len));
+        if read < buffer.len() {
+            break;
+        }
+    }
+    Ok(FileCommitment {
+        data: builder.finish(),
+        byte_len,
+    })
+}
+
+impl FileCommitment {
+    pub fn info(&self) -> FileCommitmentInfo {
+        FileCommitmentInfo {
+            chunks: self.data.commitment(),
+            byte_len: self.byte_len,
+        }
+    }
+
+    pub(crate) fn data(&self) -> &ChunkedData {
+        &self.data
+    }
+
+    pub fn prove_range(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        offset: u64,
+        len: usize,
+    ) -> Result<RangeProof, Error> {
+        check_range(offset, len, self.byte_len)?;
+        let scalars = covering_scalars(offset, len);
+        let mut values = Vec::with_capacity(scalars.len());
+        let mut proofs = Vec::with_capacity(scalars.len());
+        for index in scalars.clone() {
+            let (value, proof) = self.data.prove(committer, index)?;
+            values.push(value);
+            proofs.push(proof);
+        }
+        Ok(RangeProof {
+            first_scalar: scalars.start,
+            values,
+            proofs,
+        })
+    }
+}
+
+impl FileCommitmentInfo {
+    // Checks that `bytes` are the file's contents at `offset`
+    pub fn verify_range(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        offset: u64,
+        bytes: &[u8],
+        proof: &RangeProof,
+    ) -> Result<bool, Error> {
+        check_range(offset, bytes.len(), self.byte_len)?;
+        let scalars = covering_scalars(offset, bytes.len());
+        if proof.first_scalar != scalars.start
+            || proof.values.len() != scalars.len()
+            || proof.proofs.len() != scalars.len()
+        {
+            return Ok(false);
+        }
+
+        let start = (offset % BYTES_PER_SCALAR as u64) as usize;
+        if &unpack_bytes(&proof.values)[start..start + bytes.len()] != bytes {
+            return Ok(false);
+        }
+        for ((index, value), opening) in scalars.zip(&proof.values).zip(&proof.proofs) {
+            if !self.chunks.verify(committer, index, *value, opening)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[test]
+fn commits_to_files_and_proves_ranges() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(3, &mut test_rng());
+
+    // 300 bytes is ten scalars, so three chunks of four
+    let contents: Vec<u8> = (0..300u32).map(|i| (i * 7 % 256) as u8).collect();
+    let path = std::env::temp_dir().join(format!("kzg-file-commitment-{}", std::process::id()));
+    std::fs::write(&path, &contents).unwrap();
+    let file_commitment = commit_file(&committer, &path, 4).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let info = file_commitment.info();
+    assert_eq!(info.byte_len, 300);
+    assert_eq!(info.chunks.len, 10);
+
+    for (offset, len) in [(0, 1), (25, 40), (290, 10), (124, 31)] {
+        let proof = file_commitment
+            .prove_range(&committer, offset, len)
+            .unwrap();
+        let range = &contents[offset as usize..offset as usize + len];
+        assert_eq!(
+            info.verify_range(&committer, offset, range, &proof),
+            Ok(true)
+        );
+
+        let mut tampered = range.to_vec();
+        tampered[0] ^= 1;
+        assert_eq!(
+            info.verify_range(&committer, offset, &tampered, &proof),
+            Ok(false)
+        );
+    }
+
+    assert!(file_commitment.prove_range(&committer, 299, 2).is_err());
+    // Ranges whose end doesn't fit in a u64 are out of range rather than wrapping around
+    assert_eq!(
+        file_commitment.prove_range(&committer, u64::MAX, 2).err(),
+        Some(Error::IndexOutOfRange {
+            index: usize::MAX,
+            len: 300
+        })
+    );
+    let proof = file_commitment.prove_range(&committer, 0, 2).unwrap();
+    assert_eq!(
+        info.verify_range(&committer, u64::MAX - 1, &contents[..2], &proof),
+        Err(Error::IndexOutOfRange {
+            index: usize::MAX,
+            len: 300
+        })
+    );
+    assert_eq!(
+        commit_file(&committer, "/nonexistent/kzg-file", 4).err(),
+        Some(Error::Io(std::io::ErrorKind::NotFound))
+    );
+}
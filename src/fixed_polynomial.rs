@@ -0,0 +1,83 @@
+use blstrs::Scalar;
+use group::ff::Field;
+
+// A polynomial with at most N coefficients stored inline, lowest degree first, so evaluating it and dividing it for an
+// opening never touch the heap. The MSMs that commit and open still allocate their own scratch. Unused high
+// coefficients are zero
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedPolynomial<const N: usize>([Scalar; N]);
+
+impl<const N: usize> FixedPolynomial<N> {
+    pub fn new(coefficients: [Scalar; N]) -> Self {
+        FixedPolynomial(coefficients)
+    }
+
+    pub fn coeffs(&self) -> &[Scalar; N] {
+        &self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|c| bool::from(c.is_zero()))
+    }
+
+    // The degree ignoring zero high coefficients. The zero polynomial has no degree
+    pub fn degree(&self) -> Option<usize> {
+        self.0.iter().rposition(|c| !bool::from(c.is_zero()))
+    }
+
+    // Horner's rule
+    pub fn evaluate(&self, point: Scalar) -> Scalar {
+        self.0
+            .iter()
+            .rev()
+            .fold(Scalar::ZERO, |total, coefficient| {
+                total * point + coefficient
+            })
+    }
+
+    // Synthetic division by $(x - point)$, returning the quotient and the remainder, which is the evaluation at the
+    // point. The quotient has degree one lower, so it always fits in N coefficients
+    pub fn divide_by_linear(&self, point: Scalar) -> (FixedPolynomial<N>, Scalar) {
+        let mut quotient = [Scalar::ZERO; N];
+        let mut carry = Scalar::ZERO;
+        for i in (0..N).rev() {
+            let value = self.0[i] + carry * point;
+            if i > 0 {
+                quotient[i - 1] = value;
+            }
+            carry = value;
+        }
+        (FixedPolynomial(quotient), carry)
+    }
+}
+
+#[test]
+fn evaluates_and_divides_fixed_polynomials() {
+    use crate::polynomials::Polynomial;
+
+    let coefficients = [
+        -Scalar::from(1),
+        Scalar::from(3),
+        -Scalar::from(4),
+        Scalar::from(1),
+        Scalar::ZERO,
+    ];
+    let fixed = FixedPolynomial::new(coefficients);
+    let polynomial = Polynomial::new(&coefficients);
+    let point = Scalar::from(5_u64);
+
+    assert_eq!(fixed.degree(), Some(3));
+    assert_eq!(fixed.evaluate(point), Scalar::from(39_u64));
+
+    let (quotient, remainder) = fixed.divide_by_linear(point);
+    assert_eq!(remainder, polynomial.evaluate(point));
+    let mut dividend = polynomial.clone();
+    dividend.0[0] -= remainder;
+    assert_eq!(
+        Polynomial::new(quotient.coeffs()),
+        dividend / Polynomial::new(&[-point, Scalar::ONE])
+    );
+
+    assert!(FixedPolynomial::new([Scalar::ZERO; 3]).is_zero());
+    assert_eq!(FixedPolynomial::new([Scalar::ZERO; 3]).degree(), None);
+}
@@ -0,0 +1,89 @@
+use crate::polynomial_commitments::GlobalParameters;
+use blstrs::{G1Projective, G2Projective};
+use group::Group;
+
+// Hash-to-curve suites for derived generators, following the RFC 9380 naming scheme
+const G1_DST: &[u8] = b"KZG_GENERATOR_V1_BLS12381G1_XMD:SHA-256_SSWU_RO_";
+const G2_DST: &[u8] = b"KZG_GENERATOR_V1_BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+// The base points the powers of tau are built on. Setup normally uses the curve's standard generators, but protocols
+// that need a base nobody knows the discrete log of relative to the standard ones (a second base for hiding
+// commitments, say) can derive them from a domain tag instead. Derivation is public and deterministic, so anyone can
+// check the points weren't chosen with a trapdoor
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Generators {
+    pub g1: G1Projective,
+    pub g2: G2Projective,
+}
+
+impl Generators {
+    pub fn standard() -> Self {
+        Generators {
+            g1: G1Projective::generator(),
+            g2: G2Projective::generator(),
+        }
+    }
+
+    // Hashes the tag to each group. The tag must be non-empty and unique to the protocol using the generators
+    pub fn derive(domain_tag: &[u8]) -> Self {
+        assert!(!domain_tag.is_empty(), "a domain tag is required");
+        Generators {
+            g1: G1Projective::hash_to_curve(domain_tag, G1_DST, &[]),
+            g2: G2Projective::hash_to_curve(domain_tag, G2_DST, &[]),
+        }
+    }
+}
+
+impl Default for Generators {
+    fn default() -> Self {
+        Generators::standard()
+    }
+}
+
+impl GlobalParameters {
+    // The first power of each chain
+    pub fn generators(&self) -> Generators {
+        Generators {
+            g1: self.gs()[0],
+            g2: self.hs()[0],
+        }
+    }
+}
+
+#[test]
+fn derives_independent_generators() {
+    let derived = Generators::derive(b"pedersen blinding base");
+    assert_eq!(derived, Generators::derive(b"pedersen blinding base"));
+    assert_ne!(derived, Generators::derive(b"another protocol"));
+    assert_ne!(derived.g1, Generators::standard().g1);
+    assert_ne!(derived.g2, Generators::standard().g2);
+    assert!(!bool::from(derived.g1.is_identity()));
+}
+
+#[test]
+fn commits_and_verifies_over_derived_generators() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+    use blstrs::Scalar;
+
+    let generators = Generators::derive(b"test");
+    let mut committer = GenericPolynomialCommitment::new();
+    let gp = committer.setup_with_generators(3, generators, &mut test_rng());
+    assert_eq!(gp.generators(), generators);
+    assert!(gp.verify_powers(&mut test_rng()));
+
+    let polynomial = Polynomial::new_from_i64(&[1, 2, 3, 4]);
+    let commitment = committer.commit(&polynomial).unwrap();
+    let (witness, evaluation) = committer
+        .create_witness(&polynomial, Scalar::from(5))
+        .unwrap();
+    assert!(committer
+        .verify_evaluation(commitment, Scalar::from(5), evaluation, witness)
+        .unwrap());
+
+    // Saved parameters keep their generators
+    let reloaded = GlobalParameters::from_bytes(&gp.to_bytes()).unwrap();
+    assert_eq!(reloaded.generators(), generators);
+    assert!(GlobalParameters::try_new(gp.gs().to_vec(), gp.hs().to_vec()).is_err());
+}
@@ -0,0 +1,95 @@
+use crate::error::Error;
+use blstrs::{G1Affine, G1Projective, Scalar};
+use group::Curve;
+
+// Encodings matching gnark-crypto's BLS12-381 types, so commitments and proofs can pass between this crate and Go
+// services using gnark. gnark's compressed points use the same flag bits as the ZCash format blstrs implements (0x80
+// compressed, 0x40 infinity, 0x20 for the lexicographically larger y), so points need no conversion. Field elements are
+// 32 bytes big-endian where this crate otherwise uses little-endian
+
+pub const GNARK_PROOF_SIZE: usize = 48 + 32;
+
+// `bls12381.G1Affine.Bytes()`, which is what gnark's `kzg.Digest` serializes to
+pub fn g1_to_gnark(point: &G1Projective) -> [u8; 48] {
+    point.to_affine().to_compressed()
+}
+
+pub fn g1_from_gnark(bytes: &[u8; 48]) -> Result<G1Projective, Error> {
+    Option::<G1Affine>::from(G1Affine::from_compressed(bytes))
+        .map(G1Projective::from)
+        .ok_or(Error::InvalidPoint {
+            group: "G1",
+            index: 0,
+        })
+}
+
+// `fr.Element.Bytes()`
+pub fn scalar_to_gnark(scalar: &Scalar) -> [u8; 32] {
+    scalar.to_bytes_be()
+}
+
+// Rejects non-canonical values like gnark's `SetBytesCanonical`
+pub fn scalar_from_gnark(bytes: &[u8; 32]) -> Result<Scalar, Error> {
+    Option::<Scalar>::from(Scalar::from_bytes_be(bytes)).ok_or(Error::InvalidScalar { index: 0 })
+}
+
+// gnark's `kzg.OpeningProof`, encoded as its `WriteTo` does: the compressed quotient commitment `H` followed by
+// `ClaimedValue`
+pub fn opening_proof_to_gnark(
+    witness: &G1Projective,
+    evaluation: &Scalar,
+) -> [u8; GNARK_PROOF_SIZE] {
+    let mut bytes = [0u8; GNARK_PROOF_SIZE];
+    bytes[..48].copy_from_slice(&g1_to_gnark(witness));
+    bytes[48..].copy_from_slice(&scalar_to_gnark(evaluation));
+    bytes
+}
+
+pub fn opening_proof_from_gnark(bytes: &[u8]) -> Result<(G1Projective, Scalar), Error> {
+    if bytes.len() != GNARK_PROOF_SIZE {
+        return Err(Error::InvalidEncoding(
+            "wrong length for a gnark opening proof",
+        ));
+    }
+    let witness = g1_from_gnark(bytes[..48].try_into().unwrap())?;
+    let evaluation = scalar_from_gnark(bytes[48..].try_into().unwrap())?;
+    Ok((witness, evaluation))
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn round_trips_gnark_encodings() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+    use group::Group;
+
+    // gnark's encoding of the generator and of the point at infinity
+    let generator = g1_to_gnark(&G1Projective::generator());
+    assert_eq!(generator[0], 0x97);
+    assert_eq!(g1_to_gnark(&G1Projective::identity())[0], 0xc0);
+    assert_eq!(scalar_to_gnark(&Scalar::from(1))[31], 1);
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(2, &mut test_rng());
+    let polynomial = Polynomial::new_from_bytes(&[1, 2, 3]);
+    let point = Scalar::from(5);
+    let commitment = committer.commit(&polynomial).unwrap();
+    let (witness, evaluation) = committer.create_witness(&polynomial, point).unwrap();
+
+    let digest = g1_to_gnark(&commitment);
+    let proof = opening_proof_to_gnark(&witness, &evaluation);
+    let (decoded_witness, decoded_evaluation) = opening_proof_from_gnark(&proof).unwrap();
+    assert_eq!(
+        committer.verify_evaluation(
+            g1_from_gnark(&digest).unwrap(),
+            point,
+            decoded_evaluation,
+            decoded_witness
+        ),
+        Ok(true)
+    );
+
+    assert!(scalar_from_gnark(&[0xff; 32]).is_err());
+    assert!(opening_proof_from_gnark(&proof[..79]).is_err());
+}
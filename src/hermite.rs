@@ -0,0 +1,115 @@
+use crate::constant_time::ct_eq_gt;
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::polynomials::Polynomial;
+use blstrs::{pairing, G1Projective, Scalar};
+use group::{Curve, Group};
+
+// Hermite openings prove the value and the first derivative at a point together. Dividing by $(x - z)^2$ leaves
+// $p(x) = q(x)(x - z)^2 + r(x)$ with $r(x) = p(z) + p'(z)(x - z)$, so the verifier checks
+// $e(C - [r(\tau)]_1, h) = e([q(\tau)]_1, [(\tau - z)^2]_2)$. That needs $\tau^2$ in G2
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HermiteOpening {
+    pub witness: G1Projective,
+    pub evaluation: Scalar,
+    pub derivative: Scalar,
+}
+
+impl GenericPolynomialCommitment {
+    pub fn create_hermite_witness(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+    ) -> Result<HermiteOpening, Error> {
+        self.global_parameters()?;
+        if polynomial.is_zero() {
+            return Err(Error::ZeroPolynomial);
+        }
+        let evaluation = polynomial.evaluate(point);
+        let derivative = polynomial.derivative().evaluate(point);
+
+        let square = Polynomial::vanishing(&[point, point]);
+        let (quotient, remainder) = polynomial.div_rem(&square)?;
+        // Sanity check the remainder is the tangent line at the point
+        debug_assert_eq!(remainder.evaluate(point), evaluation);
+        if quotient.is_zero() {
+            return Err(Error::IdentityWitness);
+        }
+        Ok(HermiteOpening {
+            witness: self.msm(quotient.coeffs())?,
+            evaluation,
+            derivative,
+        })
+    }
+
+    pub fn verify_hermite(
+        &self,
+        committed_polynomial: G1Projective,
+        point: Scalar,
+        opening: &HermiteOpening,
+    ) -> Result<bool, Error> {
+        let global_parameters = self.global_parameters()?;
+        if global_parameters.gs().len() < 2 || global_parameters.hs().len() < 3 {
+            return Err(Error::InvalidParameters(
+                "hermite verification needs tau^2 in G2",
+            ));
+        }
+        if bool::from(committed_polynomial.is_identity()) {
+            return Err(Error::IdentityCommitment);
+        }
+        if bool::from(opening.witness.is_identity()) {
+            return Err(Error::IdentityWitness);
+        }
+
+        // $[r(\tau)]_1$ for $r(x) = (p(z) - p'(z) z) + p'(z) x$
+        let remainder = self.msm(&[
+            opening.evaluation - opening.derivative * point,
+            opening.derivative,
+        ])?;
+        // $[(\tau - z)^2]_2$
+        let square =
+            global_parameters.commit_in_g2(Polynomial::vanishing(&[point, point]).coeffs())?;
+
+        let lhs = pairing(
+            &(committed_polynomial - remainder).to_affine(),
+            &global_parameters.hs()[0].to_affine(),
+        );
+        let rhs = pairing(&opening.witness.to_affine(), &square.to_affine());
+        Ok(ct_eq_gt(&lhs, &rhs).into())
+    }
+}
+
+#[test]
+fn proves_value_and_derivative() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(4, &mut test_rng());
+    let polynomial = Polynomial::new_from_i64(&[2, -1, 3, -4, 1]);
+    let commitment = committer.commit(&polynomial).unwrap();
+    let point = Scalar::from(3);
+
+    let opening = committer
+        .create_hermite_witness(&polynomial, point)
+        .unwrap();
+    assert_eq!(opening.evaluation, polynomial.evaluate(point));
+    assert_eq!(opening.derivative, polynomial.derivative().evaluate(point));
+    assert_eq!(
+        committer.verify_hermite(commitment, point, &opening),
+        Ok(true)
+    );
+
+    let wrong_derivative = HermiteOpening {
+        derivative: opening.derivative + Scalar::from(1),
+        ..opening
+    };
+    assert_eq!(
+        committer.verify_hermite(commitment, point, &wrong_derivative),
+        Ok(false)
+    );
+    assert_eq!(
+        committer.verify_hermite(commitment, Scalar::from(4), &opening),
+        Ok(false)
+    );
+}
@@ -0,0 +1,230 @@
+use crate::constant_time::ct_eq_gt;
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::polynomials::Polynomial;
+use crate::secret::zeroize_scalar;
+use blstrs::{pairing, G1Projective, G2Projective, Scalar};
+use group::ff::Field;
+use group::{Curve, Group};
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Evaluation at a point the prover never sees. The verifier picks z and a blinding factor $\alpha$, and sends the
+// powers of z in G2, the same powers times $\alpha$, and in G1 the quotient bases
+// $B_i = [(\tau^i - z^i) / (\tau - z)]_1 = \sum_{k < i} z^{i-1-k} [\tau^k]_1$, which it can compute from the
+// parameters since it knows z. For a committed p the prover answers with $Y = \sum_i p_i [z^i]_2 = [p(z)]_2$, the same
+// sum over the blinded powers, and $W = \sum_i p_i B_i = [q(\tau)]_1$ for $q = (p - p(z)) / (x - z)$. The verifier
+// checks $e(C, h) = e(W, [\tau]_2 - [z]_2) \cdot e([1]_1, Y)$, and that the blinded answer is $\alpha Y$, which is what
+// stops the prover mixing powers of tau into Y to balance a made-up W.
+//
+// What the verifier learns is $[p(z)]_2$, a commitment to the value rather than the value; it can test candidate
+// values against it. The prover only ever sees z in the exponent, so z stays hidden under discrete log as long as it
+// comes from a large set; a z drawn from a few possibilities can be found by trying them. Only the prover is
+// constrained: nothing checks that the query is well-formed, and bases that aren't powers of one point get back other
+// combinations of the coefficients, so the verifier has to be trusted to follow the protocol
+
+// Kept by the verifier. Wiped on drop
+pub struct HiddenPointSecret {
+    point: Scalar,
+    blinding: Scalar,
+}
+
+// Sent to the prover
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HiddenPointQuery {
+    pub powers: Vec<G2Projective>,
+    pub blinded_powers: Vec<G2Projective>,
+    pub quotient_bases: Vec<G1Projective>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HiddenEvaluation {
+    // $[p(z)]_2$
+    pub value: G2Projective,
+    pub blinded_value: G2Projective,
+    pub witness: G1Projective,
+}
+
+impl HiddenPointSecret {
+    pub fn new<R: RngCore + CryptoRng>(point: Scalar, rng: &mut R) -> Self {
+        HiddenPointSecret {
+            point,
+            blinding: Scalar::random(rng),
+        }
+    }
+
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        HiddenPointSecret::new(Scalar::random(&mut *rng), rng)
+    }
+
+    pub fn expose_point(&self) -> Scalar {
+        self.point
+    }
+
+    // Covers polynomials of any degree the committer's parameters support
+    pub fn query(
+        &self,
+        committer: &GenericPolynomialCommitment,
+    ) -> Result<HiddenPointQuery, Error> {
+        let global_parameters = committer.global_parameters()?;
+        let gs = global_parameters.gs();
+        let h = global_parameters.hs()[0];
+
+        let mut powers = Vec::with_capacity(gs.len());
+        let mut quotient_bases = Vec::with_capacity(gs.len());
+        let mut power = h;
+        let mut base = G1Projective::identity();
+        for g in gs {
+            powers.push(power);
+            quotient_bases.push(base);
+            power *= self.point;
+            // $B_{i+1} = z B_i + [\tau^i]_1$
+            base = base * self.point + g;
+        }
+        let blinded_powers = powers.iter().map(|power| power * self.blinding).collect();
+        Ok(HiddenPointQuery {
+            powers,
+            blinded_powers,
+            quotient_bases,
+        })
+    }
+
+    // Checks the answer is for the committed polynomial. The value is then in `evaluation.value`
+    pub fn verify(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        commitment: G1Projective,
+        evaluation: &HiddenEvaluation,
+    ) -> Result<bool, Error> {
+        let global_parameters = committer.global_parameters()?;
+        if global_parameters.hs().len() < 2 {
+            return Err(Error::InvalidParameters("verification needs tau in G2"));
+        }
+        if evaluation.blinded_value != evaluation.value * self.blinding {
+            return Ok(false);
+        }
+        let g = global_parameters.gs()[0];
+        let h = global_parameters.hs()[0];
+        let h_tau = global_parameters.hs()[1];
+
+        let lhs = pairing(&commitment.to_affine(), &h.to_affine());
+        let rhs = pairing(
+            &evaluation.witness.to_affine(),
+            &(h_tau - h * self.point).to_affine(),
+        ) + pairing(&g.to_affine(), &evaluation.value.to_affine());
+        Ok(ct_eq_gt(&lhs, &rhs).into())
+    }
+
+    // Whether a verified evaluation is `value`
+    pub fn reveals(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        evaluation: &HiddenEvaluation,
+        value: Scalar,
+    ) -> Result<bool, Error> {
+        let h = committer.global_parameters()?.hs()[0];
+        Ok(evaluation.value == h * value)
+    }
+}
+
+impl Zeroize for HiddenPointSecret {
+    fn zeroize(&mut self) {
+        zeroize_scalar(&mut self.point);
+        zeroize_scalar(&mut self.blinding);
+    }
+}
+
+impl Drop for HiddenPointSecret {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for HiddenPointSecret {}
+
+impl core::fmt::Debug for HiddenPointSecret {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("HiddenPointSecret(..)")
+    }
+}
+
+impl GenericPolynomialCommitment {
+    // The prover's side. Only needs the query, not the parameters
+    pub fn evaluate_at_hidden_point(
+        &self,
+        polynomial: &Polynomial,
+        query: &HiddenPointQuery,
+    ) -> Result<HiddenEvaluation, Error> {
+        let len = polynomial.len();
+        if query.blinded_powers.len() != query.powers.len()
+            || query.quotient_bases.len() != query.powers.len()
+        {
+            return Err(Error::InvalidEncoding(
+                "query has different numbers of powers and bases",
+            ));
+        }
+        if len > query.powers.len() {
+            return Err(Error::DegreeTooLarge {
+                max: query.powers.len().saturating_sub(1),
+                actual: len - 1,
+            });
+        }
+        if len == 0 {
+            return Ok(HiddenEvaluation {
+                value: G2Projective::identity(),
+                blinded_value: G2Projective::identity(),
+                witness: G1Projective::identity(),
+            });
+        }
+        let coefficients = polynomial.coeffs();
+        Ok(HiddenEvaluation {
+            value: G2Projective::multi_exp(&query.powers[..len], coefficients),
+            blinded_value: G2Projective::multi_exp(&query.blinded_powers[..len], coefficients),
+            witness: G1Projective::multi_exp(&query.quotient_bases[..len], coefficients),
+        })
+    }
+}
+
+#[test]
+fn evaluates_at_a_hidden_point() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(4, &mut rng);
+    let polynomial = Polynomial::new_from_i64(&[2, -1, 3, -4, 1]);
+    let commitment = committer.commit(&polynomial).unwrap();
+
+    let secret = HiddenPointSecret::new(Scalar::from(6), &mut rng);
+    let query = secret.query(&committer).unwrap();
+    let evaluation = committer
+        .evaluate_at_hidden_point(&polynomial, &query)
+        .unwrap();
+    assert_eq!(secret.verify(&committer, commitment, &evaluation), Ok(true));
+    let value = polynomial.evaluate(Scalar::from(6));
+    assert_eq!(secret.reveals(&committer, &evaluation, value), Ok(true));
+    assert_eq!(
+        secret.reveals(&committer, &evaluation, value + Scalar::ONE),
+        Ok(false)
+    );
+
+    // Another polynomial's answer doesn't verify against this commitment
+    let other = Polynomial::new_from_i64(&[2, -1, 3, -4, 2]);
+    let forged = committer.evaluate_at_hidden_point(&other, &query).unwrap();
+    assert_eq!(secret.verify(&committer, commitment, &forged), Ok(false));
+
+    // Nor does $Y = [p(\tau)]_2$ with $W = 0$, which balances the pairing equation but isn't in the span of the
+    // powers of z
+    let gp = committer.global_parameters().unwrap();
+    let unblinded = HiddenEvaluation {
+        value: gp.commit_in_g2(polynomial.coeffs()).unwrap(),
+        blinded_value: G2Projective::identity(),
+        witness: G1Projective::identity(),
+    };
+    assert_eq!(secret.verify(&committer, commitment, &unblinded), Ok(false));
+
+    assert!(committer
+        .evaluate_at_hidden_point(&Polynomial::new_from_i64(&[1; 6]), &query)
+        .is_err());
+}
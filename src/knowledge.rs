@@ -0,0 +1,193 @@
+use crate::constant_time::ct_eq_g1;
+use crate::error::Error;
+use crate::polynomial_commitments::GlobalParameters;
+use crate::polynomials::Polynomial;
+use crate::secret::{zeroize_scalar, zeroize_scalars};
+use crate::transcript::Transcript;
+use blstrs::{G1Projective, Scalar};
+use group::ff::Field;
+use rand::{CryptoRng, RngCore};
+
+// Schnorr-style proofs of knowledge, made non-interactive through a `Transcript`. The caller starts the transcript
+// with its protocol label and absorbs whatever context the proof is bound to, e.g. a ceremony round or a dealer's
+// index; the proof absorbs its statement and first message itself before squeezing the challenge. Verifying replays
+// the same transcript, so the challenge can't be chosen after the nonce commitment
+
+// Knowledge of x with $P = x B$, e.g. the secret a ceremony participant multiplied the previous powers of tau by
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DlogProof {
+    pub nonce_commitment: G1Projective,
+    pub response: Scalar,
+}
+
+// Knowledge of coefficients $p_i$ with $C = \sum_i p_i [\tau^i]_1$. The response is a whole polynomial, so the proof
+// is as long as what it hides; it's for settings like VSS where the dealer's polynomial is small
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpeningKnowledgeProof {
+    pub nonce_commitment: G1Projective,
+    pub responses: Vec<Scalar>,
+}
+
+fn dlog_challenge<T: Transcript>(
+    transcript: &mut T,
+    base: &G1Projective,
+    public: &G1Projective,
+    nonce_commitment: &G1Projective,
+) -> Scalar {
+    transcript.append_g1(b"dlog base", base);
+    transcript.append_g1(b"dlog public", public);
+    transcript.append_g1(b"dlog nonce commitment", nonce_commitment);
+    transcript.challenge_scalar(b"dlog challenge")
+}
+
+// $k$ random, $R = k B$, $c$ from the transcript, $s = k + c x$
+pub fn prove_dlog<T: Transcript, R: RngCore + CryptoRng>(
+    transcript: &mut T,
+    base: &G1Projective,
+    secret: &Scalar,
+    rng: &mut R,
+) -> DlogProof {
+    let mut nonce = Scalar::random(rng);
+    let nonce_commitment = base * nonce;
+    let challenge = dlog_challenge(transcript, base, &(base * secret), &nonce_commitment);
+    let response = nonce + challenge * secret;
+    zeroize_scalar(&mut nonce);
+    DlogProof {
+        nonce_commitment,
+        response,
+    }
+}
+
+// $s B = R + c P$
+pub fn verify_dlog<T: Transcript>(
+    transcript: &mut T,
+    base: &G1Projective,
+    public: &G1Projective,
+    proof: &DlogProof,
+) -> bool {
+    let challenge = dlog_challenge(transcript, base, public, &proof.nonce_commitment);
+    ct_eq_g1(
+        &(base * proof.response),
+        &(proof.nonce_commitment + public * challenge),
+    )
+    .into()
+}
+
+fn opening_challenge<T: Transcript>(
+    transcript: &mut T,
+    commitment: &G1Projective,
+    nonce_commitment: &G1Projective,
+) -> Scalar {
+    transcript.append_g1(b"knowledge commitment", commitment);
+    transcript.append_g1(b"knowledge nonce commitment", nonce_commitment);
+    transcript.challenge_scalar(b"knowledge challenge")
+}
+
+impl GlobalParameters {
+    // The same protocol as `prove_dlog` with the powers of tau as bases: a random polynomial r of the same length,
+    // $R = [r(\tau)]_1$, and $s = r + c p$ coefficient by coefficient
+    pub fn prove_knowledge<T: Transcript, R: RngCore + CryptoRng>(
+        &self,
+        transcript: &mut T,
+        polynomial: &Polynomial,
+        rng: &mut R,
+    ) -> Result<OpeningKnowledgeProof, Error> {
+        let commitment = self.commit_in_g1(polynomial.coeffs())?;
+        let mut nonces: Vec<Scalar> = (0..polynomial.len())
+            .map(|_| Scalar::random(&mut *rng))
+            .collect();
+        let nonce_commitment = self.commit_in_g1(&nonces)?;
+        let challenge = opening_challenge(transcript, &commitment, &nonce_commitment);
+        let responses = nonces
+            .iter()
+            .zip(polynomial.iter())
+            .map(|(nonce, coefficient)| nonce + challenge * coefficient)
+            .collect();
+        zeroize_scalars(&mut nonces);
+        Ok(OpeningKnowledgeProof {
+            nonce_commitment,
+            responses,
+        })
+    }
+
+    // $[s(\tau)]_1 = R + c C$. Errs if the response has more coefficients than there are powers of tau
+    pub fn verify_knowledge<T: Transcript>(
+        &self,
+        transcript: &mut T,
+        commitment: &G1Projective,
+        proof: &OpeningKnowledgeProof,
+    ) -> Result<bool, Error> {
+        let challenge = opening_challenge(transcript, commitment, &proof.nonce_commitment);
+        let response_commitment = self.commit_in_g1(&proof.responses)?;
+        Ok(ct_eq_g1(
+            &response_commitment,
+            &(proof.nonce_commitment + commitment * challenge),
+        )
+        .into())
+    }
+}
+
+#[test]
+fn proves_knowledge_of_openings_and_discrete_logs() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::rng::test_rng;
+    use crate::transcript::Sha256Transcript;
+
+    let mut rng = test_rng();
+    let mut committer = GenericPolynomialCommitment::new();
+    let global_parameters = committer.setup_with_rng(3, &mut test_rng());
+
+    let polynomial = Polynomial::rand(3, &mut rng);
+    let commitment = committer.commit(&polynomial).unwrap();
+    let proof = global_parameters
+        .prove_knowledge(&mut Sha256Transcript::new(b"test"), &polynomial, &mut rng)
+        .unwrap();
+    assert_eq!(
+        global_parameters.verify_knowledge(
+            &mut Sha256Transcript::new(b"test"),
+            &commitment,
+            &proof
+        ),
+        Ok(true)
+    );
+    // Bound to the transcript's context and to the commitment
+    assert_eq!(
+        global_parameters.verify_knowledge(
+            &mut Sha256Transcript::new(b"other"),
+            &commitment,
+            &proof
+        ),
+        Ok(false)
+    );
+    assert_eq!(
+        global_parameters.verify_knowledge(
+            &mut Sha256Transcript::new(b"test"),
+            &(commitment + global_parameters.gs()[0]),
+            &proof
+        ),
+        Ok(false)
+    );
+
+    // A ceremony participant proving the secret behind its update of the first power of tau
+    let base = global_parameters.gs()[1];
+    let secret = Scalar::random(&mut rng);
+    let updated = base * secret;
+    let proof = prove_dlog(
+        &mut Sha256Transcript::new(b"test"),
+        &base,
+        &secret,
+        &mut rng,
+    );
+    assert!(verify_dlog(
+        &mut Sha256Transcript::new(b"test"),
+        &base,
+        &updated,
+        &proof
+    ));
+    assert!(!verify_dlog(
+        &mut Sha256Transcript::new(b"test"),
+        &base,
+        &base,
+        &proof
+    ));
+}
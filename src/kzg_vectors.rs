@@ -0,0 +1,347 @@
+use crate::blob::{Blob, FIELD_ELEMENTS_PER_BLOB};
+use crate::domain::EvaluationDomain;
+use crate::encoding::decode_hex;
+use crate::error::Error;
+use crate::polynomial_commitments::{GenericPolynomialCommitment, GlobalParameters};
+use crate::types::{Commitment, FieldElement, Proof};
+use blstrs::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use serde_yaml::Value;
+use std::path::{Path, PathBuf};
+
+// Runs the blob functions against the c-kzg-4844 reference vectors. A vector is a YAML (or JSON) document with an
+// `input` map of 0x-prefixed hex and an `output` that is null when the input must be rejected. The vectors are made
+// with the Ethereum ceremony's setup, which c-kzg ships as `trusted_setup.txt`
+
+// The vector directories under c-kzg's `tests` this loader runs
+pub const HANDLERS: [&str; 3] = [
+    "blob_to_kzg_commitment",
+    "compute_kzg_proof",
+    "verify_kzg_proof",
+];
+
+// `trusted_setup.txt`: the number of G1 points and of G2 points, then the G1 points in the Lagrange basis of the
+// domain in natural order, then the monomial G2 points, all compressed hex. Newer files go on to list the monomial G1
+// points, which are ignored since they're the FFT of the Lagrange ones
+pub fn parse_trusted_setup(text: &str) -> Result<GlobalParameters, Error> {
+    let mut tokens = text.split_whitespace();
+    let mut count = || -> Result<usize, Error> {
+        tokens
+            .next()
+            .and_then(|token| token.parse().ok())
+            .ok_or(Error::InvalidEncoding(
+                "trusted setup doesn't start with its point counts",
+            ))
+    };
+    let (g1_count, g2_count) = (count()?, count()?);
+    let domain = EvaluationDomain::new(g1_count)
+        .filter(|domain| domain.size() == g1_count)
+        .ok_or(Error::InvalidParameters(
+            "Lagrange points must cover a power of two domain",
+        ))?;
+
+    let lagrange = (0..g1_count)
+        .map(|index| {
+            tokens
+                .next()
+                .and_then(decode_hex)
+                .and_then(|bytes| <[u8; 48]>::try_from(bytes).ok())
+                .and_then(|bytes| Option::<G1Affine>::from(G1Affine::from_compressed(&bytes)))
+                .map(G1Projective::from)
+                .ok_or(Error::InvalidPoint { group: "G1", index })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let hs = (0..g2_count)
+        .map(|index| {
+            tokens
+                .next()
+                .and_then(decode_hex)
+                .and_then(|bytes| <[u8; 96]>::try_from(bytes).ok())
+                .and_then(|bytes| Option::<G2Affine>::from(G2Affine::from_compressed(&bytes)))
+                .map(G2Projective::from)
+                .ok_or(Error::InvalidPoint { group: "G2", index })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // The Lagrange basis is the inverse transform of the powers of tau
+    GlobalParameters::try_new(domain.fft_g1(&lagrange), hs)
+}
+
+// Whether the committer gives the vector's output for its input. Errs when the committer has no parameters, or when
+// the document isn't a vector for `handler`
+pub fn check_vector(
+    committer: &GenericPolynomialCommitment,
+    handler: &str,
+    document: &str,
+) -> Result<bool, Error> {
+    let max_degree = committer.max_degree()?;
+    let vector: Value = serde_yaml::from_str(document)?;
+    let (input, output) = (&vector["input"], &vector["output"]);
+
+    match handler {
+        "blob_to_kzg_commitment" => {
+            let expected = optional(output, hex)?;
+            let actual = Blob::from_bytes(&hex(&input["blob"])?)
+                .and_then(|blob| blob.commit(committer))
+                .map(|commitment| commitment.point_bytes().to_vec());
+            Ok(actual.ok() == expected)
+        }
+        "compute_kzg_proof" => {
+            let expected = optional(output, |output| {
+                match output.as_sequence().map(Vec::as_slice) {
+                    Some([proof, evaluation]) => Ok((hex(proof)?, hex(evaluation)?)),
+                    _ => Err(Error::InvalidEncoding(
+                        "proof output isn't a proof and a value",
+                    )),
+                }
+            })?;
+            let blob = Blob::from_bytes(&hex(&input["blob"])?);
+            let actual = field_element(&hex(&input["z"])?)
+                .and_then(|point| blob?.prove(committer, point))
+                .map(|(proof, evaluation)| {
+                    (
+                        proof.to_bytes().to_vec(),
+                        evaluation.inner().to_bytes_be().to_vec(),
+                    )
+                });
+            Ok(actual.ok() == expected)
+        }
+        "verify_kzg_proof" => {
+            let expected = optional(output, |output| {
+                output.as_bool().ok_or(Error::InvalidEncoding(
+                    "verification output isn't a boolean",
+                ))
+            })?;
+            let commitment = hex(&input["commitment"])?;
+            let (point, evaluation) = (hex(&input["z"])?, hex(&input["y"])?);
+            let proof = hex(&input["proof"])?;
+            let actual = (|| -> Result<bool, Error> {
+                // Ceremony commitments are to blobs, so they're over the blob domain
+                let commitment = Commitment::from_parts(
+                    Commitment::from_point_bytes(
+                        &<[u8; 48]>::try_from(commitment.as_slice())?,
+                        max_degree,
+                    )?
+                    .inner(),
+                    max_degree,
+                    Some(FIELD_ELEMENTS_PER_BLOB),
+                );
+                let proof = Proof::from_bytes(&<[u8; 48]>::try_from(proof.as_slice())?)?;
+                Blob::verify(
+                    committer,
+                    &commitment,
+                    field_element(&point)?,
+                    field_element(&evaluation)?,
+                    &proof,
+                )
+            })();
+            Ok(actual.ok() == expected)
+        }
+        _ => Err(Error::InvalidEncoding("not a handler with blob vectors")),
+    }
+}
+
+// Checks every `data.yaml` or `data.json` under each of the `HANDLERS` directories in `tests_dir`, and returns the
+// paths of the vectors whose output the committer didn't reproduce
+pub fn run_vectors(
+    committer: &GenericPolynomialCommitment,
+    tests_dir: &Path,
+) -> Result<Vec<PathBuf>, Error> {
+    let mut failures = Vec::new();
+    for handler in HANDLERS {
+        for path in vector_files(&tests_dir.join(handler))? {
+            let document = std::fs::read_to_string(&path)?;
+            if !check_vector(committer, handler, &document)? {
+                failures.push(path);
+            }
+        }
+    }
+    Ok(failures)
+}
+
+fn vector_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(vector_files(&path)?);
+        } else if matches!(
+            path.file_name().and_then(|name| name.to_str()),
+            Some("data.yaml" | "data.json")
+        ) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn hex(value: &Value) -> Result<Vec<u8>, Error> {
+    value
+        .as_str()
+        .and_then(decode_hex)
+        .ok_or(Error::InvalidEncoding("vector field isn't hex"))
+}
+
+// A null output means the input has to be rejected
+fn optional<T>(
+    output: &Value,
+    parse: impl Fn(&Value) -> Result<T, Error>,
+) -> Result<Option<T>, Error> {
+    match output {
+        Value::Null => Ok(None),
+        output => parse(output).map(Some),
+    }
+}
+
+// The vectors encode field elements big-endian, as the spec does
+fn field_element(bytes: &[u8]) -> Result<FieldElement, Error> {
+    Option::<Scalar>::from(Scalar::from_bytes_be(&<[u8; 32]>::try_from(bytes)?))
+        .map(FieldElement::from_inner)
+        .ok_or(Error::InvalidScalar { index: 0 })
+}
+
+#[test]
+fn runs_vectors_made_with_a_trusted_setup_file() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+    use group::Curve;
+
+    let to_hex = |bytes: &[u8]| {
+        let digits: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        format!("0x{}", digits)
+    };
+
+    // A setup file in c-kzg's layout, with the 65 G2 points the ceremony has
+    let global_parameters = GenericPolynomialCommitment::new()
+        .setup_with_rng(FIELD_ELEMENTS_PER_BLOB - 1, &mut test_rng());
+    let domain = EvaluationDomain::new(FIELD_ELEMENTS_PER_BLOB).unwrap();
+    let mut text = format!("{}\n65\n", FIELD_ELEMENTS_PER_BLOB);
+    for point in domain.ifft_g1(global_parameters.gs()) {
+        text += &format!("{}\n", &to_hex(&point.to_affine().to_compressed())[2..]);
+    }
+    for point in &global_parameters.hs()[..65] {
+        text += &format!("{}\n", &to_hex(&point.to_affine().to_compressed())[2..]);
+    }
+    let parsed = parse_trusted_setup(&text).unwrap();
+    assert_eq!(parsed.gs(), global_parameters.gs());
+    assert_eq!(parsed.hs(), &global_parameters.hs()[..65]);
+    assert!(parse_trusted_setup(&text[..text.len() / 2]).is_err());
+    let committer = GenericPolynomialCommitment::with_global_parameters(parsed);
+
+    let mut bytes = vec![0u8; crate::blob::BYTES_PER_BLOB];
+    for (i, chunk) in bytes.chunks_exact_mut(32).enumerate() {
+        chunk[28..].copy_from_slice(&(i as u32 * 7 + 1).to_be_bytes());
+    }
+    let blob = Blob::from_bytes(&bytes).unwrap();
+    let commitment = to_hex(&blob.commit(&committer).unwrap().point_bytes());
+    let point = FieldElement::from(987654321);
+    let (proof, evaluation) = blob.prove(&committer, point).unwrap();
+    let (proof, evaluation) = (
+        to_hex(&proof.to_bytes()),
+        to_hex(&evaluation.inner().to_bytes_be()),
+    );
+    let (blob_hex, z) = (to_hex(&bytes), to_hex(&point.inner().to_bytes_be()));
+    let mut non_canonical = bytes.clone();
+    non_canonical[..32].copy_from_slice(&[0xff; 32]);
+    let non_canonical = to_hex(&non_canonical);
+
+    let check = |handler: &str, document: String| check_vector(&committer, handler, &document);
+    assert_eq!(
+        check(
+            "blob_to_kzg_commitment",
+            format!("input:\n  blob: '{blob_hex}'\noutput: '{commitment}'\n")
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        check(
+            "blob_to_kzg_commitment",
+            format!("input:\n  blob: '{non_canonical}'\noutput: null\n")
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        check(
+            "blob_to_kzg_commitment",
+            format!("input:\n  blob: '{non_canonical}'\noutput: '{commitment}'\n")
+        ),
+        Ok(false)
+    );
+    // JSON vectors parse too
+    assert_eq!(
+        check(
+            "compute_kzg_proof",
+            format!(
+                r#"{{"input": {{"blob": "{blob_hex}", "z": "{z}"}}, "output": ["{proof}", "{evaluation}"]}}"#
+            )
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        check(
+            "compute_kzg_proof",
+            format!(
+                "input:\n  blob: '{blob_hex}'\n  z: '0x{}'\noutput: null\n",
+                "ff".repeat(32)
+            )
+        ),
+        Ok(true)
+    );
+
+    let verify = |evaluation: &str, output: &str| {
+        check(
+            "verify_kzg_proof",
+            format!(
+                "input:\n  commitment: '{commitment}'\n  z: '{z}'\n  y: '{evaluation}'\n  \
+                 proof: '{proof}'\noutput: {output}\n"
+            ),
+        )
+    };
+    assert_eq!(verify(&evaluation, "true"), Ok(true));
+    assert_eq!(verify(&z, "false"), Ok(true));
+    assert_eq!(verify(&z, "true"), Ok(false));
+    assert_eq!(verify("0x00", "null"), Ok(true));
+
+    // The same vectors laid out as c-kzg's tests directory
+    let dir = std::env::temp_dir().join(format!("kzg-vectors-{}", std::process::id()));
+    for (handler, document) in [
+        (
+            "blob_to_kzg_commitment",
+            format!("input:\n  blob: '{blob_hex}'\noutput: '{commitment}'\n"),
+        ),
+        (
+            "compute_kzg_proof",
+            format!("input:\n  blob: '{blob_hex}'\n  z: '{z}'\noutput: ['{proof}', '{evaluation}']\n"),
+        ),
+        (
+            "verify_kzg_proof",
+            format!(
+                "input:\n  commitment: '{commitment}'\n  z: '{z}'\n  y: '{z}'\n  proof: '{proof}'\noutput: true\n"
+            ),
+        ),
+    ] {
+        let case = dir.join(handler).join("kzg-mainnet").join("case_0");
+        std::fs::create_dir_all(&case).unwrap();
+        std::fs::write(case.join("data.yaml"), document).unwrap();
+    }
+    let failures = run_vectors(&committer, &dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        failures.unwrap(),
+        vec![dir.join("verify_kzg_proof/kzg-mainnet/case_0/data.yaml")]
+    );
+
+    assert!(check(
+        "blob_to_kzg_commitment",
+        "input: {}\noutput: null\n".to_string()
+    )
+    .is_err());
+    assert!(check(
+        "compute_challenge",
+        format!("input:\n  blob: '{blob_hex}'\noutput: null\n")
+    )
+    .is_err());
+    assert_eq!(
+        check_vector(&GenericPolynomialCommitment::new(), "verify_kzg_proof", ""),
+        Err(Error::SetupIncomplete)
+    );
+}
@@ -0,0 +1,95 @@
+use crate::domain::EvaluationDomain;
+use crate::error::Error;
+use crate::polynomial_commitments::GlobalParameters;
+use blstrs::{G1Projective, Scalar};
+
+// The powers of tau in the Lagrange basis of an evaluation domain, $[L_i(\tau)]_1$. Derived from the monomial powers,
+// as `setup` or a ptau file gives them, with an inverse FFT over G1, so no separate Lagrange setup has to be
+// distributed. Committing to values over the domain is then one MSM, without interpolating them first
+#[derive(Clone, Debug)]
+pub struct LagrangeBasis {
+    domain: EvaluationDomain,
+    points: Vec<G1Projective>,
+}
+
+impl GlobalParameters {
+    // `size` must be a power of two no larger than the number of powers of tau. Takes $O(n \log n)$ group operations
+    pub fn lagrange_basis(&self, size: usize) -> Result<LagrangeBasis, Error> {
+        if !size.is_power_of_two() || size > self.gs().len() {
+            return Err(Error::InvalidParameters(
+                "Lagrange basis size must be a power of two covered by the setup",
+            ));
+        }
+        let domain = EvaluationDomain::new(size).ok_or(Error::InvalidParameters(
+            "Lagrange basis size is too large for an evaluation domain",
+        ))?;
+        // $L_i(x) = \frac{1}{n} \sum_j \omega^{-ij} x^j$, so the basis is the inverse transform of the powers
+        let points = domain.ifft_g1(&self.gs()[..size]);
+        Ok(LagrangeBasis { domain, points })
+    }
+}
+
+impl LagrangeBasis {
+    pub fn domain(&self) -> &EvaluationDomain {
+        &self.domain
+    }
+
+    pub fn points(&self) -> &[G1Projective] {
+        &self.points
+    }
+
+    // `evaluations[i]` is the value at the i-th domain element. The same commitment as to the interpolated polynomial
+    pub fn commit(&self, evaluations: &[Scalar]) -> Result<G1Projective, Error> {
+        if evaluations.len() != self.points.len() {
+            return Err(Error::LengthMismatch {
+                expected: self.points.len(),
+                actual: evaluations.len(),
+            });
+        }
+        Ok(G1Projective::multi_exp(&self.points, evaluations))
+    }
+}
+
+#[test]
+fn commits_to_evaluations_without_interpolating() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let global_parameters = committer.setup_with_rng(7, &mut test_rng());
+    let basis = global_parameters.lagrange_basis(8).unwrap();
+
+    let polynomial = Polynomial::rand(7, &mut test_rng());
+    let evaluations = basis.domain().fft(polynomial.coeffs());
+    assert_eq!(
+        basis.commit(&evaluations).unwrap(),
+        committer.commit(&polynomial).unwrap()
+    );
+
+    // A smaller domain uses a prefix of the powers
+    let small = global_parameters.lagrange_basis(4).unwrap();
+    let polynomial = Polynomial::rand(3, &mut test_rng());
+    assert_eq!(
+        small.commit(&small.domain().fft(polynomial.coeffs())),
+        global_parameters.commit_in_g1(polynomial.coeffs())
+    );
+
+    assert!(global_parameters.lagrange_basis(6).is_err());
+    assert!(global_parameters.lagrange_basis(16).is_err());
+    assert_eq!(
+        basis.commit(&evaluations[..4]),
+        Err(Error::LengthMismatch {
+            expected: 8,
+            actual: 4
+        })
+    );
+
+    // A single point is its own Lagrange basis
+    let single = global_parameters.lagrange_basis(1).unwrap();
+    assert_eq!(single.points(), &global_parameters.gs()[..1]);
+    assert_eq!(
+        single.commit(&[Scalar::from(5)]),
+        global_parameters.commit_in_g1(&[Scalar::from(5)])
+    );
+}
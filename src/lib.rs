@@ -1,2 +1,113 @@
+mod archive;
+mod batch;
+mod constant_time;
+mod degree;
+mod error;
+mod gnark;
+mod merkle;
+mod pairing_check;
+pub mod prelude;
+mod scalars;
+mod ssz;
+pub mod transcript;
+mod types;
+mod verifier_key;
+mod versioned_hash;
+
+#[cfg(feature = "prover")]
+mod blob;
+#[cfg(feature = "prover")]
+mod blob_stream;
+#[cfg(feature = "prover")]
+mod byte_commitment;
+#[cfg(feature = "prover")]
+mod cancel;
+#[cfg(feature = "prover")]
+mod chunked;
+#[cfg(feature = "prover")]
+mod constancy;
+#[cfg(feature = "prover")]
+mod distributed_msm;
+#[cfg(feature = "prover")]
+mod domain;
+#[cfg(feature = "prover")]
+mod domain_polynomial;
+#[cfg(feature = "prover")]
+mod dyn_commitment;
+#[cfg(feature = "prover")]
+mod encoding;
+#[cfg(feature = "prover")]
+mod extension;
+#[cfg(feature = "prover")]
+mod file_commitment;
+#[cfg(feature = "prover")]
+mod fixed_polynomial;
+#[cfg(feature = "prover")]
+mod generators;
+#[cfg(feature = "prover")]
+mod hermite;
+#[cfg(feature = "prover")]
+mod hidden_point;
+#[cfg(feature = "prover")]
+mod knowledge;
+#[cfg(feature = "prover")]
+mod lagrange;
+#[cfg(feature = "prover")]
+mod linear_code;
+#[cfg(feature = "prover")]
+mod manifest;
+#[cfg(feature = "prover")]
+mod membership;
+#[cfg(feature = "prover")]
+mod msm;
+#[cfg(feature = "prover")]
+mod opening_cache;
+#[cfg(feature = "prover")]
+mod oracle;
+#[cfg(feature = "prover")]
+mod parallel;
+#[cfg(feature = "prover")]
 mod polynomial_commitments;
+#[cfg(feature = "prover")]
 mod polynomials;
+#[cfg(feature = "prover")]
+mod precomputed_openings;
+#[cfg(feature = "prover")]
+mod progress;
+#[cfg(feature = "prover")]
+mod range_opening;
+#[cfg(feature = "prover")]
+mod registry;
+#[cfg(feature = "prover")]
+mod retrievability;
+#[cfg(feature = "prover")]
+mod rng;
+#[cfg(feature = "prover")]
+mod secret;
+#[cfg(feature = "prover")]
+mod sparse;
+#[cfg(feature = "prover")]
+mod srs_cache;
+#[cfg(feature = "prover")]
+mod streaming;
+#[cfg(feature = "prover")]
+mod swapped;
+#[cfg(feature = "prover")]
+mod threshold;
+#[cfg(feature = "prover")]
+mod toeplitz;
+
+#[cfg(feature = "ark")]
+mod ark;
+#[cfg(feature = "async")]
+mod async_prover;
+#[cfg(feature = "test-utils")]
+pub mod conformance;
+#[cfg(feature = "conformance")]
+pub mod kzg_vectors;
+#[cfg(feature = "differential")]
+mod differential;
+#[cfg(feature = "fetch")]
+mod fetch;
+#[cfg(feature = "proto")]
+mod proto;
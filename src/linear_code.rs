@@ -0,0 +1,407 @@
+use crate::domain::EvaluationDomain;
+use crate::error::Error;
+use crate::merkle::{hash_leaf, Hash, MerklePath, MerkleTree};
+use crate::polynomial_commitments::PolynomialCommitment;
+use crate::polynomials::Polynomial;
+use crate::transcript::{Sha256Transcript, Transcript};
+use blstrs::Scalar;
+use group::ff::Field;
+use rand::{CryptoRng, RngCore};
+
+// A transparent commitment in the style of Ligero and Brakedown: only hashes and a linear code, so no trusted setup,
+// no pairings, and nothing a quantum computer breaks. The coefficients fill a rows × k matrix M row by row, each
+// row is encoded to a codeword, and the commitment is the Merkle root over the columns of the encoded matrix.
+//
+// Writing $a_i = z^{ik}$ and $b_j = z^j$, $p(z) = a^T M b$. An opening sends $u = a^T M$, from which the evaluation is
+// $\langle u, b \rangle$, and $u' = r^T M$ for random r, which tests that the committed rows really are codewords.
+// By linearity $\mathrm{Enc}(u)$ must agree with $a^T$ times the encoded matrix in every column, so the verifier
+// checks that in randomly chosen columns it receives with their Merkle paths. Proofs are $O(\sqrt n)$ field elements
+// rather than one group element, which is the price of the cheap prover.
+//
+// Each opened column catches a matrix far from the code with probability about a third of the code's relative
+// distance, so for 128 bits a rate 1/4 Reed-Solomon code needs around 300 columns. Brakedown's linear-time expander
+// code can be used through `LinearCode`; the code provided here is Reed-Solomon, which encodes in $O(k \log k)$
+pub trait LinearCode {
+    fn message_len(&self) -> usize;
+    fn codeword_len(&self) -> usize;
+    // Must be linear. `message` is exactly `message_len` long
+    fn encode(&self, message: &[Scalar]) -> Vec<Scalar>;
+}
+
+// Messages as coefficients, codewords as their evaluations over a domain `blowup` times larger
+#[derive(Clone, Debug)]
+pub struct ReedSolomon {
+    message_len: usize,
+    domain: EvaluationDomain,
+}
+
+impl ReedSolomon {
+    // `None` unless the blowup is a power of two of at least 2 and the codewords fit in a domain
+    pub fn new(message_len: usize, blowup: usize) -> Option<Self> {
+        if message_len == 0 || blowup < 2 || !blowup.is_power_of_two() {
+            return None;
+        }
+        let domain = EvaluationDomain::new(message_len.checked_mul(blowup)?)?;
+        Some(ReedSolomon {
+            message_len,
+            domain,
+        })
+    }
+}
+
+impl LinearCode for ReedSolomon {
+    fn message_len(&self) -> usize {
+        self.message_len
+    }
+
+    fn codeword_len(&self) -> usize {
+        self.domain.size()
+    }
+
+    fn encode(&self, message: &[Scalar]) -> Vec<Scalar> {
+        self.domain.fft(message)
+    }
+}
+
+pub struct LinearCodeCommitment<C> {
+    code: C,
+    rows: usize,
+    columns_opened: usize,
+}
+
+// What the prover keeps from committing
+#[derive(Clone, Debug)]
+pub struct CommittedMatrix {
+    rows: Vec<Vec<Scalar>>,
+    encoded: Vec<Vec<Scalar>>,
+    tree: MerkleTree,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinearCodeProof {
+    pub evaluation_row: Vec<Scalar>,
+    pub proximity_row: Vec<Scalar>,
+    // Each opened column of the encoded matrix, top to bottom, with its path
+    pub columns: Vec<(Vec<Scalar>, MerklePath)>,
+}
+
+fn column_leaf(column: &[Scalar]) -> Hash {
+    let bytes: Vec<u8> = column
+        .iter()
+        .flat_map(|value| value.to_bytes_le())
+        .collect();
+    hash_leaf(&bytes)
+}
+
+fn inner_product(a: &[Scalar], b: &[Scalar]) -> Scalar {
+    a.iter().zip(b).map(|(x, y)| *x * y).sum()
+}
+
+// $1, x, x^2, \ldots$
+fn powers(x: Scalar, len: usize) -> Vec<Scalar> {
+    let mut power = Scalar::ONE;
+    (0..len)
+        .map(|_| {
+            let current = power;
+            power *= x;
+            current
+        })
+        .collect()
+}
+
+impl<C: LinearCode> LinearCodeCommitment<C> {
+    pub fn new(code: C, rows: usize, columns_opened: usize) -> Self {
+        LinearCodeCommitment {
+            code,
+            rows: rows.max(1),
+            columns_opened,
+        }
+    }
+
+    // The most coefficients a committed polynomial can have
+    pub fn max_len(&self) -> usize {
+        self.rows * self.code.message_len()
+    }
+
+    pub fn commit(&self, polynomial: &Polynomial) -> Result<(Hash, CommittedMatrix), Error> {
+        if polynomial.len() > self.max_len() {
+            return Err(Error::DegreeTooLarge {
+                max: self.max_len() - 1,
+                actual: polynomial.len() - 1,
+            });
+        }
+        let k = self.code.message_len();
+        let rows: Vec<Vec<Scalar>> = (0..self.rows)
+            .map(|i| (0..k).map(|j| polynomial.coeff(i * k + j)).collect())
+            .collect();
+        let encoded: Vec<Vec<Scalar>> = rows.iter().map(|row| self.code.encode(row)).collect();
+        let leaves = (0..self.code.codeword_len())
+            .map(|j| column_leaf(&encoded.iter().map(|row| row[j]).collect::<Vec<_>>()))
+            .collect();
+        let tree = MerkleTree::new(leaves);
+        Ok((
+            tree.root(),
+            CommittedMatrix {
+                rows,
+                encoded,
+                tree,
+            },
+        ))
+    }
+
+    // Absorbs the statement and squeezes the proximity test's coefficients
+    fn proximity_challenge<T: Transcript>(
+        &self,
+        transcript: &mut T,
+        root: &Hash,
+        point: Scalar,
+    ) -> Vec<Scalar> {
+        transcript.append_bytes(b"linear code root", root);
+        transcript.append_scalar(b"linear code point", &point);
+        (0..self.rows)
+            .map(|_| transcript.challenge_scalar(b"linear code proximity"))
+            .collect()
+    }
+
+    // Absorbs the rows and squeezes which columns to open. Reducing 64 bits mod the codeword length is biased by
+    // less than $2^{-40}$ for any practical length
+    fn column_challenge<T: Transcript>(
+        &self,
+        transcript: &mut T,
+        evaluation_row: &[Scalar],
+        proximity_row: &[Scalar],
+    ) -> Vec<usize> {
+        for value in evaluation_row.iter().chain(proximity_row) {
+            transcript.append_scalar(b"linear code row", value);
+        }
+        (0..self.columns_opened)
+            .map(|_| {
+                let bytes = transcript
+                    .challenge_scalar(b"linear code column")
+                    .to_bytes_le();
+                let index = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+                (index % self.code.codeword_len() as u64) as usize
+            })
+            .collect()
+    }
+
+    // $a^T M$ for the rows of M
+    fn combine(coefficients: &[Scalar], rows: &[Vec<Scalar>], len: usize) -> Vec<Scalar> {
+        (0..len)
+            .map(|j| {
+                coefficients
+                    .iter()
+                    .zip(rows)
+                    .map(|(c, row)| *c * row[j])
+                    .sum()
+            })
+            .collect()
+    }
+
+    pub fn open<T: Transcript>(
+        &self,
+        matrix: &CommittedMatrix,
+        point: Scalar,
+        transcript: &mut T,
+    ) -> (LinearCodeProof, Scalar) {
+        let k = self.code.message_len();
+        let r = self.proximity_challenge(transcript, &matrix.tree.root(), point);
+        let a = powers(point.pow_vartime([k as u64]), self.rows);
+        let evaluation_row = Self::combine(&a, &matrix.rows, k);
+        let proximity_row = Self::combine(&r, &matrix.rows, k);
+        let evaluation = inner_product(&evaluation_row, &powers(point, k));
+
+        let columns = self
+            .column_challenge(transcript, &evaluation_row, &proximity_row)
+            .into_iter()
+            .map(|j| {
+                let column = matrix.encoded.iter().map(|row| row[j]).collect();
+                // Can unwrap since the index was reduced mod the number of columns
+                (column, matrix.tree.path(j).unwrap())
+            })
+            .collect();
+        (
+            LinearCodeProof {
+                evaluation_row,
+                proximity_row,
+                columns,
+            },
+            evaluation,
+        )
+    }
+
+    // Replays the transcript the prover used. Malformed proofs are rejected rather than erring
+    pub fn verify<T: Transcript>(
+        &self,
+        root: &Hash,
+        point: Scalar,
+        evaluation: Scalar,
+        proof: &LinearCodeProof,
+        transcript: &mut T,
+    ) -> bool {
+        let k = self.code.message_len();
+        if proof.evaluation_row.len() != k
+            || proof.proximity_row.len() != k
+            || proof.columns.len() != self.columns_opened
+        {
+            return false;
+        }
+        let r = self.proximity_challenge(transcript, root, point);
+        let a = powers(point.pow_vartime([k as u64]), self.rows);
+        if inner_product(&proof.evaluation_row, &powers(point, k)) != evaluation {
+            return false;
+        }
+
+        let indices =
+            self.column_challenge(transcript, &proof.evaluation_row, &proof.proximity_row);
+        let encoded_evaluation = self.code.encode(&proof.evaluation_row);
+        let encoded_proximity = self.code.encode(&proof.proximity_row);
+        indices
+            .into_iter()
+            .zip(&proof.columns)
+            .all(|(j, (column, path))| {
+                column.len() == self.rows
+                    && path.index == j
+                    && path.verify(root, &column_leaf(column))
+                    && inner_product(&a, column) == encoded_evaluation[j]
+                    && inner_product(&r, column) == encoded_proximity[j]
+            })
+    }
+}
+
+// The protocol label of the transcript each opening starts, when the scheme is used as a `PolynomialCommitment`
+const TRANSCRIPT_PROTOCOL: &[u8] = b"linear code commitment";
+
+// The scheme as a backend next to KZG. It's transparent, so setup publishes nothing and only sets the number of rows
+// for polynomials of degree d; the rng goes unused. Every opening gets a fresh transcript, and the witness is built
+// from the polynomial by committing to it again, which costs as much as encoding the rows
+impl<C: LinearCode> PolynomialCommitment for LinearCodeCommitment<C> {
+    type Parameters = ();
+    type Commitment = Hash;
+    type Witness = LinearCodeProof;
+
+    fn setup_with_rng<R: RngCore + CryptoRng>(&mut self, d: usize, _: &mut R) {
+        self.rows = (d + 1).div_ceil(self.code.message_len());
+    }
+
+    fn commit(&self, polynomial: &Polynomial) -> Result<Hash, Error> {
+        LinearCodeCommitment::commit(self, polynomial).map(|(root, _)| root)
+    }
+
+    fn create_witness(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+    ) -> Result<(LinearCodeProof, Scalar), Error> {
+        let (_, matrix) = LinearCodeCommitment::commit(self, polynomial)?;
+        Ok(self.open(
+            &matrix,
+            point,
+            &mut Sha256Transcript::new(TRANSCRIPT_PROTOCOL),
+        ))
+    }
+
+    fn verify_evaluation(
+        &self,
+        committed_polynomial: Hash,
+        point: Scalar,
+        evaluation: Scalar,
+        witness: LinearCodeProof,
+    ) -> Result<bool, Error> {
+        Ok(self.verify(
+            &committed_polynomial,
+            point,
+            evaluation,
+            &witness,
+            &mut Sha256Transcript::new(TRANSCRIPT_PROTOCOL),
+        ))
+    }
+}
+
+#[test]
+fn commits_and_opens_without_pairings() {
+    use crate::rng::test_rng;
+    use crate::transcript::Sha256Transcript;
+
+    let code = ReedSolomon::new(8, 4).unwrap();
+    let scheme = LinearCodeCommitment::new(code, 4, 12);
+    assert_eq!(scheme.max_len(), 32);
+
+    let polynomial = Polynomial::rand(20, &mut test_rng());
+    let (root, matrix) = scheme.commit(&polynomial).unwrap();
+    let point = Scalar::from(11);
+    let (proof, evaluation) = scheme.open(&matrix, point, &mut Sha256Transcript::new(b"test"));
+    assert_eq!(evaluation, polynomial.evaluate(point));
+    assert!(scheme.verify(
+        &root,
+        point,
+        evaluation,
+        &proof,
+        &mut Sha256Transcript::new(b"test")
+    ));
+
+    assert!(!scheme.verify(
+        &root,
+        point,
+        evaluation + Scalar::ONE,
+        &proof,
+        &mut Sha256Transcript::new(b"test")
+    ));
+    let mut tampered = proof.clone();
+    tampered.columns[0].0[1] += Scalar::ONE;
+    assert!(!scheme.verify(
+        &root,
+        point,
+        evaluation,
+        &tampered,
+        &mut Sha256Transcript::new(b"test")
+    ));
+    let mut tampered = proof.clone();
+    tampered.evaluation_row[0] += Scalar::ONE;
+    tampered.evaluation_row[1] -= point.invert().unwrap();
+    // Same evaluation, but no longer a combination of the committed rows
+    assert_eq!(
+        inner_product(&tampered.evaluation_row, &powers(point, 8)),
+        evaluation
+    );
+    assert!(!scheme.verify(
+        &root,
+        point,
+        evaluation,
+        &tampered,
+        &mut Sha256Transcript::new(b"test")
+    ));
+
+    assert!(scheme
+        .commit(&Polynomial::rand(32, &mut test_rng()))
+        .is_err());
+    assert!(ReedSolomon::new(8, 3).is_none());
+}
+
+#[test]
+fn is_a_polynomial_commitment_backend() {
+    use crate::rng::test_rng;
+
+    let mut scheme = LinearCodeCommitment::new(ReedSolomon::new(8, 4).unwrap(), 1, 12);
+    // Setup only sizes the matrix
+    PolynomialCommitment::setup_with_rng(&mut scheme, 40, &mut test_rng());
+    assert_eq!(scheme.max_len(), 48);
+
+    let polynomial = Polynomial::rand(40, &mut test_rng());
+    let root = PolynomialCommitment::commit(&scheme, &polynomial).unwrap();
+    let point = Scalar::from(5);
+    let (proof, evaluation) = scheme.create_witness(&polynomial, point).unwrap();
+    assert_eq!(evaluation, polynomial.evaluate(point));
+    assert_eq!(
+        scheme.verify_evaluation(root, point, evaluation, proof.clone()),
+        Ok(true)
+    );
+    assert_eq!(
+        scheme.verify_evaluation(root, point + Scalar::ONE, evaluation, proof.clone()),
+        Ok(false)
+    );
+    assert_eq!(
+        scheme.verify_evaluation(root, point, evaluation + Scalar::ONE, proof),
+        Ok(false)
+    );
+}
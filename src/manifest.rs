@@ -0,0 +1,310 @@
+use crate::chunked::{commitment_leaf, ChunkedCommitment, ChunkedProof};
+use crate::degree::MaxDegree;
+use crate::encoding::BYTES_PER_SCALAR;
+use crate::error::Error;
+use crate::file_commitment::{FileCommitment, FileCommitmentInfo};
+use crate::merkle::MerkleTree;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use blstrs::{G1Affine, G1Projective, Scalar};
+use group::Curve;
+
+// A self-describing record of a committed file, for handing a commitment from the tool that made it to tools that
+// check it. Besides the root it lists every chunk commitment, so a reader can rebuild any Merkle path without the
+// file, and it can carry openings computed ahead of time. All integers are little-endian u64s and points are
+// compressed in the ZCash format.
+//
+// Layout:
+//
+// - `KZGM`, then the version byte 1
+// - the file's byte length, the number of scalars, the chunk size in scalars and the bytes packed per scalar (31)
+// - the max degree of the setup the chunks were committed with
+// - the 32-byte Merkle root
+// - the number of chunks, then one 48-byte commitment per chunk
+// - the number of precomputed openings, then per opening the scalar index, the 32-byte value and the 48-byte witness
+const MAGIC: &[u8; 4] = b"KZGM";
+const VERSION: u8 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileManifest {
+    info: FileCommitmentInfo,
+    max_degree: MaxDegree,
+    chunk_commitments: Vec<G1Projective>,
+    openings: Vec<ManifestOpening>,
+}
+
+// The scalar at `index` and the KZG witness for it in its chunk. The Merkle path comes from the manifest
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ManifestOpening {
+    pub index: usize,
+    pub value: Scalar,
+    pub witness: G1Projective,
+}
+
+impl FileCommitment {
+    // Precomputes openings of the scalars at `openings`, which may be empty
+    pub fn manifest(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        openings: &[usize],
+    ) -> Result<FileManifest, Error> {
+        let openings = openings
+            .iter()
+            .map(|&index| {
+                let (value, proof) = self.data().prove(committer, index)?;
+                Ok(ManifestOpening {
+                    index,
+                    value,
+                    witness: proof.witness,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(FileManifest {
+            info: self.info(),
+            max_degree: committer.max_degree()?,
+            chunk_commitments: self.data().chunk_commitments().to_vec(),
+            openings,
+        })
+    }
+}
+
+// Reads from a byte cursor, failing on truncation
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(Error::InvalidEncoding("file manifest is truncated"))?;
+        let taken = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(taken)
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    // A count or index, which must fit in memory on this platform
+    fn usize(&mut self) -> Result<usize, Error> {
+        usize::try_from(self.u64()?)
+            .map_err(|_| Error::InvalidEncoding("file manifest length is too large"))
+    }
+
+    fn point(&mut self, index: usize) -> Result<G1Projective, Error> {
+        let bytes: &[u8; 48] = self.take(48)?.try_into().unwrap();
+        Option::<G1Affine>::from(G1Affine::from_compressed(bytes))
+            .map(G1Projective::from)
+            .ok_or(Error::InvalidPoint { group: "G1", index })
+    }
+}
+
+impl FileManifest {
+    // Enough to verify ranges of the file
+    pub fn info(&self) -> FileCommitmentInfo {
+        self.info
+    }
+
+    // The setup a verifier has to load to check the openings
+    pub fn max_degree(&self) -> MaxDegree {
+        self.max_degree
+    }
+
+    pub fn chunk_commitments(&self) -> &[G1Projective] {
+        &self.chunk_commitments
+    }
+
+    pub fn openings(&self) -> &[ManifestOpening] {
+        &self.openings
+    }
+
+    // A full proof for a precomputed opening, with its Merkle path rebuilt from the chunk commitments
+    pub fn proof(&self, opening: &ManifestOpening) -> Result<ChunkedProof, Error> {
+        let chunk_index = opening.index / self.info.chunks.chunk_size;
+        let chunk_commitment =
+            *self
+                .chunk_commitments
+                .get(chunk_index)
+                .ok_or(Error::IndexOutOfRange {
+                    index: opening.index,
+                    len: self.info.chunks.len,
+                })?;
+        let tree = MerkleTree::new(self.chunk_commitments.iter().map(commitment_leaf).collect());
+        Ok(ChunkedProof {
+            chunk_commitment,
+            // The chunk was found above, so it has a leaf
+            path: tree.path(chunk_index).unwrap(),
+            witness: opening.witness,
+        })
+    }
+
+    // Checks every precomputed opening against the root. Fails if `committer` has a setup of another degree
+    pub fn verify_openings(&self, committer: &GenericPolynomialCommitment) -> Result<bool, Error> {
+        committer.max_degree()?.check(self.max_degree)?;
+        for opening in &self.openings {
+            let proof = self.proof(opening)?;
+            if !self
+                .info
+                .chunks
+                .verify(committer, opening.index, opening.value, &proof)?
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.push(VERSION);
+        for value in [
+            self.info.byte_len,
+            self.info.chunks.len as u64,
+            self.info.chunks.chunk_size as u64,
+            BYTES_PER_SCALAR as u64,
+            self.max_degree.get() as u64,
+        ] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out.extend_from_slice(&self.info.chunks.root);
+        out.extend_from_slice(&(self.chunk_commitments.len() as u64).to_le_bytes());
+        for commitment in &self.chunk_commitments {
+            out.extend_from_slice(&commitment.to_affine().to_compressed());
+        }
+        out.extend_from_slice(&(self.openings.len() as u64).to_le_bytes());
+        for opening in &self.openings {
+            out.extend_from_slice(&(opening.index as u64).to_le_bytes());
+            out.extend_from_slice(&opening.value.to_bytes_le());
+            out.extend_from_slice(&opening.witness.to_affine().to_compressed());
+        }
+        out
+    }
+
+    // Rejects manifests whose layout doesn't add up or whose chunk commitments don't hash to the root. The openings
+    // are only decoded; `verify_openings` checks them
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut cursor = Cursor { bytes, offset: 0 };
+        if cursor.take(MAGIC.len())? != MAGIC || cursor.take(1)?[0] != VERSION {
+            return Err(Error::InvalidEncoding("not a version 1 file manifest"));
+        }
+        let byte_len = cursor.u64()?;
+        let len = cursor.usize()?;
+        let chunk_size = cursor.usize()?;
+        if cursor.u64()? != BYTES_PER_SCALAR as u64 {
+            return Err(Error::InvalidEncoding(
+                "file manifest packs bytes differently",
+            ));
+        }
+        let max_degree = MaxDegree::new(cursor.usize()?);
+        let root = cursor.take(32)?.try_into().unwrap();
+
+        if !chunk_size.is_power_of_two() || byte_len.div_ceil(BYTES_PER_SCALAR as u64) != len as u64
+        {
+            return Err(Error::InvalidEncoding(
+                "file manifest layout is inconsistent",
+            ));
+        }
+        let chunk_count = cursor.usize()?;
+        if chunk_count != len.div_ceil(chunk_size) {
+            return Err(Error::InvalidEncoding(
+                "file manifest has the wrong number of chunks",
+            ));
+        }
+        let chunk_commitments = (0..chunk_count)
+            .map(|index| cursor.point(index))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let tree = MerkleTree::new(chunk_commitments.iter().map(commitment_leaf).collect());
+        if tree.root() != root {
+            return Err(Error::InvalidEncoding(
+                "file manifest root doesn't match its chunk commitments",
+            ));
+        }
+
+        let opening_count = cursor.usize()?;
+        let mut openings = vec![];
+        for i in 0..opening_count {
+            let index = cursor.usize()?;
+            let value =
+                Option::<Scalar>::from(Scalar::from_bytes_le(cursor.take(32)?.try_into().unwrap()))
+                    .ok_or(Error::InvalidScalar { index: i })?;
+            let witness = cursor.point(chunk_count + i)?;
+            openings.push(ManifestOpening {
+                index,
+                value,
+                witness,
+            });
+        }
+        if cursor.offset != bytes.len() {
+            return Err(Error::InvalidEncoding("file manifest has trailing bytes"));
+        }
+
+        Ok(FileManifest {
+            info: FileCommitmentInfo {
+                chunks: ChunkedCommitment {
+                    root,
+                    chunk_size,
+                    len,
+                },
+                byte_len,
+            },
+            max_degree,
+            chunk_commitments,
+            openings,
+        })
+    }
+}
+
+#[test]
+fn manifests_round_trip_and_verify() {
+    use crate::file_commitment::commit_file;
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(3, &mut test_rng());
+
+    // 300 bytes is ten scalars in three chunks
+    let contents: Vec<u8> = (0..300u32).map(|i| (i * 13 % 256) as u8).collect();
+    let path = std::env::temp_dir().join(format!("kzg-file-manifest-{}", std::process::id()));
+    std::fs::write(&path, &contents).unwrap();
+    let file_commitment = commit_file(&committer, &path, 4).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let manifest = file_commitment.manifest(&committer, &[0, 5, 9]).unwrap();
+    assert_eq!(manifest.chunk_commitments().len(), 3);
+    let decoded = FileManifest::from_bytes(&manifest.to_bytes()).unwrap();
+    assert_eq!(decoded, manifest);
+    assert_eq!(decoded.info(), file_commitment.info());
+    assert_eq!(decoded.verify_openings(&committer), Ok(true));
+
+    // The rebuilt proofs are the ones the prover would have made
+    let opening = decoded.openings()[1];
+    assert_eq!(
+        decoded.proof(&opening).unwrap(),
+        file_commitment.data().prove(&committer, 5).unwrap().1
+    );
+
+    // A wrong value is caught by verification; a wrong chunk commitment already by decoding
+    let mut bytes = manifest.to_bytes();
+    let value_offset = bytes.len() - 48 - 32;
+    bytes[value_offset] ^= 1;
+    assert_eq!(
+        FileManifest::from_bytes(&bytes)
+            .unwrap()
+            .verify_openings(&committer),
+        Ok(false)
+    );
+    let mut bytes = manifest.to_bytes();
+    let chunks_offset = MAGIC.len() + 1 + 5 * 8 + 32 + 8;
+    bytes[chunks_offset..chunks_offset + 48]
+        .copy_from_slice(&manifest.chunk_commitments()[1].to_affine().to_compressed());
+    assert!(FileManifest::from_bytes(&bytes).is_err());
+    assert!(FileManifest::from_bytes(&manifest.to_bytes()[1..]).is_err());
+
+    // Opening past the end of the file
+    assert!(file_commitment.manifest(&committer, &[10]).is_err());
+}
@@ -0,0 +1,105 @@
+use crate::constant_time::ct_eq_gt;
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::polynomials::Polynomial;
+use blstrs::{pairing, G1Projective, G2Projective, Scalar};
+use group::{Curve, Group};
+
+// Proofs that a committed polynomial vanishes on a whole set S of points, i.e. that $Z_S(x) = \prod_{s \in S} (x - s)$
+// divides it. The witness is a commitment to $p / Z_S$ and the verifier checks $e(C, g_2) = e(W, [Z_S(\tau)]_2)$.
+// KZG-based accumulators prove that every element of S is a member this way
+impl GenericPolynomialCommitment {
+    // Commitment to $p / Z_S$. Errs with `NonZeroRemainder` if p doesn't vanish on all of S
+    pub fn create_vanishing_witness(
+        &self,
+        polynomial: &Polynomial,
+        roots: &[Scalar],
+    ) -> Result<G1Projective, Error> {
+        self.global_parameters()?;
+        if polynomial.is_zero() {
+            return Err(Error::ZeroPolynomial);
+        }
+        let (quotient, remainder) = polynomial.div_rem(&Polynomial::vanishing(roots))?;
+        if let Some(remainder_degree) = remainder.degree() {
+            return Err(Error::NonZeroRemainder { remainder_degree });
+        }
+        self.msm(quotient.coeffs())
+    }
+
+    // $[Z_S(\tau)]_2$, which the verifier can compute once per set and reuse. Needs |S| + 1 powers of tau in G2
+    pub fn commit_vanishing_polynomial(&self, roots: &[Scalar]) -> Result<G2Projective, Error> {
+        self.global_parameters()?
+            .commit_in_g2(Polynomial::vanishing(roots).coeffs())
+    }
+
+    pub fn verify_vanishing(
+        &self,
+        committed_polynomial: G1Projective,
+        vanishing_commitment: G2Projective,
+        witness: G1Projective,
+    ) -> Result<bool, Error> {
+        let global_parameters = self.global_parameters()?;
+        if bool::from(committed_polynomial.is_identity()) {
+            return Err(Error::IdentityCommitment);
+        }
+        if bool::from(witness.is_identity()) {
+            return Err(Error::IdentityWitness);
+        }
+
+        let lhs = pairing(
+            &committed_polynomial.to_affine(),
+            &global_parameters.hs()[0].to_affine(),
+        );
+        let rhs = pairing(&witness.to_affine(), &vanishing_commitment.to_affine());
+        Ok(ct_eq_gt(&lhs, &rhs).into())
+    }
+}
+
+#[test]
+fn proves_a_polynomial_vanishes_on_a_set() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut polynomial_committer = GenericPolynomialCommitment::new();
+    polynomial_committer.setup_with_rng(5, &mut rng);
+
+    // Degree 5 with roots 1, 2 and 3, and two more that aren't in the set
+    let members = [Scalar::from(1), Scalar::from(2), Scalar::from(3)];
+    let roots = [
+        members[0],
+        members[1],
+        members[2],
+        Scalar::from(8),
+        Scalar::from(9),
+    ];
+    let polynomial = Polynomial::rand_with_roots(&roots, &mut rng);
+    let commitment = polynomial_committer.commit(&polynomial).unwrap();
+
+    let witness = polynomial_committer
+        .create_vanishing_witness(&polynomial, &members)
+        .unwrap();
+    let vanishing_commitment = polynomial_committer
+        .commit_vanishing_polynomial(&members)
+        .unwrap();
+    assert_eq!(
+        polynomial_committer.verify_vanishing(commitment, vanishing_commitment, witness),
+        Ok(true)
+    );
+
+    // The same witness doesn't convince the verifier about a different set
+    let other_set = [members[0], members[1], Scalar::from(4)];
+    let other_commitment = polynomial_committer
+        .commit_vanishing_polynomial(&other_set)
+        .unwrap();
+    assert_eq!(
+        polynomial_committer.verify_vanishing(commitment, other_commitment, witness),
+        Ok(false)
+    );
+
+    // And the prover can't produce one for a set the polynomial doesn't vanish on
+    assert!(matches!(
+        polynomial_committer.create_vanishing_witness(&polynomial, &other_set),
+        Err(Error::NonZeroRemainder { .. })
+    ));
+}
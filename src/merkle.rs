@@ -0,0 +1,111 @@
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+// Leaves and inner nodes are hashed with different prefixes, so an inner node can't be passed off as a leaf
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+pub fn hash_leaf(data: &[u8]) -> Hash {
+    Sha256::new()
+        .chain_update([LEAF_PREFIX])
+        .chain_update(data)
+        .finalize()
+        .into()
+}
+
+fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    Sha256::new()
+        .chain_update([NODE_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+// Binary SHA-256 Merkle tree. The leaves are padded with the hash of an empty leaf up to a power of two
+#[derive(Clone, Debug)]
+pub struct MerkleTree {
+    // Leaf hashes first, root last
+    layers: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<Hash>) -> Self {
+        let mut leaves = leaves;
+        leaves.resize(leaves.len().max(1).next_power_of_two(), hash_leaf(&[]));
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| hash_node(&pair[0], &pair[1]))
+                .collect();
+            layers.push(next);
+        }
+        MerkleTree { layers }
+    }
+
+    pub fn root(&self) -> Hash {
+        self.layers.last().unwrap()[0]
+    }
+
+    // `None` if there's no such leaf
+    pub fn path(&self, index: usize) -> Option<MerklePath> {
+        if index >= self.layers[0].len() {
+            return None;
+        }
+        let siblings = self.layers[..self.layers.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(depth, layer)| layer[(index >> depth) ^ 1])
+            .collect();
+        Some(MerklePath { index, siblings })
+    }
+}
+
+// The sibling hashes from a leaf up to the root
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerklePath {
+    pub index: usize,
+    pub siblings: Vec<Hash>,
+}
+
+impl MerklePath {
+    pub fn verify(&self, root: &Hash, leaf: &Hash) -> bool {
+        if self.siblings.len() >= usize::BITS as usize || self.index >> self.siblings.len() != 0 {
+            return false;
+        }
+        let computed = self
+            .siblings
+            .iter()
+            .enumerate()
+            .fold(*leaf, |node, (depth, sibling)| {
+                if (self.index >> depth) & 1 == 0 {
+                    hash_node(&node, sibling)
+                } else {
+                    hash_node(sibling, &node)
+                }
+            });
+        &computed == root
+    }
+}
+
+#[test]
+fn proves_membership_of_leaves() {
+    let leaves: Vec<Hash> = (0..5u8).map(|i| hash_leaf(&[i])).collect();
+    let tree = MerkleTree::new(leaves.clone());
+
+    for (i, leaf) in leaves.iter().enumerate() {
+        let path = tree.path(i).unwrap();
+        assert_eq!(path.siblings.len(), 3);
+        assert!(path.verify(&tree.root(), leaf));
+        assert!(!path.verify(&tree.root(), &hash_leaf(&[9])));
+    }
+
+    let mut wrong_index = tree.path(1).unwrap();
+    wrong_index.index = 2;
+    assert!(!wrong_index.verify(&tree.root(), &leaves[1]));
+    assert!(tree.path(8).is_none());
+}
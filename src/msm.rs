@@ -0,0 +1,327 @@
+use crate::error::Error;
+use crate::parallel::{map_indexed, threads};
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use blstrs::{G1Affine, G1Projective, Scalar};
+use group::{Curve, Group};
+use std::sync::OnceLock;
+
+// How the committer computes its MSMs over the powers of tau, for its commitments and openings. blst's Pippenger is the
+// default; this crate's Pippenger trades memory for speed or tunes for the core count. Every strategy gives the same
+// points. `GlobalParameters` methods and MSMs over other bases always use blst's
+
+// Bits in a scalar's canonical encoding
+const SCALAR_BITS: usize = 256;
+// Beyond this the buckets take more memory than they save in additions
+pub const MAX_WINDOW: u32 = 24;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MsmConfig {
+    // `G1Projective::multi_exp`
+    #[default]
+    Library,
+    Pippenger(PippengerConfig),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PippengerConfig {
+    // Window size in bits, or `None` to pick one from the MSM size and the number of threads
+    pub window: Option<u32>,
+    pub buckets: BucketStrategy,
+    // Keep each power of tau shifted by every window, so an MSM is one pass over the buckets with no doublings. Costs
+    // a table about 256 / window times the size of the parameters, built on first use
+    pub precompute: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BucketStrategy {
+    // Digits in $[0, 2^c)$, with $2^c - 1$ buckets
+    Unsigned,
+    // Digits in $[-2^{c-1}, 2^{c-1})$, with half as many buckets and a point negation where a digit is negative
+    Signed,
+}
+
+impl MsmConfig {
+    // Pippenger with signed digits and the window picked per MSM
+    pub fn auto() -> Self {
+        MsmConfig::Pippenger(PippengerConfig {
+            window: None,
+            buckets: BucketStrategy::Signed,
+            precompute: false,
+        })
+    }
+}
+
+impl PippengerConfig {
+    fn window(&self, len: usize) -> Result<u32, Error> {
+        let window = self.window.unwrap_or_else(|| auto_window(len, threads()));
+        let min = match self.buckets {
+            BucketStrategy::Unsigned => 1,
+            BucketStrategy::Signed => 2,
+        };
+        if window < min || window > MAX_WINDOW {
+            return Err(Error::InvalidParameters("msm window is out of range"));
+        }
+        Ok(window)
+    }
+}
+
+// About $\ln n$ bits for n points, since that balances the bucket additions against the running sums. Windows are
+// what runs in parallel, so the window also shrinks until there's at least one per thread
+fn auto_window(len: usize, threads: usize) -> u32 {
+    let window = if len < 32 {
+        3
+    } else {
+        (len as f64).ln().ceil() as u32 + 2
+    };
+    let per_thread = (SCALAR_BITS / threads.max(1)) as u32;
+    window.min(per_thread).clamp(2, MAX_WINDOW)
+}
+
+fn windows(window: u32) -> usize {
+    // One more than the bits need, for the carry out of the top signed digit
+    SCALAR_BITS.div_ceil(window as usize) + 1
+}
+
+// The scalar's digits in base $2^c$, lowest first
+fn digits(scalar: &Scalar, window: u32, buckets: BucketStrategy) -> Vec<i64> {
+    let bytes = scalar.to_bytes_le();
+    let c = window as usize;
+    let raw = |j: usize| {
+        (0..c)
+            .map(|bit| j * c + bit)
+            .filter(|&position| position < SCALAR_BITS)
+            .filter(|&position| bytes[position / 8] >> (position % 8) & 1 == 1)
+            .fold(0i64, |digit, position| digit | 1 << (position - j * c))
+    };
+    let half = 1i64 << (c - 1);
+    let mut carry = 0;
+    (0..windows(window))
+        .map(|j| match buckets {
+            BucketStrategy::Unsigned => raw(j),
+            BucketStrategy::Signed => {
+                let digit = raw(j) + carry;
+                carry = (digit >= half) as i64;
+                digit - (carry << c)
+            }
+        })
+        .collect()
+}
+
+fn bucket_count(window: u32, buckets: BucketStrategy) -> usize {
+    match buckets {
+        BucketStrategy::Unsigned => (1 << window) - 1,
+        BucketStrategy::Signed => 1 << (window - 1),
+    }
+}
+
+fn add_to_bucket(buckets: &mut [G1Projective], digit: i64, base: &G1Affine) {
+    match digit {
+        0 => {}
+        d if d > 0 => buckets[d as usize - 1] += base,
+        d => buckets[(-d) as usize - 1] -= base,
+    }
+}
+
+// $\sum_k k \cdot B_k$, by running sums from the top bucket down
+fn sum_buckets(buckets: &[G1Projective]) -> G1Projective {
+    let mut running = G1Projective::identity();
+    let mut sum = G1Projective::identity();
+    for bucket in buckets.iter().rev() {
+        running += bucket;
+        sum += running;
+    }
+    sum
+}
+
+fn pippenger(
+    bases: &[G1Affine],
+    scalars: &[Scalar],
+    window: u32,
+    buckets: BucketStrategy,
+) -> G1Projective {
+    let digits: Vec<Vec<i64>> = scalars.iter().map(|s| digits(s, window, buckets)).collect();
+    let window_indices: Vec<usize> = (0..windows(window)).collect();
+    let sums = map_indexed(&window_indices, |_, &j| {
+        let mut window_buckets = vec![G1Projective::identity(); bucket_count(window, buckets)];
+        for (base, digits) in bases.iter().zip(&digits) {
+            add_to_bucket(&mut window_buckets, digits[j], base);
+        }
+        sum_buckets(&window_buckets)
+    });
+    sums.iter()
+        .rev()
+        .fold(G1Projective::identity(), |mut result, sum| {
+            for _ in 0..window {
+                result = result.double();
+            }
+            result + sum
+        })
+}
+
+// Every power of tau times $2^{cj}$ for each window j, stored window-major
+#[derive(Debug)]
+pub(crate) struct PrecomputedBases {
+    window: u32,
+    len: usize,
+    points: Vec<G1Affine>,
+}
+
+impl PrecomputedBases {
+    fn new(gs: &[G1Projective], window: u32) -> Self {
+        let mut shifted = gs.to_vec();
+        let mut points = Vec::with_capacity(gs.len() * windows(window));
+        for _ in 0..windows(window) {
+            let mut affine = vec![G1Affine::identity(); gs.len()];
+            G1Projective::batch_normalize(&shifted, &mut affine);
+            points.extend(affine);
+            for point in shifted.iter_mut() {
+                for _ in 0..window {
+                    *point = point.double();
+                }
+            }
+        }
+        PrecomputedBases {
+            window,
+            len: gs.len(),
+            points,
+        }
+    }
+
+    // All windows share one set of buckets, so there are no doublings. Points are split between threads instead
+    fn msm(&self, scalars: &[Scalar], buckets: BucketStrategy) -> G1Projective {
+        let per_thread = scalars.len().div_ceil(threads()).max(1);
+        let chunks: Vec<&[Scalar]> = scalars.chunks(per_thread).collect();
+        map_indexed(&chunks, |chunk, scalars| {
+            let mut chunk_buckets =
+                vec![G1Projective::identity(); bucket_count(self.window, buckets)];
+            for (offset, scalar) in scalars.iter().enumerate() {
+                let i = chunk * per_thread + offset;
+                for (j, digit) in digits(scalar, self.window, buckets).into_iter().enumerate() {
+                    add_to_bucket(&mut chunk_buckets, digit, &self.points[j * self.len + i]);
+                }
+            }
+            sum_buckets(&chunk_buckets)
+        })
+        .into_iter()
+        .sum()
+    }
+}
+
+impl GenericPolynomialCommitment {
+    // Keeps the parameters; any precomputed table is dropped and rebuilt for the new config when next needed
+    pub fn with_msm_config(mut self, config: MsmConfig) -> Self {
+        self.msm_config = config;
+        self.precomputed_bases = OnceLock::new();
+        self
+    }
+
+    pub fn msm_config(&self) -> MsmConfig {
+        self.msm_config
+    }
+
+    // Commits to up to `gs.len()` scalars with the configured strategy. No scalars commit to the identity
+    pub(crate) fn msm(&self, scalars: &[Scalar]) -> Result<G1Projective, Error> {
+        let gs = self.global_parameters()?.gs();
+        if scalars.len() > gs.len() {
+            return Err(Error::DegreeTooLarge {
+                max: gs.len() - 1,
+                actual: scalars.len() - 1,
+            });
+        }
+        if scalars.is_empty() {
+            return Ok(G1Projective::identity());
+        }
+        let config = match self.msm_config {
+            MsmConfig::Library => {
+                return Ok(G1Projective::multi_exp(&gs[..scalars.len()], scalars))
+            }
+            MsmConfig::Pippenger(config) => config,
+        };
+        if config.precompute {
+            // The table serves every MSM, so its window is picked for the largest
+            let window = config.window(gs.len())?;
+            let table = self
+                .precomputed_bases
+                .get_or_init(|| PrecomputedBases::new(gs, window));
+            return Ok(table.msm(scalars, config.buckets));
+        }
+        let mut bases = vec![G1Affine::identity(); scalars.len()];
+        G1Projective::batch_normalize(&gs[..scalars.len()], &mut bases);
+        Ok(pippenger(
+            &bases,
+            scalars,
+            config.window(scalars.len())?,
+            config.buckets,
+        ))
+    }
+}
+
+#[test]
+fn every_strategy_matches_multi_exp() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+    use group::ff::Field;
+
+    let mut rng = test_rng();
+    let gp = GenericPolynomialCommitment::new().setup_with_rng(40, &mut rng);
+    let mut scalars: Vec<Scalar> = (0..41).map(|_| Scalar::random(&mut rng)).collect();
+    // The largest and smallest digits, and all-zero windows
+    scalars[0] = -Scalar::ONE;
+    scalars[1] = Scalar::ZERO;
+    scalars[2] = Scalar::from(u64::MAX);
+
+    let mut configs = vec![MsmConfig::Library, MsmConfig::auto()];
+    for buckets in [BucketStrategy::Unsigned, BucketStrategy::Signed] {
+        for window in [None, Some(2), Some(5), Some(8)] {
+            for precompute in [false, true] {
+                configs.push(MsmConfig::Pippenger(PippengerConfig {
+                    window,
+                    buckets,
+                    precompute,
+                }));
+            }
+        }
+    }
+    for config in configs {
+        let committer =
+            GenericPolynomialCommitment::with_global_parameters(gp.clone()).with_msm_config(config);
+        for len in [0, 1, 7, 41] {
+            assert_eq!(
+                committer.msm(&scalars[..len]),
+                Ok(G1Projective::multi_exp(&gp.gs()[..len], &scalars[..len])),
+                "{config:?} with {len} scalars"
+            );
+        }
+
+        // Commitments and witnesses go through the configured MSM
+        let polynomial = Polynomial::new(&scalars);
+        let default = GenericPolynomialCommitment::with_global_parameters(gp.clone());
+        assert_eq!(committer.commit(&polynomial), default.commit(&polynomial));
+        assert_eq!(
+            committer.create_witness(&polynomial, Scalar::from(3)),
+            default.create_witness(&polynomial, Scalar::from(3))
+        );
+        assert_eq!(
+            committer.create_hermite_witness(&polynomial, Scalar::from(3)),
+            default.create_hermite_witness(&polynomial, Scalar::from(3))
+        );
+    }
+
+    let too_wide = GenericPolynomialCommitment::with_global_parameters(gp).with_msm_config(
+        MsmConfig::Pippenger(PippengerConfig {
+            window: Some(MAX_WINDOW + 1),
+            buckets: BucketStrategy::Signed,
+            precompute: false,
+        }),
+    );
+    assert!(too_wide.msm(&scalars).is_err());
+}
+
+#[test]
+fn picks_smaller_windows_for_more_threads() {
+    assert_eq!(auto_window(8, 1), 3);
+    assert_eq!(auto_window(1 << 16, 1), 14);
+    assert_eq!(auto_window(1 << 16, 32), 8);
+    assert_eq!(auto_window(1 << 16, 1000), 2);
+}
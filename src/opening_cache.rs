@@ -0,0 +1,96 @@
+use crate::error::Error;
+use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+use crate::polynomials::Polynomial;
+use blstrs::{G1Projective, Scalar};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// A committed polynomial that remembers the openings it has produced. Applications that keep proving the same
+// positions (hot keys in a vector commitment) get repeat openings from the cache instead of redoing the division and
+// MSM. Cloning the handle is cheap and clones share the cache, so it can be handed to every request that needs it
+#[derive(Clone)]
+pub struct PolynomialHandle {
+    inner: Arc<HandleInner>,
+}
+
+struct HandleInner {
+    polynomial: Polynomial,
+    commitment: G1Projective,
+    // Keyed by the point's canonical bytes, since `Scalar` isn't `Hash`
+    openings: Mutex<HashMap<[u8; 32], (G1Projective, Scalar)>>,
+}
+
+impl PolynomialHandle {
+    pub fn new(
+        committer: &GenericPolynomialCommitment,
+        polynomial: Polynomial,
+    ) -> Result<Self, Error> {
+        let commitment = committer.commit(&polynomial)?;
+        Ok(PolynomialHandle {
+            inner: Arc::new(HandleInner {
+                polynomial,
+                commitment,
+                openings: Mutex::new(HashMap::new()),
+            }),
+        })
+    }
+
+    pub fn polynomial(&self) -> &Polynomial {
+        &self.inner.polynomial
+    }
+
+    pub fn commitment(&self) -> G1Projective {
+        self.inner.commitment
+    }
+
+    // Same as `create_witness`, but each point is only computed once. The committer must be the one the handle was
+    // created with, or cached openings won't match fresh ones
+    pub fn open(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        point: Scalar,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        let key = point.to_bytes_le();
+        if let Some(opening) = self.inner.openings.lock().unwrap().get(&key) {
+            return Ok(*opening);
+        }
+        // Computed without holding the lock, so other positions aren't blocked meanwhile
+        let opening = committer.create_witness(&self.inner.polynomial, point)?;
+        self.inner.openings.lock().unwrap().insert(key, opening);
+        Ok(opening)
+    }
+
+    pub fn cached_openings(&self) -> usize {
+        self.inner.openings.lock().unwrap().len()
+    }
+
+    pub fn clear_cache(&self) {
+        self.inner.openings.lock().unwrap().clear();
+    }
+}
+
+#[test]
+fn caches_repeated_openings() {
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(2, &mut test_rng());
+    let handle = PolynomialHandle::new(&committer, Polynomial::new_from_bytes(&[1, 2, 3])).unwrap();
+    let shared = handle.clone();
+
+    let point = Scalar::from(5);
+    let opening = handle.open(&committer, point).unwrap();
+    assert_eq!(shared.cached_openings(), 1);
+    assert_eq!(shared.open(&committer, point).unwrap(), opening);
+    assert_eq!(handle.cached_openings(), 1);
+
+    let (witness, evaluation) = shared.open(&committer, Scalar::from(6)).unwrap();
+    assert_eq!(handle.cached_openings(), 2);
+    assert_eq!(
+        committer.verify_evaluation(handle.commitment(), Scalar::from(6), evaluation, witness),
+        Ok(true)
+    );
+
+    handle.clear_cache();
+    assert_eq!(shared.cached_openings(), 0);
+}
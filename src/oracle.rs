@@ -0,0 +1,150 @@
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::polynomials::Polynomial;
+use blstrs::{G1Projective, Scalar};
+
+// The seam between an IOP and the commitment scheme that compiles it. Protocol code works with polynomial oracles:
+// the prover sends a commitment, answers queries, and backs any answer with an opening when asked; the verifier
+// checks openings against commitments. Written against these two traits, the protocol doesn't know it's KZG
+// underneath, and a different scheme only needs new implementations
+pub trait PolynomialOracle {
+    type Commitment;
+    type Proof;
+
+    // What the prover sends in place of the polynomial
+    fn commitment(&self) -> Self::Commitment;
+
+    // The polynomial's value at `point`, without a proof
+    fn query(&self, point: Scalar) -> Scalar;
+
+    // The value at `point` and a proof of it, computed when asked
+    fn open(&self, point: Scalar) -> Result<(Scalar, Self::Proof), Error>;
+}
+
+pub trait OracleVerifier {
+    type Commitment;
+    type Proof;
+
+    fn check(
+        &self,
+        commitment: &Self::Commitment,
+        point: Scalar,
+        evaluation: Scalar,
+        proof: &Self::Proof,
+    ) -> Result<bool, Error>;
+}
+
+// A KZG-committed polynomial of any degree the parameters cover. IOPs routinely commit to low-degree and constant
+// polynomials (quotients, masks), so this uses the bare `open` and `check_opening` rather than `commit` and
+// `create_witness`, which insist on the full degree and reject constants
+pub struct KzgOracle<'a> {
+    committer: &'a GenericPolynomialCommitment,
+    polynomial: Polynomial,
+    commitment: G1Projective,
+}
+
+impl<'a> KzgOracle<'a> {
+    // Commits right away, so the commitment can be sent before any query
+    pub fn new(
+        committer: &'a GenericPolynomialCommitment,
+        polynomial: Polynomial,
+    ) -> Result<Self, Error> {
+        let commitment = committer.msm(polynomial.coeffs())?;
+        Ok(KzgOracle {
+            committer,
+            polynomial,
+            commitment,
+        })
+    }
+
+    pub fn polynomial(&self) -> &Polynomial {
+        &self.polynomial
+    }
+}
+
+impl PolynomialOracle for KzgOracle<'_> {
+    type Commitment = G1Projective;
+    type Proof = G1Projective;
+
+    fn commitment(&self) -> G1Projective {
+        self.commitment
+    }
+
+    fn query(&self, point: Scalar) -> Scalar {
+        self.polynomial.evaluate(point)
+    }
+
+    fn open(&self, point: Scalar) -> Result<(Scalar, G1Projective), Error> {
+        let (witness, evaluation) = self.committer.open(&self.polynomial, point)?;
+        Ok((evaluation, witness))
+    }
+}
+
+impl OracleVerifier for GenericPolynomialCommitment {
+    type Commitment = G1Projective;
+    type Proof = G1Projective;
+
+    fn check(
+        &self,
+        commitment: &G1Projective,
+        point: Scalar,
+        evaluation: Scalar,
+        proof: &G1Projective,
+    ) -> Result<bool, Error> {
+        self.check_opening(*commitment, point, evaluation, *proof)
+    }
+}
+
+#[test]
+fn compiles_a_product_check_through_the_traits() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+    use crate::transcript::{Sha256Transcript, Transcript};
+    use group::ff::Field;
+
+    // A toy IOP for $a \cdot b = c$ that only sees the traits: the verifier picks a point and checks the three
+    // openings and the product there
+    fn prove_and_check<O, V>(oracles: [&O; 3], verifier: &V) -> Result<bool, Error>
+    where
+        O: PolynomialOracle<Commitment = G1Projective>,
+        V: OracleVerifier<Commitment = G1Projective, Proof = O::Proof>,
+    {
+        let mut transcript = Sha256Transcript::new(b"product check");
+        for oracle in oracles {
+            transcript.append_g1(b"commitment", &oracle.commitment());
+        }
+        let point = transcript.challenge_scalar(b"point");
+        let mut values = [Scalar::ZERO; 3];
+        for (value, oracle) in values.iter_mut().zip(oracles) {
+            let (evaluation, proof) = oracle.open(point)?;
+            if evaluation != oracle.query(point)
+                || !verifier.check(&oracle.commitment(), point, evaluation, &proof)?
+            {
+                return Ok(false);
+            }
+            *value = evaluation;
+        }
+        Ok(values[0] * values[1] == values[2])
+    }
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(6, &mut test_rng());
+    let a = Polynomial::new_from_i64(&[1, 2, 3]);
+    let b = Polynomial::new_from_i64(&[4, 0, 5]);
+    let c = Polynomial::new_from_i64(&[4, 8, 17, 10, 15]);
+    let wrong = Polynomial::new_from_i64(&[4, 8, 17, 10, 16]);
+
+    let oracle = |polynomial: &Polynomial| KzgOracle::new(&committer, polynomial.clone()).unwrap();
+    let (a, b, c, wrong) = (oracle(&a), oracle(&b), oracle(&c), oracle(&wrong));
+    assert_eq!(prove_and_check([&a, &b, &c], &committer), Ok(true));
+    assert_eq!(prove_and_check([&a, &b, &wrong], &committer), Ok(false));
+
+    // Constants open too
+    let constant = oracle(&Polynomial::new_from_i64(&[7]));
+    let (evaluation, proof) = constant.open(Scalar::from(3)).unwrap();
+    assert_eq!(evaluation, Scalar::from(7));
+    assert_eq!(
+        committer.check(&constant.commitment(), Scalar::from(3), evaluation, &proof),
+        Ok(true)
+    );
+}
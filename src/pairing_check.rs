@@ -0,0 +1,166 @@
+use crate::constant_time::ct_eq_gt;
+use crate::error::Error;
+use crate::types::{Commitment, FieldElement, Proof};
+use crate::verifier_key::VerifierKey;
+use blstrs::{Bls12, G1Affine, G1Projective, G2Prepared, G2Projective, Gt, Scalar};
+use group::ff::Field;
+use group::{Curve, Group};
+use pairing::{MillerLoopResult, MultiMillerLoop};
+use rand_core::{CryptoRng, RngCore};
+
+// Collects pairing-product equations $\prod_j e(A_j, B_j) = 1$ from however many checks a protocol makes (degree
+// bounds, openings, zero tests) and decides all of them with one multi-Miller loop and one final exponentiation.
+// Each equation is scaled by a fresh random weight, so a false one can only cancel against the others with
+// probability about $1/r$. Terms on the same G2 point are merged, so equations sharing h or $[\tau]_2$ add no Miller
+// loops. The weights come from the checker's own rng after the equations are fixed
+pub struct PairingCheck<R> {
+    terms: Vec<(G1Projective, G2Projective)>,
+    equations: usize,
+    rng: R,
+}
+
+impl<R: RngCore + CryptoRng> PairingCheck<R> {
+    pub fn new(rng: R) -> Self {
+        PairingCheck {
+            terms: vec![],
+            equations: 0,
+            rng,
+        }
+    }
+
+    // The number of equations added so far
+    pub fn len(&self) -> usize {
+        self.equations
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.equations == 0
+    }
+
+    // $\prod_j e(a_j, b_j) = 1$
+    pub fn add_product(&mut self, terms: &[(G1Projective, G2Projective)]) -> &mut Self {
+        let weight = Scalar::random(&mut self.rng);
+        for (a, b) in terms {
+            match self.terms.iter_mut().find(|(_, existing)| existing == b) {
+                Some((sum, _)) => *sum += a * weight,
+                None => self.terms.push((a * weight, *b)),
+            }
+        }
+        self.equations += 1;
+        self
+    }
+
+    // $e(a, b) = e(c, d)$
+    pub fn add_equality(
+        &mut self,
+        (a, b): (G1Projective, G2Projective),
+        (c, d): (G1Projective, G2Projective),
+    ) -> &mut Self {
+        self.add_product(&[(a, b), (-c, d)])
+    }
+
+    // True if every equation holds, except with negligible probability
+    pub fn verify(&self) -> bool {
+        let g1: Vec<G1Affine> = self.terms.iter().map(|(a, _)| a.to_affine()).collect();
+        let g2: Vec<G2Prepared> = self
+            .terms
+            .iter()
+            .map(|(_, b)| G2Prepared::from(b.to_affine()))
+            .collect();
+        let pairs: Vec<(&G1Affine, &G2Prepared)> = g1.iter().zip(&g2).collect();
+        let result = Bls12::multi_miller_loop(&pairs).final_exponentiation();
+        ct_eq_gt(&result, &Gt::identity()).into()
+    }
+}
+
+impl VerifierKey {
+    // Adds the equation `verify_evaluation` checks, with the same errors for commitments that don't match the key.
+    // It's rearranged as $e(C - [v]_1 + z W, h) \cdot e(-W, [\tau]_2) = 1$ so that every opening shares two G2 points
+    pub fn add_opening<R: RngCore + CryptoRng>(
+        &self,
+        check: &mut PairingCheck<R>,
+        commitment: &Commitment,
+        point: FieldElement,
+        evaluation: FieldElement,
+        proof: &Proof,
+    ) -> Result<(), Error> {
+        self.check_compatible(commitment)?;
+        if bool::from(commitment.inner().is_identity()) {
+            return Err(Error::IdentityCommitment);
+        }
+        if bool::from(proof.inner().is_identity()) {
+            return Err(Error::IdentityWitness);
+        }
+        let witness = proof.inner();
+        check.add_product(&[
+            (
+                commitment.inner() - self.g() * evaluation.inner() + witness * point.inner(),
+                self.h(),
+            ),
+            (-witness, self.h_tau()),
+        ]);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn combines_equations_into_one_check() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut committer = GenericPolynomialCommitment::new();
+    let key = committer
+        .setup_with_rng(3, &mut test_rng())
+        .verifier_key()
+        .unwrap();
+
+    let mut openings = vec![];
+    for i in 0..3 {
+        let polynomial = Polynomial::rand(3, &mut rng);
+        let commitment = committer.commit_opaque(&polynomial).unwrap();
+        let point = FieldElement::from(i + 2);
+        let (proof, evaluation) = committer.prove(&polynomial, point).unwrap();
+        openings.push((commitment, point, evaluation, proof));
+    }
+    let a = Scalar::random(&mut rng);
+    let g = G1Projective::generator();
+    let h = G2Projective::generator();
+
+    let mut check = PairingCheck::new(test_rng());
+    assert!(check.verify());
+    for (commitment, point, evaluation, proof) in &openings {
+        key.add_opening(&mut check, commitment, *point, *evaluation, proof)
+            .unwrap();
+    }
+    check.add_equality((g * a, h), (g, h * a));
+    assert_eq!(check.len(), 4);
+    assert!(check.verify());
+
+    // One false equation among true ones fails the whole check
+    let mut tampered = PairingCheck::new(test_rng());
+    let (commitment, point, evaluation, proof) = &openings[1];
+    key.add_opening(
+        &mut tampered,
+        &openings[0].0,
+        openings[0].1,
+        openings[0].2,
+        &openings[0].3,
+    )
+    .unwrap();
+    key.add_opening(
+        &mut tampered,
+        commitment,
+        *point,
+        *evaluation + FieldElement::one(),
+        proof,
+    )
+    .unwrap();
+    assert!(!tampered.verify());
+
+    let mut unrelated = PairingCheck::new(test_rng());
+    unrelated.add_equality((g * a, h), (g, h * (a + Scalar::ONE)));
+    assert!(!unrelated.verify());
+}
@@ -0,0 +1,40 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Maps `f` over a slice, on rayon's pool with the `parallel` feature and in order on the calling thread without it.
+// Code that has independent per-item work goes through here, so builds without the feature (WASM, embedded) get a
+// deterministic single-threaded path and don't pull in rayon at all
+#[cfg(feature = "parallel")]
+pub fn map_indexed<T: Sync, U: Send>(items: &[T], f: impl Fn(usize, &T) -> U + Sync) -> Vec<U> {
+    items
+        .par_iter()
+        .enumerate()
+        .map(|(i, item)| f(i, item))
+        .collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn map_indexed<T, U>(items: &[T], f: impl Fn(usize, &T) -> U) -> Vec<U> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| f(i, item))
+        .collect()
+}
+
+// How many threads `map_indexed` spreads work over
+#[cfg(feature = "parallel")]
+pub fn threads() -> usize {
+    rayon::current_num_threads()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn threads() -> usize {
+    1
+}
+
+#[test]
+fn maps_in_order() {
+    let squares = map_indexed(&[1, 2, 3, 4], |i, x| (i, x * x));
+    assert_eq!(squares, vec![(0, 1), (1, 4), (2, 9), (3, 16)]);
+}
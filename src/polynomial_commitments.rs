@@ -1,68 +1,342 @@
+use crate::constant_time::ct_eq_gt;
+pub use crate::error::Error;
+use crate::fixed_polynomial::FixedPolynomial;
+use crate::generators::Generators;
+use crate::msm::{MsmConfig, PrecomputedBases};
+use crate::parallel::map_indexed;
 use crate::polynomials::Polynomial;
-use blstrs::{pairing, G1Affine, G1Projective, G2Projective, Scalar};
+use crate::progress::{NoProgress, Operation, Progress};
+#[cfg(test)]
+use crate::rng::test_rng;
+use crate::scalars::{scalars_from_data, IntoScalar};
+use crate::secret::{zeroize_scalar, zeroize_scalars};
+use blstrs::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
 use group::prime::PrimeCurveAffine;
 use group::Curve;
-use group::{ff::Field as FieldT, Group};
-use rand::Rng;
+use group::{ff::Field as FieldT, ff::PrimeField, Group};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, OnceLock};
+use zeroize::Zeroize;
+
+// Points between setup progress reports
+const SETUP_PROGRESS_INTERVAL: usize = 1 << 10;
+
+// Multiples of a fixed base from a table of $k \cdot 2^{wj} \cdot G$ for every w-bit window j and digit k, so each
+// multiple costs one addition per window and no doublings. The lookups are indexed by the scalar's digits, which is
+// fine for setup on a trusted machine but not for anything that has to run in constant time
+struct FixedBase<T> {
+    window: usize,
+    table: Vec<Vec<T>>,
+}
+
+impl<T: Group> FixedBase<T> {
+    // The window is the one that minimizes building the table plus `count` multiplications with it
+    fn new(base: T, count: usize) -> Self {
+        let windows = |window: usize| (Scalar::NUM_BITS as usize).div_ceil(window);
+        let window = (1..=16)
+            .min_by_key(|&window| windows(window) * ((1 << window) + count))
+            .unwrap();
+        let mut table = Vec::with_capacity(windows(window));
+        let mut shifted = base;
+        for _ in 0..windows(window) {
+            let mut multiples = Vec::with_capacity(1 << window);
+            let mut multiple = T::identity();
+            for _ in 0..1 << window {
+                multiples.push(multiple);
+                multiple += shifted;
+            }
+            table.push(multiples);
+            // The loop leaves $2^w$ times the window's base, which is the next window's base
+            shifted = multiple;
+        }
+        FixedBase { window, table }
+    }
+
+    fn mul(&self, scalar: &Scalar) -> T {
+        let mut bytes = scalar.to_bytes_le();
+        let bit = |position: usize| (bytes[position / 8] >> (position % 8) & 1) as usize;
+        let mut result = T::identity();
+        for (j, multiples) in self.table.iter().enumerate() {
+            let digit = (0..self.window)
+                .map(|i| j * self.window + i)
+                .filter(|&position| position < 256)
+                .fold(0, |digit, position| {
+                    digit | bit(position) << (position - j * self.window)
+                });
+            result += multiples[digit];
+        }
+        bytes.zeroize();
+        result
+    }
+}
 
-// Generate global parameters for some group's generator
-fn generate_tau_points<T: Group + std::ops::Mul<Scalar, Output = T>>(
+// Generate global parameters for some group's generator, one for each power of tau. The multiplications are
+// independent, so they're spread over threads in batches, with a report after each batch. Progress counts from
+// `offset` out of `total`, since setup reports both groups as one operation
+fn generate_tau_points<T: Group>(
     generator: T,
-    tau: Scalar,
-    length: usize,
+    powers: &[Scalar],
+    (offset, total): (usize, usize),
+    progress: &dyn Progress,
 ) -> Vec<T> {
-    let mut generators = Vec::with_capacity(length);
-    generators.push(generator);
-    let mut generator = generator.clone();
-
-    for _ in 1..length {
-        generator = generator * tau;
-        generators.push(generator);
+    let fixed_base = FixedBase::new(generator, powers.len());
+    let mut generators = Vec::with_capacity(powers.len());
+    for batch in powers.chunks(SETUP_PROGRESS_INTERVAL) {
+        generators.extend(map_indexed(batch, |_, power| fixed_base.mul(power)));
+        progress.report(
+            Operation::Setup,
+            (offset + generators.len()) as u64,
+            total as u64,
+        );
     }
     generators
 }
 
 #[derive(Clone, Debug)]
 pub struct GlobalParameters {
-    pub gs: Vec<G1Projective>,
+    gs: Vec<G1Projective>,
     hs: Vec<G2Projective>,
 }
 
+const G1_COMPRESSED_SIZE: usize = 48;
+const G2_COMPRESSED_SIZE: usize = 96;
+const FINGERPRINT_SIZE: usize = 32;
+
 impl GlobalParameters {
+    // Unchecked, for parameters we just generated ourselves
     fn new(gs: Vec<G1Projective>, hs: Vec<G2Projective>) -> Self {
         GlobalParameters { gs, hs }
     }
-}
 
-#[derive(Debug, Eq, PartialEq)]
-pub enum Error {
-    // Tried to use a polynomial of an inappropriate degree
-    IncorrectDegree,
-    // Setup not complete; tried to use commitment scheme prior to setup
-    SetupIncomplete,
+    // Build parameters from points that came from somewhere else. Both vectors must be non-empty, there can't be
+    // more G2 powers than G1 powers, the first point of each must be the standard generator, and no point may be
+    // the identity
+    pub fn try_new(gs: Vec<G1Projective>, hs: Vec<G2Projective>) -> Result<Self, Error> {
+        GlobalParameters::try_new_with_generators(gs, hs, &Generators::standard())
+    }
+
+    // Same as `try_new`, for parameters built on derived generators
+    pub fn try_new_with_generators(
+        gs: Vec<G1Projective>,
+        hs: Vec<G2Projective>,
+        generators: &Generators,
+    ) -> Result<Self, Error> {
+        if gs.is_empty() || hs.is_empty() {
+            return Err(Error::InvalidParameters(
+                "need at least one point in each group",
+            ));
+        }
+        if hs.len() > gs.len() {
+            return Err(Error::InvalidParameters("more G2 powers than G1 powers"));
+        }
+        if gs[0] != generators.g1 || hs[0] != generators.g2 {
+            return Err(Error::InvalidParameters(
+                "first points must be the group generators",
+            ));
+        }
+        let has_identity = gs.iter().any(|g| bool::from(g.is_identity()))
+            || hs.iter().any(|h| bool::from(h.is_identity()));
+        if has_identity {
+            return Err(Error::InvalidParameters("contains the identity point"));
+        }
+        Ok(GlobalParameters { gs, hs })
+    }
+
+    // Powers of tau in G1
+    pub fn gs(&self) -> &[G1Projective] {
+        &self.gs
+    }
+
+    // Powers of tau in G2
+    pub fn hs(&self) -> &[G2Projective] {
+        &self.hs
+    }
+
+    // Commit to up to `gs.len()` coefficients in G1, without the exact-degree requirement `commit` has. No
+    // coefficients commit to the identity. Always blst's MSM, whatever the committer's `MsmConfig`
+    pub fn commit_in_g1(&self, coefficients: &[Scalar]) -> Result<G1Projective, Error> {
+        if coefficients.len() > self.gs.len() {
+            return Err(Error::DegreeTooLarge {
+                max: self.gs.len() - 1,
+                actual: coefficients.len() - 1,
+            });
+        }
+        if coefficients.is_empty() {
+            return Ok(G1Projective::identity());
+        }
+        Ok(G1Projective::multi_exp(
+            &self.gs[..coefficients.len()],
+            coefficients,
+        ))
+    }
+
+    // Commit to up to `hs.len()` coefficients in G2
+    pub fn commit_in_g2(&self, coefficients: &[Scalar]) -> Result<G2Projective, Error> {
+        if coefficients.len() > self.hs.len() {
+            return Err(Error::DegreeTooLarge {
+                max: self.hs.len() - 1,
+                actual: coefficients.len() - 1,
+            });
+        }
+        if coefficients.is_empty() {
+            return Ok(G2Projective::identity());
+        }
+        Ok(G2Projective::multi_exp(
+            &self.hs[..coefficients.len()],
+            coefficients,
+        ))
+    }
+
+    // Checks that the points really are consecutive powers of one tau, which `try_new` can't see. Parameters from a
+    // ceremony or a download should pass this before use. Each chain is checked with one pair of pairings on random
+    // linear combinations: $e(\sum r_i g_{i+1}, h) = e(\sum r_i g_i, h_1)$, and the same for the G2 powers
+    pub fn verify_powers<R: RngCore + CryptoRng>(&self, rng: &mut R) -> bool {
+        if self.hs.len() < 2 {
+            return self.gs.len() == 1;
+        }
+        let g1_weights: Vec<Scalar> = (1..self.gs.len())
+            .map(|_| Scalar::random(&mut *rng))
+            .collect();
+        let g2_weights: Vec<Scalar> = (1..self.hs.len())
+            .map(|_| Scalar::random(&mut *rng))
+            .collect();
+        let shifted_g1 = G1Projective::multi_exp(&self.gs[1..], &g1_weights);
+        let unshifted_g1 = G1Projective::multi_exp(&self.gs[..self.gs.len() - 1], &g1_weights);
+        let shifted_g2 = G2Projective::multi_exp(&self.hs[1..], &g2_weights);
+        let unshifted_g2 = G2Projective::multi_exp(&self.hs[..self.hs.len() - 1], &g2_weights);
+
+        let g1 = self.gs[0].to_affine();
+        let g2 = self.hs[0].to_affine();
+        let g1_chain = ct_eq_gt(
+            &pairing(&shifted_g1.to_affine(), &g2),
+            &pairing(&unshifted_g1.to_affine(), &self.hs[1].to_affine()),
+        );
+        let g2_chain = ct_eq_gt(
+            &pairing(&g1, &shifted_g2.to_affine()),
+            &pairing(&self.gs[1].to_affine(), &unshifted_g2.to_affine()),
+        );
+        (g1_chain & g2_chain).into()
+    }
+
+    // Canonical serialization: the number of G1 and G2 points as little-endian u32s, followed by the compressed G1
+    // points and then the compressed G2 points
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            8 + self.gs.len() * G1_COMPRESSED_SIZE + self.hs.len() * G2_COMPRESSED_SIZE,
+        );
+        bytes.extend_from_slice(&(self.gs.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.hs.len() as u32).to_le_bytes());
+        for g in self.gs.iter() {
+            bytes.extend_from_slice(&g.to_affine().to_compressed());
+        }
+        for h in self.hs.iter() {
+            bytes.extend_from_slice(&h.to_affine().to_compressed());
+        }
+        bytes
+    }
+
+    // SHA-256 over the canonical serialization. Nodes can compare this to assert they're all using the same setup
+    pub fn fingerprint(&self) -> [u8; FINGERPRINT_SIZE] {
+        Sha256::digest(self.canonical_bytes()).into()
+    }
+
+    // The format saved to disk: the canonical serialization with its fingerprint appended
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.canonical_bytes();
+        bytes.extend_from_slice(&self.fingerprint());
+        bytes
+    }
+
+    // Inverse of `to_bytes`. Rejects malformed points and data whose fingerprint doesn't match its contents
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 8 + FINGERPRINT_SIZE {
+            return Err(Error::InvalidEncoding("too short for global parameters"));
+        }
+        let (canonical, fingerprint) = bytes.split_at(bytes.len() - FINGERPRINT_SIZE);
+        if Sha256::digest(canonical).as_slice() != fingerprint {
+            return Err(Error::FingerprintMismatch);
+        }
+
+        let gs_len = u32::from_le_bytes(canonical[0..4].try_into().unwrap()) as usize;
+        let hs_len = u32::from_le_bytes(canonical[4..8].try_into().unwrap()) as usize;
+        let points = &canonical[8..];
+        if points.len() != gs_len * G1_COMPRESSED_SIZE + hs_len * G2_COMPRESSED_SIZE {
+            return Err(Error::InvalidEncoding(
+                "point count doesn't match the data length",
+            ));
+        }
+        let (g_bytes, h_bytes) = points.split_at(gs_len * G1_COMPRESSED_SIZE);
+
+        // Decompression does a subgroup check per point, which dominates loading large parameters
+
+        let gs_chunks: Vec<&[u8]> = g_bytes.chunks_exact(G1_COMPRESSED_SIZE).collect();
+        let gs = map_indexed(&gs_chunks, |index, chunk| {
+            Option::<G1Affine>::from(G1Affine::from_compressed((*chunk).try_into().unwrap()))
+                .map(G1Projective::from)
+                .ok_or(Error::InvalidPoint { group: "G1", index })
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+        let hs_chunks: Vec<&[u8]> = h_bytes.chunks_exact(G2_COMPRESSED_SIZE).collect();
+        let hs = map_indexed(&hs_chunks, |index, chunk| {
+            Option::<G2Affine>::from(G2Affine::from_compressed((*chunk).try_into().unwrap()))
+                .map(G2Projective::from)
+                .ok_or(Error::InvalidPoint { group: "G2", index })
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+        // Parameters may have been set up over derived generators, so the saved first points are taken as the
+        // generators. The fingerprint has already been checked
+        let generators = match (gs.first(), hs.first()) {
+            (Some(g1), Some(g2)) => Generators { g1: *g1, g2: *g2 },
+            _ => Generators::standard(),
+        };
+        GlobalParameters::try_new_with_generators(gs, hs, &generators)
+    }
 }
 
 pub trait PolynomialCommitment {
+    // What setup publishes: the powers of tau for KZG, nothing for a transparent scheme
+    type Parameters;
+    // A point in G1 for KZG, a Merkle root for a hash-based scheme
+    type Commitment;
+    type Witness;
+
     fn setup(
         &mut self,
-        // This is something like "max degree"
+        // The max degree: the parameters have d + 1 powers of tau, enough to commit to polynomials of degree d
         d: usize,
-    ) -> GlobalParameters;
-    /// Should be $f(\tau) \cdot G \in \mathbb G$
-    fn commit(&self, polynomial: &Polynomial) -> Result<G1Projective, Error>;
-    fn create_witness(&self, polynomial: Polynomial, point: Scalar) -> (G1Projective, Scalar);
+    ) -> Self::Parameters {
+        self.setup_with_rng(d, &mut OsRng)
+    }
+    /// Same as `setup`, but draws the trapdoor from the given rng
+    fn setup_with_rng<R: RngCore + CryptoRng>(&mut self, d: usize, rng: &mut R)
+        -> Self::Parameters;
+    /// For KZG, $f(\tau) \cdot G \in \mathbb G$
+    fn commit(&self, polynomial: &Polynomial) -> Result<Self::Commitment, Error>;
+    fn create_witness(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+    ) -> Result<(Self::Witness, Scalar), Error>;
     fn verify_evaluation(
         &self,
-        committed_polynomial: G1Projective,
+        committed_polynomial: Self::Commitment,
         point: Scalar,
         evaluation: Scalar,
-        witness: G1Projective,
-    ) -> bool;
+        witness: Self::Witness,
+    ) -> Result<bool, Error>;
 }
 
+// Parameters are held behind an `Arc` so committers sharing one SRS don't each keep a copy
 #[derive(Debug)]
 pub struct GenericPolynomialCommitment {
-    global_parameters: Option<GlobalParameters>,
+    global_parameters: Option<Arc<GlobalParameters>>,
+    pub(crate) msm_config: MsmConfig,
+    pub(crate) precomputed_bases: OnceLock<PrecomputedBases>,
 }
 
 impl GenericPolynomialCommitment {
@@ -70,77 +344,237 @@ impl GenericPolynomialCommitment {
     pub fn new() -> Self {
         GenericPolynomialCommitment {
             global_parameters: None,
+            msm_config: MsmConfig::default(),
+            precomputed_bases: OnceLock::new(),
         }
     }
-}
 
-impl PolynomialCommitment for GenericPolynomialCommitment {
-    // A trusted setup procedure which can generate global parameters for the application
-    fn setup(
+    // Use parameters produced elsewhere (loaded from disk, imported from a ceremony) instead of running `setup`
+    pub fn with_global_parameters(global_parameters: GlobalParameters) -> Self {
+        GenericPolynomialCommitment::with_shared_parameters(Arc::new(global_parameters))
+    }
+
+    // Use parameters already loaded elsewhere in the process, e.g. from a `ParametersRegistry`
+    pub fn with_shared_parameters(global_parameters: Arc<GlobalParameters>) -> Self {
+        GenericPolynomialCommitment {
+            global_parameters: Some(global_parameters),
+            msm_config: MsmConfig::default(),
+            precomputed_bases: OnceLock::new(),
+        }
+    }
+
+    // `setup_with_rng`, with the powers of tau built on the given generators rather than the standard ones
+    pub fn setup_with_generators<R: RngCore + CryptoRng>(
         &mut self,
-        // This is something like "max degree"
         d: usize,
+        generators: Generators,
+        rng: &mut R,
     ) -> GlobalParameters {
-        let mut rng = rand::thread_rng();
-        let tau: u64 = rng.gen();
-        let tau = Scalar::from(tau);
+        self.setup_with_progress(d, generators, rng, &NoProgress)
+    }
 
-        let gs = generate_tau_points(G1Projective::generator(), tau, d);
-        let hs = generate_tau_points(G2Projective::generator(), tau, d);
+    pub fn setup_with_progress<R: RngCore + CryptoRng>(
+        &mut self,
+        d: usize,
+        generators: Generators,
+        rng: &mut R,
+        progress: &dyn Progress,
+    ) -> GlobalParameters {
+        let mut tau = Scalar::random(&mut *rng);
+
+        // A polynomial of degree d has d + 1 coefficients, so it needs $\tau^0$ through $\tau^d$
+        let length = d + 1;
+        let mut powers = Vec::with_capacity(length);
+        let mut power = Scalar::ONE;
+        for _ in 0..length {
+            powers.push(power);
+            power *= tau;
+        }
+        let gs = generate_tau_points(generators.g1, &powers, (0, 2 * length), progress);
+        let hs = generate_tau_points(generators.g2, &powers, (length, 2 * length), progress);
+        // Tau is toxic waste: anyone who learns it (or any power of it) can forge openings, so don't leave it lying
+        // around in memory
+        zeroize_scalar(&mut tau);
+        zeroize_scalar(&mut power);
+        zeroize_scalars(&mut powers);
 
         let global_parameters = GlobalParameters::new(gs, hs);
-        self.global_parameters = Some(global_parameters.clone());
+        self.global_parameters = Some(Arc::new(global_parameters.clone()));
+        self.precomputed_bases = OnceLock::new();
         global_parameters
     }
+}
+
+impl GenericPolynomialCommitment {
+    pub fn global_parameters(&self) -> Result<&GlobalParameters, Error> {
+        self.global_parameters
+            .as_deref()
+            .ok_or(Error::SetupIncomplete)
+    }
 
-    // Generate the commitment to the polynomial
-    fn commit(&self, polynomial: &Polynomial) -> Result<G1Projective, Error> {
-        if self.global_parameters.is_none() {
-            return Err(Error::SetupIncomplete);
+    // Commit to coefficients given lowest degree first, possibly with trailing zeros
+    fn commit_coefficients(&self, coefficients: &[Scalar]) -> Result<G1Projective, Error> {
+        let global_parameters = self
+            .global_parameters
+            .as_deref()
+            .ok_or(Error::SetupIncomplete)?;
+        let degree = coefficients.iter().rposition(|c| !bool::from(c.is_zero()));
+        match degree {
+            // The commitment would be the identity, which is also what a forged "commitment to nothing" looks like
+            None => Err(Error::ZeroPolynomial),
+            Some(degree) if degree != global_parameters.gs.len() - 1 => {
+                Err(Error::IncorrectDegree {
+                    expected: global_parameters.gs.len() - 1,
+                    actual: degree,
+                })
+            }
+            // For $f_0 .. f_d$ we need to calculate $f_i \times H_i$ where H is the global parameters. We can just use this to do it in an optimized way
+            Some(_) => self.msm(&coefficients[..global_parameters.gs.len()]),
         }
+    }
 
-        let global_parameters = &self.global_parameters.as_ref().unwrap();
-        if polynomial.0.len() != global_parameters.gs.len() {
-            return Err(Error::IncorrectDegree);
+    // `commit` for a fixed-size polynomial, without copying its coefficients to the heap first
+    pub fn commit_fixed<const N: usize>(
+        &self,
+        polynomial: &FixedPolynomial<N>,
+    ) -> Result<G1Projective, Error> {
+        self.commit_coefficients(polynomial.coeffs())
+    }
+
+    // Commit to application data, one element per coefficient. Unlike `commit`, data shorter than the parameters is
+    // fine: the missing entries are zeros, which is the same commitment as padding it out by hand
+    pub fn commit_data<T: IntoScalar>(&self, data: &[T]) -> Result<G1Projective, Error> {
+        self.msm(&scalars_from_data(data)?)
+    }
+
+    // `create_witness` for a fixed-size polynomial. The quotient comes from synthetic division on the stack
+    pub fn create_witness_fixed<const N: usize>(
+        &self,
+        polynomial: &FixedPolynomial<N>,
+        point: Scalar,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        let global_parameters = self
+            .global_parameters
+            .as_deref()
+            .ok_or(Error::SetupIncomplete)?;
+        if polynomial.is_zero() {
+            return Err(Error::ZeroPolynomial);
         }
-        // For $f_0 .. f_d$ we need to calculate $f_i \times H_i$ where H is the global parameters. We can just use this to do it in an optimized way
-        Ok(G1Projective::multi_exp(
-            &global_parameters.gs,
-            &polynomial.0,
-        ))
+
+        let (quotient, evaluation) = polynomial.divide_by_linear(point);
+        let quotient_len = match quotient.degree() {
+            None => return Err(Error::IdentityWitness),
+            Some(degree) => degree + 1,
+        };
+        if quotient_len > global_parameters.gs.len() {
+            return Err(Error::DegreeTooLarge {
+                max: global_parameters.gs.len() - 1,
+                actual: quotient_len - 1,
+            });
+        }
+
+        let witness = self.msm(&quotient.coeffs()[..quotient_len])?;
+        Ok((witness, evaluation))
+    }
+}
+
+impl PolynomialCommitment for GenericPolynomialCommitment {
+    type Parameters = GlobalParameters;
+    type Commitment = G1Projective;
+    type Witness = G1Projective;
+
+    // A trusted setup procedure which can generate global parameters for the application
+    fn setup_with_rng<R: RngCore + CryptoRng>(
+        &mut self,
+        // The max degree, as for `setup`
+        d: usize,
+        rng: &mut R,
+    ) -> GlobalParameters {
+        self.setup_with_generators(d, Generators::standard(), rng)
+    }
+
+    // Generate the commitment to the polynomial
+    fn commit(&self, polynomial: &Polynomial) -> Result<G1Projective, Error> {
+        self.commit_coefficients(polynomial.coeffs())
     }
 
     // Create the witness and evaluation used for later verifying the evaluation
     // φ(x)−φ(i) / (x−i)
-    fn create_witness(&self, polynomial: Polynomial, point: Scalar) -> (G1Projective, Scalar) {
-        // The evaulation: φ(i). TODO: Does it need to be mod p?
-        let evaluation = polynomial.evaluate(point);
-        // Dividend φ(x)−φ(i). We retain the highest degree coefficients(φ(x)) and get −φ(i) by subtracting it by the lowest degree coefficient
-        let mut witness_polynomial = polynomial.clone();
-        witness_polynomial.0[0] -= &evaluation;
-        let divisor = Polynomial::new(&[-point, Scalar::ONE]);
-        witness_polynomial = witness_polynomial / divisor;
+    fn create_witness(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        if polynomial.is_zero() {
+            return Err(Error::ZeroPolynomial);
+        }
+        let (witness, evaluation) = self.open(polynomial, point)?;
+        // Constant polynomials have a zero quotient, so the witness would be the identity and say nothing about the point
+        if bool::from(witness.is_identity()) {
+            return Err(Error::IdentityWitness);
+        }
+        Ok((witness, evaluation))
+    }
 
-        // A small commit to this new polynomial where we care less about the length
-        let witness = G1Projective::multi_exp(
-            &self.global_parameters.as_ref().unwrap().gs[..witness_polynomial.0.len()],
-            &witness_polynomial.0,
-        );
+    // Determine if the hidden polynomial evaluated at the point did produce the evaluation based on the witness
+    fn verify_evaluation(
+        &self,
+        committed_polynomial: G1Projective,
+        point: Scalar,
+        evaluation: Scalar,
+        witness: G1Projective,
+    ) -> Result<bool, Error> {
+        self.global_parameters()?;
+        if bool::from(committed_polynomial.is_identity()) {
+            return Err(Error::IdentityCommitment);
+        }
+        if bool::from(witness.is_identity()) {
+            return Err(Error::IdentityWitness);
+        }
+        self.check_opening(committed_polynomial, point, evaluation, witness)
+    }
+}
+
+// The bare opening and pairing check. `create_witness` and `verify_evaluation` wrap these and reject the identity,
+// which is what a forged commitment to nothing looks like. Data commitments, where the zero polynomial or a constant
+// chunk is perfectly legitimate and the commitment is bound some other way (e.g. by a Merkle root), use these directly
+impl GenericPolynomialCommitment {
+    // The witness for any polynomial that fits the parameters, including zero and constant ones
+    pub fn open(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        // Protocols often open at 0 or ±1, where the quotient needs no division at all
+        if let Some((quotient, evaluation)) = polynomial.divide_by_structured_linear(point) {
+            let witness = self.msm(quotient.coeffs())?;
+            return Ok((witness, evaluation));
+        }
+
+        // Dividing φ(x) by (x - i) leaves φ(i) as the remainder, so the quotient of φ(x) − φ(i) comes from the same pass
+        let (quotient, evaluation) = polynomial.divide_by_linear(point);
 
-        (witness, evaluation)
+        // A small commit to this new polynomial where we care less about the length
+        let witness = self.msm(quotient.coeffs())?;
+        Ok((witness, evaluation))
     }
 
-    // Determine if the hidden polynomial evaluated at the point did produce the evaluation based on the witness
     // $e(\frac {C}{g^{\phi(i)}}, {g}) = e(w_i, \frac{g^\alpha}{g^i})$
-    fn verify_evaluation(
+    pub fn check_opening(
         &self,
         committed_polynomial: G1Projective,
         point: Scalar,
         evaluation: Scalar,
         witness: G1Projective,
-    ) -> bool {
-        let g1 = G1Projective::generator();
-        let g2 = G2Projective::generator();
+    ) -> Result<bool, Error> {
+        let global_parameters = self.global_parameters()?;
+        // Verification needs $g^\alpha$ in G2
+        if global_parameters.hs.len() < 2 {
+            return Err(Error::InvalidParameters("verification needs tau in G2"));
+        }
+
+        let g1 = global_parameters.gs[0];
+        let g2 = global_parameters.hs[0];
         let evaluation_inverse = g1 * -evaluation;
 
         // $\frac {C}{g^{\phi(i)}}$
@@ -150,16 +584,109 @@ impl PolynomialCommitment for GenericPolynomialCommitment {
         let point_commitment_inverted = g2 * -point;
 
         // $\frac{g^\alpha}{g^i}$
-        let right_side = self.global_parameters.as_ref().unwrap().hs[1] + point_commitment_inverted;
+        let right_side = global_parameters.hs[1] + point_commitment_inverted;
         let rhs = pairing(&witness.to_affine(), &right_side.to_affine());
-        lhs == rhs
+        Ok(ct_eq_gt(&lhs, &rhs).into())
     }
 }
 
 #[test]
 fn setup() {
     let mut polynomial_committer = GenericPolynomialCommitment::new();
-    let gp = polynomial_committer.setup(5);
+    let gp = polynomial_committer.setup(4);
+}
+
+#[test]
+fn setup_is_reproducible_with_seeded_rng() {
+    use crate::rng::seeded_rng_from_u64;
+
+    let mut polynomial_committer = GenericPolynomialCommitment::new();
+    let first = polynomial_committer.setup_with_rng(4, &mut seeded_rng_from_u64(42));
+    let second = polynomial_committer.setup_with_rng(4, &mut seeded_rng_from_u64(42));
+    let third = polynomial_committer.setup_with_rng(4, &mut seeded_rng_from_u64(43));
+
+    assert_eq!(first.gs, second.gs);
+    assert_ne!(first.gs, third.gs);
+}
+
+#[test]
+fn global_parameters_round_trip_through_bytes() {
+    let mut polynomial_committer = GenericPolynomialCommitment::new();
+    let gp = polynomial_committer.setup_with_rng(4, &mut test_rng());
+
+    let bytes = gp.to_bytes();
+    let decoded = GlobalParameters::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.gs, gp.gs);
+    assert_eq!(decoded.hs, gp.hs);
+    assert_eq!(decoded.fingerprint(), gp.fingerprint());
+
+    let other = polynomial_committer.setup_with_rng(4, &mut crate::rng::seeded_rng_from_u64(1));
+    assert_ne!(other.fingerprint(), gp.fingerprint());
+}
+
+#[test]
+fn validates_global_parameters() {
+    let g = G1Projective::generator();
+    let h = G2Projective::generator();
+    let tau = Scalar::from(9);
+
+    assert!(GlobalParameters::try_new(vec![g, g * tau], vec![h, h * tau]).is_ok());
+    assert!(GlobalParameters::try_new(vec![g, g * tau], vec![h]).is_ok());
+
+    let invalid = [
+        (vec![], vec![h]),
+        (vec![g], vec![]),
+        (vec![g], vec![h, h * tau]),
+        (vec![g * tau, g], vec![h]),
+        (vec![g], vec![h * tau]),
+        (vec![g, G1Projective::identity()], vec![h]),
+        (vec![g, g * tau], vec![h, G2Projective::identity()]),
+    ];
+    for (gs, hs) in invalid {
+        assert!(matches!(
+            GlobalParameters::try_new(gs, hs),
+            Err(Error::InvalidParameters(_))
+        ));
+    }
+}
+
+#[test]
+fn verifies_powers_of_tau() {
+    let mut rng = test_rng();
+    let gp = GenericPolynomialCommitment::new().setup_with_rng(5, &mut rng);
+    assert!(gp.verify_powers(&mut rng));
+
+    let g = G1Projective::generator();
+    let h = G2Projective::generator();
+    let tau = Scalar::from(9);
+    let broken_g1 =
+        GlobalParameters::try_new(vec![g, g * tau, g * tau * tau * tau], vec![h, h * tau]).unwrap();
+    assert!(!broken_g1.verify_powers(&mut rng));
+    let broken_g2 =
+        GlobalParameters::try_new(vec![g, g * tau, g * tau * tau], vec![h, h * tau, h * tau])
+            .unwrap();
+    assert!(!broken_g2.verify_powers(&mut rng));
+}
+
+#[test]
+fn rejects_tampered_global_parameters() {
+    let mut polynomial_committer = GenericPolynomialCommitment::new();
+    let gp = polynomial_committer.setup_with_rng(2, &mut test_rng());
+
+    let mut bytes = gp.to_bytes();
+    bytes[10] ^= 1;
+    assert_eq!(
+        GlobalParameters::from_bytes(&bytes).unwrap_err(),
+        Error::FingerprintMismatch
+    );
+    assert_eq!(
+        GlobalParameters::from_bytes(&bytes[..100]).unwrap_err(),
+        Error::FingerprintMismatch
+    );
+    assert!(matches!(
+        GlobalParameters::from_bytes(&[0; 8]),
+        Err(Error::InvalidEncoding(_))
+    ));
 }
 
 #[test]
@@ -170,28 +697,43 @@ fn errs_on_incorrect_polynomial_degree() {
     let mut polynomial_committer = GenericPolynomialCommitment::new();
 
     let max_degree = 25;
-    polynomial_committer.setup(max_degree);
+    polynomial_committer.setup_with_rng(max_degree, &mut test_rng());
 
     let too_small_commitment = polynomial_committer.commit(&small_polynomial);
     let too_large_commitment = polynomial_committer.commit(&large_polynomial);
-    assert_eq!(too_small_commitment, Err(Error::IncorrectDegree));
-    assert_eq!(too_large_commitment, Err(Error::IncorrectDegree));
+    assert_eq!(
+        too_small_commitment,
+        Err(Error::IncorrectDegree {
+            expected: 25,
+            actual: 2
+        })
+    );
+    assert_eq!(
+        too_large_commitment,
+        Err(Error::IncorrectDegree {
+            expected: 25,
+            actual: 419
+        })
+    );
 }
 
 #[test]
 fn adjusts_polynomial_of_different_size_to_correct_degree() {
-    let mut small_polynomial = Polynomial::new_from_bytes(&[1, 2, 3]);
-    let mut large_polynomial = Polynomial::new_from_bytes(&[1; 420]);
+    let small_polynomial = Polynomial::new_from_bytes(&[1, 2, 3]);
+    let large_polynomial = Polynomial::new_from_bytes(&[1; 420]);
 
-    let polynomial_committer = GenericPolynomialCommitment::new();
+    let mut polynomial_committer = GenericPolynomialCommitment::new();
 
     let max_degree = 25;
+    polynomial_committer.setup_with_rng(max_degree, &mut test_rng());
 
-    let too_small_polynomial_then_adjusted = small_polynomial.adjust_to_degree(max_degree);
-    let too_large_polynomial_then_adjusted = large_polynomial.adjust_to_degree(max_degree);
+    let too_small_polynomial_then_adjusted =
+        small_polynomial.adjusted_to_degree_with_rng(max_degree, &mut test_rng());
+    let too_large_polynomial_then_adjusted =
+        large_polynomial.adjusted_to_degree_with_rng(max_degree, &mut test_rng());
 
-    let too_small_commitment = polynomial_committer.commit(too_small_polynomial_then_adjusted);
-    let too_large_commitment = polynomial_committer.commit(too_large_polynomial_then_adjusted);
+    let too_small_commitment = polynomial_committer.commit(&too_small_polynomial_then_adjusted);
+    let too_large_commitment = polynomial_committer.commit(&too_large_polynomial_then_adjusted);
 
     assert!(too_small_commitment.is_ok());
     assert!(too_large_commitment.is_ok());
@@ -201,16 +743,15 @@ fn adjusts_polynomial_of_different_size_to_correct_degree() {
 fn polynomial_commitment() {
     use crate::*;
 
-    let mut polynomial = Polynomial::new_from_bytes(&[1, 2, 3]);
+    let polynomial = Polynomial::new_from_bytes(&[1, 2, 3]);
     let mut polynomial_committer = GenericPolynomialCommitment::new();
     let max_degree = 25;
 
-    polynomial_committer.setup(max_degree);
+    polynomial_committer.setup_with_rng(max_degree, &mut test_rng());
 
     // Get degree of polynomial commitment, and pad accordingly
-    polynomial.adjust_to_degree(max_degree);
-
-    let commitment = polynomial_committer.commit(&polynomial);
+    let commitment = polynomial_committer
+        .commit(&polynomial.adjusted_to_degree_with_rng(max_degree, &mut test_rng()));
 
     assert!(commitment.is_ok());
 }
@@ -220,17 +761,144 @@ fn creates_and_verifies_witness_polynomial_evaluation() {
     env_logger::init();
 
     let mut polynomial_committer = GenericPolynomialCommitment::new();
-    polynomial_committer.setup(3);
+    polynomial_committer.setup_with_rng(2, &mut test_rng());
 
     let polynomial = Polynomial::new_from_bytes(&[1, 2, 3]);
     let point = Scalar::from(5);
 
     let commitment = polynomial_committer.commit(&polynomial);
-    let (witness, evaluation) = polynomial_committer.create_witness(polynomial, point);
+    let (witness, evaluation) = polynomial_committer
+        .create_witness(&polynomial, point)
+        .unwrap();
     let result =
         polynomial_committer.verify_evaluation(commitment.unwrap(), point, evaluation, witness);
 
-    assert!(result);
+    assert_eq!(result, Ok(true));
+}
+
+#[test]
+fn rejects_zero_polynomial_and_identity_points() {
+    let mut polynomial_committer = GenericPolynomialCommitment::new();
+    polynomial_committer.setup_with_rng(2, &mut test_rng());
+
+    let zero = Polynomial::new_from_bytes(&[0, 0, 0]);
+    assert_eq!(
+        polynomial_committer.commit(&zero),
+        Err(Error::ZeroPolynomial)
+    );
+    assert_eq!(
+        polynomial_committer.create_witness(&zero, Scalar::from(5)),
+        Err(Error::ZeroPolynomial)
+    );
+
+    let constant = Polynomial::new_from_bytes(&[7]);
+    assert_eq!(
+        polynomial_committer.create_witness(&constant, Scalar::from(5)),
+        Err(Error::IdentityWitness)
+    );
+
+    let commitment = polynomial_committer
+        .commit(&Polynomial::new_from_bytes(&[1, 2, 3]))
+        .unwrap();
+    assert_eq!(
+        polynomial_committer.verify_evaluation(
+            commitment,
+            Scalar::from(5),
+            Scalar::from(86),
+            G1Projective::identity()
+        ),
+        Err(Error::IdentityWitness)
+    );
+    assert_eq!(
+        polynomial_committer.verify_evaluation(
+            G1Projective::identity(),
+            Scalar::from(5),
+            Scalar::ZERO,
+            G1Projective::generator()
+        ),
+        Err(Error::IdentityCommitment)
+    );
+}
+
+#[test]
+fn opens_at_structured_points() {
+    let mut polynomial_committer = GenericPolynomialCommitment::new();
+    polynomial_committer.setup_with_rng(3, &mut test_rng());
+    let polynomial = Polynomial::new_from_i64(&[-1, 3, -4, 1]);
+    let commitment = polynomial_committer.commit(&polynomial).unwrap();
+
+    for point in [Scalar::ZERO, Scalar::ONE, -Scalar::ONE] {
+        let (witness, evaluation) = polynomial_committer
+            .create_witness(&polynomial, point)
+            .unwrap();
+        assert_eq!(evaluation, polynomial.evaluate(point));
+        assert_eq!(
+            polynomial_committer.verify_evaluation(commitment, point, evaluation, witness),
+            Ok(true)
+        );
+    }
+}
+
+#[test]
+fn errs_when_used_before_setup() {
+    let polynomial_committer = GenericPolynomialCommitment::new();
+    let polynomial = Polynomial::new_from_bytes(&[1, 2, 3]);
+
+    assert_eq!(
+        polynomial_committer.create_witness(&polynomial, Scalar::from(5)),
+        Err(Error::SetupIncomplete)
+    );
+    assert_eq!(
+        polynomial_committer.verify_evaluation(
+            G1Projective::generator(),
+            Scalar::from(5),
+            Scalar::ONE,
+            G1Projective::generator()
+        ),
+        Err(Error::SetupIncomplete)
+    );
+}
+
+#[test]
+fn commits_and_opens_fixed_polynomials() {
+    let mut polynomial_committer = GenericPolynomialCommitment::new();
+    polynomial_committer.setup_with_rng(2, &mut test_rng());
+
+    let coefficients = [
+        Scalar::from(1),
+        Scalar::from(2),
+        Scalar::from(3),
+        Scalar::ZERO,
+    ];
+    let fixed = FixedPolynomial::new(coefficients);
+    let polynomial = Polynomial::new(&coefficients);
+    let point = Scalar::from(5);
+
+    let commitment = polynomial_committer.commit_fixed(&fixed).unwrap();
+    assert_eq!(
+        commitment,
+        polynomial_committer.commit(&polynomial).unwrap()
+    );
+
+    let (witness, evaluation) = polynomial_committer
+        .create_witness_fixed(&fixed, point)
+        .unwrap();
+    assert_eq!(
+        (witness, evaluation),
+        polynomial_committer
+            .create_witness(&polynomial, point)
+            .unwrap()
+    );
+    assert_eq!(
+        polynomial_committer.verify_evaluation(commitment, point, evaluation, witness),
+        Ok(true)
+    );
+
+    let constant = FixedPolynomial::new([Scalar::from(7), Scalar::ZERO]);
+    assert_eq!(
+        polynomial_committer.create_witness_fixed(&constant, point),
+        Err(Error::IdentityWitness)
+    );
 }
 
 #[test]
@@ -280,3 +948,45 @@ fn intuition_committed_polynomial_evaluation_basic() {
 
     assert_eq!(lhs, rhs);
 }
+
+#[test]
+fn commits_to_typed_data() {
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(3, &mut test_rng());
+
+    let data = [7u32, 0, 5];
+    let polynomial = Polynomial::from_data(&data).unwrap();
+    assert_eq!(polynomial, Polynomial::new_from_i64(&[7, 0, 5]));
+    assert_eq!(
+        committer.commit_data(&data).unwrap(),
+        committer
+            .global_parameters()
+            .unwrap()
+            .commit_in_g1(polynomial.coeffs())
+            .unwrap()
+    );
+    assert_eq!(
+        committer.commit_data(&[7u64, 0, 5, 0]).unwrap(),
+        committer.commit_data(&data).unwrap()
+    );
+    assert!(committer.commit_data(&[1u8; 5]).is_err());
+    assert_eq!(
+        committer.commit_data(&[[0xffu8; 32]]),
+        Err(Error::InvalidScalar { index: 0 })
+    );
+}
+
+#[test]
+fn fixed_base_multiplication_matches_scalar_multiplication() {
+    let mut rng = test_rng();
+    let mut scalars: Vec<Scalar> = (0..20).map(|_| Scalar::random(&mut rng)).collect();
+    scalars.extend([Scalar::ZERO, Scalar::ONE, -Scalar::ONE]);
+    for count in [1, 100, 2000] {
+        let g1 = FixedBase::new(G1Projective::generator(), count);
+        let g2 = FixedBase::new(G2Projective::generator(), count);
+        for scalar in &scalars {
+            assert_eq!(g1.mul(scalar), G1Projective::generator() * scalar);
+            assert_eq!(g2.mul(scalar), G2Projective::generator() * scalar);
+        }
+    }
+}
@@ -1,16 +1,32 @@
+use crate::domain::EvaluationDomain;
+use crate::error::Error;
+use crate::parallel::map_indexed;
+use crate::scalars::{scalar_from_bigint, scalar_from_i64, scalars_from_data, IntoScalar};
 use blstrs::Scalar;
-use num_traits::pow;
-use rand::RngCore;
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use regex::Regex;
 
-use core::ops::Div;
+use core::fmt;
+use core::ops::{Add, Div, Index, Mul};
+use core::str::FromStr;
 use group::ff::Field;
+use std::sync::OnceLock;
+use subtle::{Choice, ConstantTimeEq};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Polynomial(pub Vec<Scalar>);
+// Coefficients are stored lowest degree first. Constructors keep polynomials in canonical form, without trailing
+// zero coefficients, so the zero polynomial has no coefficients at all. The coefficients are only reachable through
+// `coeffs` and the constructors, so nothing outside this module can leave a polynomial out of canonical form
+#[derive(Clone, Debug)]
+pub struct Polynomial(Vec<Scalar>);
 
 impl Polynomial {
     pub fn new(scalars: &[Scalar]) -> Self {
-        Polynomial(scalars.to_vec())
+        let mut polynomial = Polynomial(scalars.to_vec());
+        polynomial.trim();
+        polynomial
     }
 
     pub fn new_from_bytes(bytes: &[u8]) -> Self {
@@ -18,86 +34,597 @@ impl Polynomial {
             .into_iter()
             .map(|d| Scalar::from(d.clone() as u64))
             .collect();
-        Polynomial(scalars.to_vec())
+        Polynomial::new(&scalars)
     }
 
+    // The coefficient of x^i, which is zero past the end of the stored coefficients
+    pub fn coeff(&self, i: usize) -> Scalar {
+        self.0.get(i).copied().unwrap_or(Scalar::ZERO)
+    }
+
+    // All stored coefficients, lowest degree first
+    pub fn coeffs(&self) -> &[Scalar] {
+        &self.0
+    }
+
+    // Takes the coefficients as they are, trailing zeros included, for code that must not copy or reshape them, like
+    // sharing and wiping secret polynomials
+    pub(crate) fn from_coeffs_untrimmed(coefficients: Vec<Scalar>) -> Self {
+        Polynomial(coefficients)
+    }
+
+    pub(crate) fn coeffs_mut(&mut self) -> &mut Vec<Scalar> {
+        &mut self.0
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, Scalar> {
+        self.0.iter()
+    }
+
+    // Number of stored coefficients. For a canonical polynomial this is its degree + 1
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Negative coefficients map to their additive inverses, so `x - 5` is `new_from_i64(&[-5, 1])`
+    pub fn new_from_i64(coefficients: &[i64]) -> Self {
+        let scalars: Vec<Scalar> = coefficients.iter().map(|c| scalar_from_i64(*c)).collect();
+        Polynomial::new(&scalars)
+    }
+
+    // Like `new_from_i64` for coefficients of any size; values are reduced mod the field order
+    pub fn new_from_bigints(coefficients: &[BigInt]) -> Self {
+        let scalars: Vec<Scalar> = coefficients.iter().map(scalar_from_bigint).collect();
+        Polynomial::new(&scalars)
+    }
+
+    // Data elements as coefficients, lowest degree first. Errs with the index of the first element that isn't a
+    // canonical field element rather than reducing it
+    pub fn from_data<T: IntoScalar>(data: &[T]) -> Result<Self, Error> {
+        Ok(Polynomial::new(&scalars_from_data(data)?))
+    }
+
+    // A uniformly random polynomial of exactly the given degree (the leading coefficient is never zero)
+    pub fn rand<R: RngCore + CryptoRng>(degree: usize, rng: &mut R) -> Self {
+        let mut coefficients: Vec<Scalar> =
+            (0..degree).map(|_| Scalar::random(&mut *rng)).collect();
+        let mut leading = Scalar::random(&mut *rng);
+        while bool::from(leading.is_zero()) {
+            leading = Scalar::random(&mut *rng);
+        }
+        coefficients.push(leading);
+        Polynomial(coefficients)
+    }
+
+    // The monic polynomial vanishing exactly on the given points: $\prod_i (x - r_i)$
+    pub fn vanishing(roots: &[Scalar]) -> Self {
+        let mut coefficients = vec![Scalar::ONE];
+        for root in roots {
+            // Multiply by (x - root) in place: shift up by one and subtract root times the old coefficients
+            coefficients.push(Scalar::ZERO);
+            for i in (0..coefficients.len()).rev() {
+                let lower = if i > 0 {
+                    coefficients[i - 1]
+                } else {
+                    Scalar::ZERO
+                };
+                coefficients[i] = lower - *root * coefficients[i];
+            }
+        }
+        Polynomial(coefficients)
+    }
+
+    // A random nonzero multiple of the vanishing polynomial of `roots`, so it has degree `roots.len()` and evaluates
+    // to zero on every root
+    pub fn rand_with_roots<R: RngCore + CryptoRng>(roots: &[Scalar], rng: &mut R) -> Self {
+        let mut scale = Scalar::random(&mut *rng);
+        while bool::from(scale.is_zero()) {
+            scale = Scalar::random(&mut *rng);
+        }
+        let mut polynomial = Polynomial::vanishing(roots);
+        for coefficient in polynomial.0.iter_mut() {
+            *coefficient *= scale;
+        }
+        polynomial
+    }
+
+    // Canonical encoding: the number of coefficients as a little-endian u32, then each coefficient as 32 little-endian
+    // bytes, lowest degree first. Trailing zeros are never encoded, so every polynomial has exactly one encoding
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let coefficients = self.trimmed();
+        let mut bytes = Vec::with_capacity(4 + coefficients.len() * 32);
+        bytes.extend_from_slice(&(coefficients.len() as u32).to_le_bytes());
+        for coefficient in coefficients {
+            bytes.extend_from_slice(&coefficient.to_bytes_le());
+        }
+        bytes
+    }
+
+    // Inverse of `to_bytes`. Rejects a length prefix that doesn't match the data, coefficients that aren't reduced
+    // mod the field order, and trailing zero coefficients
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 4 {
+            return Err(Error::InvalidEncoding("missing length prefix"));
+        }
+        let (len, data) = bytes.split_at(4);
+        let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+        if data.len() != len * 32 {
+            return Err(Error::InvalidEncoding(
+                "length prefix doesn't match the data length",
+            ));
+        }
+
+        let coefficients = data
+            .chunks_exact(32)
+            .enumerate()
+            .map(|(index, chunk)| {
+                Option::<Scalar>::from(Scalar::from_bytes_le(chunk.try_into().unwrap()))
+                    .ok_or(Error::InvalidScalar { index })
+            })
+            .collect::<Result<Vec<Scalar>, _>>()?;
+        if let Some(true) = coefficients.last().map(|c| bool::from(c.is_zero())) {
+            return Err(Error::InvalidEncoding("trailing zero coefficient"));
+        }
+        Ok(Polynomial(coefficients))
+    }
+
+    // Drop trailing zero coefficients, putting the polynomial in canonical form
+    pub fn trim(&mut self) {
+        while let Some(true) = self.0.last().map(|c| bool::from(c.is_zero())) {
+            self.0.pop();
+        }
+    }
+
+    // The coefficients without any trailing zeros
+    fn trimmed(&self) -> &[Scalar] {
+        let len = self
+            .0
+            .iter()
+            .rposition(|c| !bool::from(c.is_zero()))
+            .map_or(0, |i| i + 1);
+        &self.0[..len]
+    }
+
+    // The degree of the polynomial, ignoring trailing zero coefficients. The zero polynomial has no degree
+    pub fn degree(&self) -> Option<usize> {
+        self.trimmed().len().checked_sub(1)
+    }
+
+    // The formal derivative, $\sum i f_i x^{i-1}$
+    pub fn derivative(&self) -> Polynomial {
+        let coefficients: Vec<Scalar> = self
+            .trimmed()
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, coefficient)| *coefficient * Scalar::from(i as u64))
+            .collect();
+        Polynomial::new(&coefficients)
+    }
+
+    // Horner's rule: $f_0 + x(f_1 + x(f_2 + \ldots))$, one multiplication and addition per coefficient
     pub fn evaluate(&self, point: Scalar) -> Scalar {
-        let mut total = Scalar::from(0_u64);
-        for (i, coefficient) in self.0.iter().enumerate() {
-            total += pow(point, i) * coefficient
+        self.0
+            .iter()
+            .rev()
+            .fold(Scalar::ZERO, |total, coefficient| {
+                total * point + coefficient
+            })
+    }
+
+    // Evaluate coefficients arriving lowest degree first without collecting them, keeping a running power of the
+    // point. Useful when the coefficients are streamed and Horner's highest-first order isn't available
+    pub fn evaluate_streaming(
+        coefficients: impl IntoIterator<Item = Scalar>,
+        point: Scalar,
+    ) -> Scalar {
+        let mut power = Scalar::ONE;
+        let mut total = Scalar::ZERO;
+        for coefficient in coefficients {
+            total += power * coefficient;
+            power *= point;
         }
         total
     }
 
+    // Evaluate several polynomials at the same point, e.g. all column polynomials at a verifier challenge. The powers
+    // of the point are computed once and shared, so each polynomial costs one dot product
+    pub fn evaluate_batch(polynomials: &[Polynomial], point: Scalar) -> Vec<Scalar> {
+        let max_len = polynomials.iter().map(|p| p.0.len()).max().unwrap_or(0);
+        let mut powers = Vec::with_capacity(max_len);
+        let mut power = Scalar::ONE;
+        for _ in 0..max_len {
+            powers.push(power);
+            power *= point;
+        }
+        map_indexed(polynomials, |_, polynomial| {
+            polynomial
+                .0
+                .iter()
+                .zip(powers.iter())
+                .map(|(coefficient, power)| *coefficient * power)
+                .sum()
+        })
+    }
+
+    // Evaluate at many points at once using a remainder tree: reduce the polynomial modulo the product of all
+    // $(x - p_i)$, then recursively modulo each half's product until only constants $f(p_i)$ remain. Small inputs
+    // are evaluated directly since building the tree isn't worth it
+    pub fn evaluate_many(&self, points: &[Scalar]) -> Vec<Scalar> {
+        if points.len() <= EVALUATE_MANY_CUTOFF {
+            return points.iter().map(|point| self.evaluate(*point)).collect();
+        }
+        let tree = SubproductTree::new(points);
+        let mut evaluations = Vec::with_capacity(points.len());
+        // Subproducts are monic, so they're never zero
+        let (_, remainder) = self.div_rem(&tree.product).unwrap();
+        tree.evaluate(remainder, &mut evaluations);
+        evaluations
+    }
+
+    // A copy padded with randomness to a given degree, or if too large, truncated to the degree. `self` is unchanged
+    pub fn adjusted_to_degree(&self, d: usize) -> Polynomial {
+        self.adjusted_to_degree_with_rng(d, &mut OsRng)
+    }
+
+    // Same as `adjusted_to_degree`, but draws the padding from the given rng
+    pub fn adjusted_to_degree_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        d: usize,
+        rng: &mut R,
+    ) -> Polynomial {
+        let mut adjusted = self.clone();
+        adjusted.pad_or_truncate(d, rng);
+        adjusted
+    }
+
     // Adjust a polynomial by padding with randomness to a given degree, or if too large, truncate it to the degree
+    #[deprecated(
+        note = "use `adjusted_to_degree`, which returns the adjusted polynomial instead of mutating in place"
+    )]
     pub fn adjust_to_degree(&mut self, d: usize) -> &mut Self {
+        self.pad_or_truncate(d, &mut OsRng);
+        self
+    }
+
+    // Same as `adjust_to_degree`, but draws the padding from the given rng
+    #[deprecated(
+        note = "use `adjusted_to_degree_with_rng`, which returns the adjusted polynomial instead of mutating in place"
+    )]
+    pub fn adjust_to_degree_with_rng<R: RngCore + CryptoRng>(
+        &mut self,
+        d: usize,
+        rng: &mut R,
+    ) -> &mut Self {
+        self.pad_or_truncate(d, rng);
+        self
+    }
+
+    // Degree d is d + 1 coefficients
+    fn pad_or_truncate<R: RngCore + CryptoRng>(&mut self, d: usize, rng: &mut R) {
+        let length = d + 1;
         // Polynomial degree is too small, will padd
-        if self.0.len() < d {
-            let difference = d - self.0.len();
-            let mut unfilled = vec![];
-            rand::thread_rng();
-            let mut rng = rand::thread_rng();
-            for _ in 0..difference {
-                unfilled.push(rng.next_u64())
-            }
-
-            let new_randoms: Vec<Scalar> = unfilled
-                .into_iter()
-                .map(|i| Scalar::from(i as u64))
-                .collect();
-            self.0.extend(new_randoms.iter());
-            self
-        } else if self.0.len() > d {
+        if self.0.len() < length {
+            // Uniform field elements, since the padding doubles as blinding
+            let difference = length - self.0.len();
+            self.0
+                .extend((0..difference).map(|_| Scalar::random(&mut *rng)));
+        } else if self.0.len() > length {
             // Polynomial degree is too big; truncate it
             // TODO: it should probably be more representative over the full polynomial than just keep the first `d`
-            self.0.truncate(d);
-            self
+            self.0.truncate(length);
+        }
+        // The result has to have degree exactly d, so a zero left on top by truncation is replaced with randomness
+        // like the padding. Redrawn until nonzero, which a uniform draw almost never needs
+        while bool::from(self.0[d].is_zero()) {
+            self.0[d] = Scalar::random(&mut *rng);
+        }
+    }
+
+    // Division by $(x - point)$ for any point by synthetic division, reading the polynomial in place. The remainder is
+    // the evaluation at `point`, so this opens in one pass
+    pub fn divide_by_linear(&self, point: Scalar) -> (Polynomial, Scalar) {
+        let coefficients = self.trimmed();
+        if coefficients.is_empty() {
+            return (Polynomial(vec![]), Scalar::ZERO);
+        }
+        let mut quotient = vec![Scalar::ZERO; coefficients.len() - 1];
+        let mut carry = Scalar::ZERO;
+        for i in (0..coefficients.len()).rev() {
+            carry = coefficients[i] + carry * point;
+            if i > 0 {
+                quotient[i - 1] = carry;
+            }
+        }
+        (Polynomial(quotient), carry)
+    }
+
+    // Division by $(x - point)$ for the points that need no multiplications: at zero the quotient is the polynomial
+    // shifted down and the remainder is the constant term, and at one and minus one synthetic division only adds or
+    // subtracts. Returns the quotient and remainder, or `None` for any other point
+    pub fn divide_by_structured_linear(&self, point: Scalar) -> Option<(Polynomial, Scalar)> {
+        let coefficients = self.trimmed();
+        if coefficients.is_empty() {
+            return Some((Polynomial(vec![]), Scalar::ZERO));
+        }
+        if bool::from(point.is_zero()) {
+            return Some((Polynomial(coefficients[1..].to_vec()), coefficients[0]));
+        }
+        let negate = if point == Scalar::ONE {
+            false
+        } else if point == -Scalar::ONE {
+            true
         } else {
-            // If it's the right degree, no change
-            self
+            return None;
+        };
+
+        let mut quotient = vec![Scalar::ZERO; coefficients.len() - 1];
+        let mut carry = Scalar::ZERO;
+        for i in (0..coefficients.len()).rev() {
+            let value = if negate {
+                coefficients[i] - carry
+            } else {
+                coefficients[i] + carry
+            };
+            if i > 0 {
+                quotient[i - 1] = value;
+            }
+            carry = value;
         }
+        Some((Polynomial(quotient), carry))
     }
 
-    fn is_zero(&self) -> bool {
+    pub fn is_zero(&self) -> bool {
         self.0.is_empty() || self.0.iter().all(|coeff| coeff.is_zero().into())
     }
 
     fn leading_coefficient(&self) -> Option<Scalar> {
         self.0.last().copied()
     }
+}
 
-    pub fn display(&self) -> String {
-        let mut result = String::new();
-        for (i, coeff) in self.0.iter().enumerate() {
-            if i > 0 {
-                result.push_str(" + ");
-                result.push_str(&format!("{}x^{}", coeff, i));
-            } else {
-                result.push_str(&format!("{}", coeff));
+// Below this many points `evaluate_many` just evaluates each point directly
+const EVALUATE_MANY_CUTOFF: usize = 16;
+
+// Binary tree whose leaves are $(x - p_i)$ and whose inner nodes hold the product of their children
+struct SubproductTree {
+    product: Polynomial,
+    children: Option<Box<(SubproductTree, SubproductTree)>>,
+}
+
+impl SubproductTree {
+    fn new(points: &[Scalar]) -> Self {
+        if points.len() == 1 {
+            return SubproductTree {
+                product: Polynomial(vec![-points[0], Scalar::ONE]),
+                children: None,
+            };
+        }
+        let (left, right) = points.split_at(points.len() / 2);
+        let left = SubproductTree::new(left);
+        let right = SubproductTree::new(right);
+        SubproductTree {
+            product: &left.product * &right.product,
+            children: Some(Box::new((left, right))),
+        }
+    }
+
+    // `remainder` is the polynomial reduced modulo this node's product. At a leaf that's the constant $f(p_i)$
+    fn evaluate(&self, remainder: Polynomial, evaluations: &mut Vec<Scalar>) {
+        match &self.children {
+            None => evaluations.push(remainder.coeff(0)),
+            Some(children) => {
+                let (left, right) = children.as_ref();
+                let (_, left_remainder) = remainder.div_rem(&left.product).unwrap();
+                let (_, right_remainder) = remainder.div_rem(&right.product).unwrap();
+                left.evaluate(left_remainder, evaluations);
+                right.evaluate(right_remainder, evaluations);
             }
         }
-        result.replace("00", "")
     }
 }
 
-// Division implementation from Arkworks
-// TODO: Needs test
-impl Div for Polynomial {
-    type Output = Self;
-    fn div(self, divisor: Self) -> Self::Output {
-        if self.is_zero() {
-            Polynomial::new(&[Scalar::from(0)])
-        } else if divisor.is_zero() {
-            panic!("Dividing by zero polynomial")
-        } else if self.0.len() < divisor.0.len() {
-            Polynomial::new(&[Scalar::from(0)])
+// Splits a coefficient into a sign and a magnitude for printing. Anything that fits in a u64, or whose negation does,
+// prints as that integer; the rest print as their canonical value in decimal
+fn signed_magnitude(coeff: &Scalar) -> (bool, String) {
+    let fits_u64 = |bytes: [u8; 32]| {
+        bytes[8..]
+            .iter()
+            .all(|b| *b == 0)
+            .then(|| u64::from_le_bytes(bytes[..8].try_into().unwrap()))
+    };
+    if let Some(value) = fits_u64(coeff.to_bytes_le()) {
+        (false, value.to_string())
+    } else if let Some(value) = fits_u64((-*coeff).to_bytes_le()) {
+        (true, value.to_string())
+    } else {
+        (
+            false,
+            BigUint::from_bytes_le(&coeff.to_bytes_le()).to_string(),
+        )
+    }
+}
+
+// Highest degree first, in the same form the parser accepts: `3x^2 + x - 1`. Zero terms are skipped and the zero
+// polynomial prints as `0`
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (i, coeff) in self.0.iter().enumerate().rev() {
+            if bool::from(coeff.is_zero()) {
+                continue;
+            }
+            let (negative, magnitude) = signed_magnitude(coeff);
+            match (first, negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+
+            if i == 0 || magnitude != "1" {
+                write!(f, "{}", magnitude)?;
+            }
+            match i {
+                0 => {}
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{}", i)?,
+            }
+        }
+        if first {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+// Highest exponent the parser will accept, so untrusted input can't make us allocate unbounded coefficient vectors
+const MAX_PARSED_DEGREE: usize = 1 << 20;
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ParsePolynomialError {
+    // There were no terms to parse
+    #[error("no terms to parse")]
+    Empty,
+    // A term isn't of the form `c`, `cx`, `x^e` or `cx^e` (optionally `c*x`), or its exponent is too large
+    #[error("invalid term `{0}`")]
+    InvalidTerm(String),
+}
+
+// Parses human-readable polynomials in `x` such as `3x^2 + 2x - 1`. Terms may appear in any order and repeated powers
+// are added together; coefficients are integers of any size and default to 1
+impl FromStr for Polynomial {
+    type Err = ParsePolynomialError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Compiled on first use and shared by every parse after that
+        static TERM_REGEX: OnceLock<Regex> = OnceLock::new();
+        let term_regex =
+            TERM_REGEX.get_or_init(|| Regex::new(r"^([0-9]+)?(?:\*?x(?:\^([0-9]+))?)?$").unwrap());
+        let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.is_empty() {
+            return Err(ParsePolynomialError::Empty);
+        }
+
+        // Split before every sign so each term carries its own
+        let mut terms = vec![];
+        let mut start = 0;
+        for (i, c) in compact.char_indices() {
+            if (c == '+' || c == '-') && i > start {
+                terms.push(&compact[start..i]);
+                start = i;
+            }
+        }
+        terms.push(&compact[start..]);
+
+        let mut coefficients: Vec<BigInt> = vec![];
+        for term in terms {
+            let invalid = || ParsePolynomialError::InvalidTerm(term.to_string());
+            let (negative, body) = match term.as_bytes()[0] {
+                b'-' => (true, &term[1..]),
+                b'+' => (false, &term[1..]),
+                _ => (false, term),
+            };
+            let captures = term_regex.captures(body).ok_or_else(invalid)?;
+            // `*` only joins a coefficient to the x, so a term can't start with one
+            if body.is_empty() || body.starts_with('*') {
+                return Err(invalid());
+            }
+
+            let mut coefficient = match captures.get(1) {
+                Some(c) => c.as_str().parse::<BigInt>().map_err(|_| invalid())?,
+                None => BigInt::from(1),
+            };
+            if negative {
+                coefficient = -coefficient;
+            }
+            let exponent = match (body.contains('x'), captures.get(2)) {
+                (false, _) => 0,
+                (true, None) => 1,
+                (true, Some(e)) => e.as_str().parse::<usize>().map_err(|_| invalid())?,
+            };
+            if exponent > MAX_PARSED_DEGREE {
+                return Err(invalid());
+            }
+
+            if coefficients.len() <= exponent {
+                coefficients.resize(exponent + 1, BigInt::from(0));
+            }
+            coefficients[exponent] += coefficient;
+        }
+        Ok(Polynomial::new_from_bigints(&coefficients))
+    }
+}
+
+// Polynomials are equal when their canonical forms are, so `[1, 2, 3]` and `[1, 2, 3, 0]` compare equal
+impl PartialEq for Polynomial {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed() == other.trimmed()
+    }
+}
+
+impl Eq for Polynomial {}
+
+impl Index<usize> for Polynomial {
+    type Output = Scalar;
+
+    fn index(&self, i: usize) -> &Scalar {
+        &self.0[i]
+    }
+}
+
+impl IntoIterator for Polynomial {
+    type Item = Scalar;
+    type IntoIter = std::vec::IntoIter<Scalar>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Polynomial {
+    type Item = &'a Scalar;
+    type IntoIter = core::slice::Iter<'a, Scalar>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+// Compares coefficients without short-circuiting on the first difference. Like `PartialEq`, trailing zeros don't count:
+// the shorter polynomial is padded with zeros rather than trimmed, so the degrees don't leak through the comparison's
+// timing either. Only the stored lengths are treated as public
+impl ConstantTimeEq for Polynomial {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        (0..self.0.len().max(other.0.len())).fold(Choice::from(1), |acc, i| {
+            acc & self.coeff(i).ct_eq(&other.coeff(i))
+        })
+    }
+}
+
+// Division implementation from Arkworks, extended to also return the remainder
+impl Polynomial {
+    fn long_division(mut self, mut divisor: Self) -> Result<(Polynomial, Polynomial), Error> {
+        self.trim();
+        divisor.trim();
+        if divisor.is_zero() {
+            Err(Error::DivisionByZero)
+        } else if self.is_zero() || self.0.len() < divisor.0.len() {
+            Ok((Polynomial::new(&[Scalar::from(0)]), self))
         } else {
             // Now we know that self.degree() >= divisor.degree();
-            let mut quotient =
-                Polynomial::new(&vec![Scalar::ZERO; self.0.len() - divisor.0.len() + 1]);
+            let mut quotient = Polynomial(vec![Scalar::ZERO; self.0.len() - divisor.0.len() + 1]);
             let mut remainder: Polynomial = self.clone().into();
-            // Can unwrap here because we know self is not zero.
-            let divisor_leading_inv = divisor.leading_coefficient().unwrap().invert().unwrap();
+            // The divisor is trimmed and nonzero, so its leading coefficient is invertible
+            let divisor_leading_inv = divisor
+                .leading_coefficient()
+                .and_then(|c| Option::<Scalar>::from(c.invert()))
+                .ok_or(Error::DivisionByZero)?;
             while !remainder.is_zero() && remainder.0.len() >= divisor.0.len() {
                 let cur_q_coeff = remainder.leading_coefficient().unwrap() * divisor_leading_inv;
                 let cur_q_degree = remainder.0.len() - divisor.0.len();
@@ -110,8 +637,295 @@ impl Div for Polynomial {
                     remainder.0.pop();
                 }
             }
-            quotient
+            Ok((quotient, remainder))
+        }
+    }
+}
+
+// Divisors with at least this many coefficients use Newton iteration instead of long division
+const FAST_DIVISION_CUTOFF: usize = 64;
+// Products where both factors have at least this many coefficients are computed with FFTs
+const FAST_MULTIPLICATION_CUTOFF: usize = 64;
+
+// Fast division: with $rev(f) = x^{deg f} f(1/x)$, the reversed quotient is $rev(f) \cdot rev(g)^{-1} \bmod x^{k}$ for
+// $k = deg f - deg g + 1$, and the power series inverse is found by Newton iteration $h \leftarrow h(2 - gh)$, doubling
+// the precision each step. With FFT multiplication this is O(n log n) instead of long division's O(n·d)
+impl Polynomial {
+    // Quotient and remainder, picking long division or Newton iteration depending on the divisor's size. Errs rather
+    // than panicking on a zero divisor, so it's safe to call on untrusted input
+    pub fn div_rem(&self, divisor: &Polynomial) -> Result<(Polynomial, Polynomial), Error> {
+        if divisor
+            .degree()
+            .map_or(false, |d| d + 1 >= FAST_DIVISION_CUTOFF)
+        {
+            self.fast_division(divisor)
+        } else {
+            self.clone().long_division(divisor.clone())
+        }
+    }
+
+    // The quotient alone, discarding any remainder like `/` does, but without panicking
+    pub fn checked_div(&self, divisor: &Polynomial) -> Result<Polynomial, Error> {
+        self.div_rem(divisor).map(|(quotient, _)| quotient)
+    }
+
+    pub fn fast_division(&self, divisor: &Polynomial) -> Result<(Polynomial, Polynomial), Error> {
+        let dividend = Polynomial::new(self.trimmed());
+        let divisor = Polynomial::new(divisor.trimmed());
+        if divisor.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        if dividend.len() < divisor.len() {
+            return Ok((Polynomial(vec![]), dividend));
+        }
+
+        let quotient_len = dividend.len() - divisor.len() + 1;
+        let reversed_dividend = reversed(dividend.coeffs(), dividend.len());
+        let reversed_divisor = reversed(divisor.coeffs(), divisor.len());
+        // The reversed divisor's constant term is the divisor's leading coefficient, which is nonzero
+        let divisor_inverse = reversed_divisor
+            .inverse_series(quotient_len)
+            .ok_or(Error::DivisionByZero)?;
+        let mut reversed_quotient = &reversed_dividend * &divisor_inverse;
+        reversed_quotient.truncate_to(quotient_len);
+        let quotient = reversed(reversed_quotient.coeffs(), quotient_len);
+
+        let product = &divisor * &quotient;
+        let remainder: Vec<Scalar> = (0..divisor.len() - 1)
+            .map(|i| dividend.coeff(i) - product.coeff(i))
+            .collect();
+        Ok((quotient, Polynomial::new(&remainder)))
+    }
+
+    // The power series inverse modulo $x^{precision}$, or `None` if the constant term is zero
+    fn inverse_series(&self, precision: usize) -> Option<Polynomial> {
+        let constant_inverse = Option::<Scalar>::from(self.coeff(0).invert())?;
+        let mut inverse = Polynomial(vec![constant_inverse]);
+        let mut current = 1;
+        while current < precision {
+            current = (2 * current).min(precision);
+            let mut truncated = Polynomial::new(&self.0[..current.min(self.0.len())]);
+            truncated.truncate_to(current);
+
+            // h(2 - gh) mod x^current
+            let mut correction = &truncated * &inverse;
+            correction.truncate_to(current);
+            for coefficient in correction.0.iter_mut() {
+                *coefficient = -*coefficient;
+            }
+            if correction.0.is_empty() {
+                correction.0.push(Scalar::ZERO);
+            }
+            correction.0[0] += Scalar::from(2);
+            inverse = &inverse * &correction;
+            inverse.truncate_to(current);
+        }
+        Some(inverse)
+    }
+
+    // Reduce modulo $x^n$
+    fn truncate_to(&mut self, n: usize) {
+        self.0.truncate(n);
+        self.trim();
+    }
+
+    // Multiply by evaluating both factors over a large enough domain, multiplying pointwise and interpolating back
+    pub fn mul_fft(&self, other: &Polynomial) -> Polynomial {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial(vec![]);
+        }
+        let product_len = self.0.len() + other.0.len() - 1;
+        let domain =
+            EvaluationDomain::new(product_len).expect("product too large for an FFT domain");
+        let mut evaluations = domain.fft(&self.0);
+        for (a, b) in evaluations.iter_mut().zip(domain.fft(&other.0)) {
+            *a *= b;
+        }
+        Polynomial::new(&domain.ifft(&evaluations))
+    }
+}
+
+// Arithmetic in $F[x]/(x^n - 1)$ and $F[x]/(x^n + 1)$. In the cyclic ring $x^n = 1$, so coefficient i folds onto
+// i mod n; in the negacyclic ring $x^n = -1$, so it also flips sign every time it wraps. When n is a power of two
+// the FFT does the multiplication directly: over the n-th roots of unity the pointwise product is already reduced
+// mod $x^n - 1$, and twisting by a 2n-th root of unity $\psi$ first turns $x^n + 1$ into that case
+impl Polynomial {
+    pub fn reduce_cyclic(&self, n: usize) -> Result<Polynomial, Error> {
+        self.reduce_wrapping(n, false)
+    }
+
+    pub fn reduce_negacyclic(&self, n: usize) -> Result<Polynomial, Error> {
+        self.reduce_wrapping(n, true)
+    }
+
+    pub fn mul_cyclic(&self, other: &Polynomial, n: usize) -> Result<Polynomial, Error> {
+        self.mul_wrapping(other, n, false)
+    }
+
+    pub fn mul_negacyclic(&self, other: &Polynomial, n: usize) -> Result<Polynomial, Error> {
+        self.mul_wrapping(other, n, true)
+    }
+
+    // With n = 0 the modulus $x^0 - 1$ is zero, so both rings reject it like any other division by zero
+    fn reduce_wrapping(&self, n: usize, negate: bool) -> Result<Polynomial, Error> {
+        if n == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        let mut reduced = vec![Scalar::ZERO; n.min(self.0.len())];
+        for (i, coefficient) in self.0.iter().enumerate() {
+            if negate && (i / n) % 2 == 1 {
+                reduced[i % n] -= coefficient;
+            } else {
+                reduced[i % n] += coefficient;
+            }
+        }
+        Ok(Polynomial::new(&reduced))
+    }
+
+    fn mul_wrapping(
+        &self,
+        other: &Polynomial,
+        n: usize,
+        negate: bool,
+    ) -> Result<Polynomial, Error> {
+        let a = self.reduce_wrapping(n, negate)?;
+        let b = other.reduce_wrapping(n, negate)?;
+        if a.is_zero() || b.is_zero() {
+            return Ok(Polynomial(vec![]));
+        }
+        // Small or odd sizes just reduce the full product. The twist needs a 2n-th root of unity as well
+        if n < FAST_MULTIPLICATION_CUTOFF || !n.is_power_of_two() {
+            return (&a * &b).reduce_wrapping(n, negate);
+        }
+        let (domain, psi) = match (EvaluationDomain::new(n), EvaluationDomain::new(2 * n)) {
+            (Some(domain), Some(double)) if negate => (domain, double.generator()),
+            (Some(domain), Some(_)) => (domain, Scalar::ONE),
+            _ => return (&a * &b).reduce_wrapping(n, negate),
+        };
+
+        // Scale coefficient i by $\psi^i$ going in and $\psi^{-i}$ coming out
+        let twisted = |coefficients: &[Scalar], psi: Scalar| -> Vec<Scalar> {
+            let mut power = Scalar::ONE;
+            coefficients
+                .iter()
+                .map(|coefficient| {
+                    let scaled = *coefficient * power;
+                    power *= psi;
+                    scaled
+                })
+                .collect()
+        };
+        let mut evaluations = domain.fft(&twisted(a.coeffs(), psi));
+        for (x, y) in evaluations
+            .iter_mut()
+            .zip(domain.fft(&twisted(b.coeffs(), psi)))
+        {
+            *x *= y;
+        }
+        Ok(Polynomial::new(&twisted(
+            &domain.ifft(&evaluations),
+            psi.invert().unwrap(),
+        )))
+    }
+}
+
+// Coefficients padded with zeros to `len`, then reversed
+fn reversed(coefficients: &[Scalar], len: usize) -> Polynomial {
+    let mut padded = coefficients.to_vec();
+    padded.resize(len, Scalar::ZERO);
+    padded.reverse();
+    Polynomial::new(&padded)
+}
+
+// Panics on a zero divisor, like integer division. Use `checked_div` or `div_rem` for untrusted divisors
+impl Div for Polynomial {
+    type Output = Self;
+    fn div(self, divisor: Self) -> Self::Output {
+        self.checked_div(&divisor)
+            .expect("Dividing by zero polynomial")
+    }
+}
+
+// Schoolbook multiplication, switching to FFTs for large factors
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial(vec![]);
+        }
+        if self.0.len().min(other.0.len()) >= FAST_MULTIPLICATION_CUTOFF {
+            return self.mul_fft(other);
+        }
+        let mut product = vec![Scalar::ZERO; self.0.len() + other.0.len() - 1];
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in other.0.iter().enumerate() {
+                product[i + j] += *a * b;
+            }
         }
+        let mut product = Polynomial(product);
+        product.trim();
+        product
+    }
+}
+
+impl Mul for Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: Polynomial) -> Polynomial {
+        &self * &other
+    }
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        let len = self.0.len().max(other.0.len());
+        let sum: Vec<Scalar> = (0..len).map(|i| self.coeff(i) + other.coeff(i)).collect();
+        Polynomial::new(&sum)
+    }
+}
+
+impl Add for Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: Polynomial) -> Polynomial {
+        &self + &other
+    }
+}
+
+impl Zero for Polynomial {
+    fn zero() -> Self {
+        Polynomial(vec![])
+    }
+
+    fn is_zero(&self) -> bool {
+        Polynomial::is_zero(self)
+    }
+}
+
+impl One for Polynomial {
+    fn one() -> Self {
+        Polynomial(vec![Scalar::ONE])
+    }
+}
+
+// Coefficients lowest degree first, like `new`
+impl FromIterator<Scalar> for Polynomial {
+    fn from_iter<I: IntoIterator<Item = Scalar>>(coefficients: I) -> Self {
+        let mut polynomial = Polynomial(coefficients.into_iter().collect());
+        polynomial.trim();
+        polynomial
+    }
+}
+
+// Appends coefficients for the next degrees up. The result is trimmed, so zeros at the end of one call don't carry
+// over to the next
+impl Extend<Scalar> for Polynomial {
+    fn extend<I: IntoIterator<Item = Scalar>>(&mut self, coefficients: I) {
+        self.0.extend(coefficients);
+        self.trim();
     }
 }
 
@@ -133,7 +947,7 @@ fn evaluation_with_leading_coefficient() {
 #[test]
 fn evaluation_scalars() {
     let poly = Polynomial::new(&[Scalar::from(2), Scalar::from(4), Scalar::from(3)]);
-    log::info!("poly {:?}", poly.display());
+    log::info!("poly {}", poly);
 
     let point = Scalar::from(6_u64);
     assert_eq!(poly.evaluate(point), Scalar::from(134_u64));
@@ -142,7 +956,7 @@ fn evaluation_scalars() {
 #[test]
 fn evaluation_negative_low_degree() {
     let poly = Polynomial::new(&[-Scalar::from(2), Scalar::from(4), Scalar::from(3)]);
-    log::info!("poly {:?}", poly.display());
+    log::info!("poly {}", poly);
 
     let point = Scalar::from(6_u64);
     assert_eq!(poly.evaluate(point), Scalar::from(130_u64));
@@ -151,7 +965,7 @@ fn evaluation_negative_low_degree() {
 #[test]
 fn evaluation_negative_high_degree() {
     let poly = Polynomial::new(&[Scalar::from(2), Scalar::from(4), -Scalar::from(3)]);
-    log::info!("poly {:?}", poly.display());
+    log::info!("poly {}", poly);
 
     let point = Scalar::from(6_u64);
     assert_eq!(poly.evaluate(point), -Scalar::from(82_u64));
@@ -160,7 +974,7 @@ fn evaluation_negative_high_degree() {
 #[test]
 fn evaluation_negative_majority_degree() {
     let poly = Polynomial::new(&[Scalar::from(2), -Scalar::from(4), -Scalar::from(3)]);
-    log::info!("poly {:?}", poly.display());
+    log::info!("poly {}", poly);
 
     let point = Scalar::from(6_u64);
     assert_eq!(poly.evaluate(point), -Scalar::from(130_u64));
@@ -175,7 +989,7 @@ fn evaluation_greater_degree() {
         Scalar::from(4),
         Scalar::from(1),
     ]);
-    log::info!("poly {:?}", poly.display());
+    log::info!("poly {}", poly);
 
     let point = Scalar::from(5_u64);
     assert_eq!(poly.evaluate(point), Scalar::from(241_u64));
@@ -208,3 +1022,477 @@ fn divides_polynomials() {
     let ans: Polynomial = Polynomial::new(&[Scalar::from(2), Scalar::from(3)]);
     assert_eq!(dividend / divisor, ans)
 }
+
+#[test]
+fn compares_polynomials_in_constant_time() {
+    let a = Polynomial::new_from_bytes(&[1, 2, 3]);
+    let b = Polynomial::new(&[Scalar::from(1), Scalar::from(2), Scalar::from(3)]);
+    let c = Polynomial::new_from_bytes(&[1, 2, 4]);
+    let d = Polynomial::new_from_bytes(&[1, 2]);
+
+    assert!(bool::from(a.ct_eq(&b)));
+    assert!(!bool::from(a.ct_eq(&c)));
+    assert!(!bool::from(a.ct_eq(&d)));
+
+    // Trailing zeros don't make a different polynomial, in either order
+    let padded = Polynomial(vec![
+        Scalar::from(1),
+        Scalar::from(2),
+        Scalar::from(3),
+        Scalar::ZERO,
+    ]);
+    assert!(bool::from(a.ct_eq(&padded)));
+    assert!(bool::from(padded.ct_eq(&a)));
+    assert!(bool::from(
+        Polynomial(vec![]).ct_eq(&Polynomial(vec![Scalar::ZERO]))
+    ));
+}
+
+#[test]
+fn canonical_form_and_degree() {
+    let poly = Polynomial::new_from_bytes(&[1, 2, 3]);
+    let padded = Polynomial::new_from_bytes(&[1, 2, 3, 0, 0]);
+    assert_eq!(padded.0.len(), 3);
+    assert_eq!(poly, padded);
+    assert_eq!(poly.degree(), Some(2));
+
+    // Equality ignores trailing zeros even when they're pushed by hand
+    let mut raw = poly.clone();
+    raw.0.push(Scalar::ZERO);
+    assert_eq!(raw, poly);
+    assert_eq!(raw.degree(), Some(2));
+    raw.trim();
+    assert_eq!(raw.0.len(), 3);
+
+    let zero = Polynomial::new_from_bytes(&[0, 0]);
+    assert!(zero.0.is_empty());
+    assert_eq!(zero.degree(), None);
+    assert_eq!(Polynomial::new_from_bytes(&[5]).degree(), Some(0));
+}
+
+#[test]
+fn coefficient_accessors() {
+    let poly = Polynomial::new_from_bytes(&[1, 2, 3]);
+
+    assert_eq!(poly.len(), 3);
+    assert!(!poly.is_empty());
+    assert!(Polynomial::new(&[]).is_empty());
+    assert_eq!(poly[1], Scalar::from(2));
+    assert_eq!(poly.coeff(2), Scalar::from(3));
+    assert_eq!(poly.coeff(10), Scalar::ZERO);
+    assert_eq!(
+        poly.coeffs(),
+        &[Scalar::from(1), Scalar::from(2), Scalar::from(3)]
+    );
+
+    let sum: Scalar = poly.iter().sum();
+    assert_eq!(sum, Scalar::from(6));
+    let borrowed: Vec<Scalar> = (&poly).into_iter().copied().collect();
+    let owned: Vec<Scalar> = poly.into_iter().collect();
+    assert_eq!(borrowed, owned);
+}
+
+#[test]
+fn constructs_from_signed_integers() {
+    // x^3 -4x^2 +3x -1
+    let poly = Polynomial::new_from_i64(&[-1, 3, -4, 1]);
+    assert_eq!(
+        poly,
+        Polynomial::new(&[
+            -Scalar::from(1),
+            Scalar::from(3),
+            -Scalar::from(4),
+            Scalar::from(1),
+        ])
+    );
+    assert_eq!(poly.evaluate(Scalar::from(5_u64)), Scalar::from(39_u64));
+
+    let big = Polynomial::new_from_bigints(&[
+        BigInt::from(-1),
+        BigInt::from(3),
+        BigInt::from(-4),
+        BigInt::from(1),
+    ]);
+    assert_eq!(big, poly);
+}
+
+#[test]
+fn parses_polynomials_from_strings() {
+    let poly: Polynomial = "3x^2 + 2x - 1".parse().unwrap();
+    assert_eq!(poly, Polynomial::new_from_i64(&[-1, 2, 3]));
+
+    // Implicit coefficients, arbitrary order, repeated powers and `*`
+    let poly: Polynomial = "-x + x^3 + 4 - 2*x^3 + x".parse().unwrap();
+    assert_eq!(poly, Polynomial::new_from_i64(&[4, 0, 0, -1]));
+
+    let poly: Polynomial = "x - 5".parse().unwrap();
+    assert_eq!(poly.evaluate(Scalar::from(5)), Scalar::ZERO);
+
+    let zero: Polynomial = "x - x".parse().unwrap();
+    assert!(zero.is_zero());
+}
+
+#[test]
+fn rejects_malformed_polynomial_strings() {
+    assert_eq!("".parse::<Polynomial>(), Err(ParsePolynomialError::Empty));
+    assert_eq!("  ".parse::<Polynomial>(), Err(ParsePolynomialError::Empty));
+    for input in [
+        "3y^2",
+        "x^",
+        "2 +",
+        "--x",
+        "3x^2x",
+        "x^99999999999",
+        "*x",
+        "2 - *x^2",
+    ] {
+        assert!(
+            matches!(
+                input.parse::<Polynomial>(),
+                Err(ParsePolynomialError::InvalidTerm(_))
+            ),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn displays_polynomials() {
+    let poly = Polynomial::new_from_i64(&[-1, 2, 3]);
+    assert_eq!(poly.to_string(), "3x^2 + 2x - 1");
+    assert_eq!(poly.to_string().parse::<Polynomial>().unwrap(), poly);
+
+    assert_eq!(
+        Polynomial::new_from_i64(&[0, -1, 0, 1]).to_string(),
+        "x^3 - x"
+    );
+    assert_eq!(Polynomial::new_from_i64(&[1, 1]).to_string(), "x + 1");
+    assert_eq!(Polynomial::new_from_i64(&[-7]).to_string(), "-7");
+    assert_eq!(Polynomial::new(&[]).to_string(), "0");
+
+    let large = Polynomial::new(&[Scalar::from(u64::MAX) + Scalar::ONE]);
+    assert_eq!(large.to_string(), "18446744073709551616");
+}
+
+#[test]
+fn display_round_trips_through_the_parser() {
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    for degree in [0, 1, 5, 20] {
+        let poly = Polynomial::rand(degree, &mut rng);
+        assert_eq!(poly.to_string().parse::<Polynomial>().unwrap(), poly);
+    }
+}
+
+#[test]
+fn round_trips_through_bytes() {
+    let poly = Polynomial::new_from_i64(&[-1, 2, 3]);
+    let bytes = poly.to_bytes();
+    assert_eq!(bytes.len(), 4 + 3 * 32);
+    assert_eq!(Polynomial::from_bytes(&bytes), Ok(poly));
+
+    let zero = Polynomial::new(&[]);
+    assert_eq!(zero.to_bytes(), vec![0, 0, 0, 0]);
+    assert_eq!(Polynomial::from_bytes(&zero.to_bytes()), Ok(zero));
+}
+
+#[test]
+fn rejects_malformed_polynomial_bytes() {
+    let bytes = Polynomial::new_from_bytes(&[1, 2]).to_bytes();
+
+    // Truncated, too long, or a length prefix that doesn't match
+    assert!(matches!(
+        Polynomial::from_bytes(&bytes[..3]),
+        Err(Error::InvalidEncoding(_))
+    ));
+    assert!(matches!(
+        Polynomial::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(Error::InvalidEncoding(_))
+    ));
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert!(matches!(
+        Polynomial::from_bytes(&extended),
+        Err(Error::InvalidEncoding(_))
+    ));
+
+    // A coefficient that isn't reduced mod r
+    let mut unreduced = bytes.clone();
+    unreduced[4..36].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        Polynomial::from_bytes(&unreduced),
+        Err(Error::InvalidScalar { index: 0 })
+    );
+
+    // A trailing zero coefficient
+    let mut trailing_zero = bytes.clone();
+    trailing_zero[0] = 3;
+    trailing_zero.extend_from_slice(&[0; 32]);
+    assert!(matches!(
+        Polynomial::from_bytes(&trailing_zero),
+        Err(Error::InvalidEncoding(_))
+    ));
+}
+
+#[test]
+fn multiplies_polynomials() {
+    // (x + 1)(2x + 3) = 2x^2 + 5x + 3
+    let product = Polynomial::new_from_i64(&[1, 1]) * Polynomial::new_from_i64(&[3, 2]);
+    assert_eq!(product, Polynomial::new_from_i64(&[3, 5, 2]));
+    assert!((&product * &Polynomial::new(&[])).is_zero());
+}
+
+#[test]
+fn generates_random_polynomials() {
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let poly = Polynomial::rand(7, &mut rng);
+    assert_eq!(poly.degree(), Some(7));
+    assert_ne!(poly, Polynomial::rand(7, &mut rng));
+    assert_eq!(Polynomial::rand(0, &mut rng).degree(), Some(0));
+
+    let roots = [Scalar::from(2), Scalar::from(5), -Scalar::from(3)];
+    assert_eq!(
+        Polynomial::vanishing(&roots),
+        "x^3 - 4x^2 - 11x + 30".parse::<Polynomial>().unwrap()
+    );
+    let with_roots = Polynomial::rand_with_roots(&roots, &mut rng);
+    assert_eq!(with_roots.degree(), Some(3));
+    for root in roots {
+        assert_eq!(with_roots.evaluate(root), Scalar::ZERO);
+    }
+    assert_ne!(with_roots.evaluate(Scalar::from(1)), Scalar::ZERO);
+}
+
+#[test]
+fn divides_by_structured_linear_factors() {
+    let polynomial = Polynomial::new_from_i64(&[-1, 3, -4, 1]);
+    for point in [Scalar::ZERO, Scalar::ONE, -Scalar::ONE] {
+        let (quotient, remainder) = polynomial.divide_by_structured_linear(point).unwrap();
+        assert_eq!(remainder, polynomial.evaluate(point));
+        let mut dividend = polynomial.clone();
+        dividend.0[0] -= remainder;
+        assert_eq!(quotient, dividend / Polynomial::new(&[-point, Scalar::ONE]));
+    }
+    assert!(polynomial
+        .divide_by_structured_linear(Scalar::from(2))
+        .is_none());
+}
+
+#[test]
+fn divides_with_remainder() {
+    // 2x^2 + 5x + 4 = (x + 1)(2x + 3) + 1
+    let (quotient, remainder) = Polynomial::new_from_i64(&[4, 5, 2])
+        .long_division(Polynomial::new_from_i64(&[1, 1]))
+        .unwrap();
+    assert_eq!(quotient, Polynomial::new_from_i64(&[3, 2]));
+    assert_eq!(remainder, Polynomial::new_from_i64(&[1]));
+}
+
+#[test]
+fn division_by_zero_errs_instead_of_panicking() {
+    let dividend = Polynomial::new_from_i64(&[4, 5, 2]);
+    let zero = Polynomial::new(&[]);
+    assert_eq!(dividend.div_rem(&zero), Err(Error::DivisionByZero));
+    assert_eq!(dividend.checked_div(&zero), Err(Error::DivisionByZero));
+    assert_eq!(dividend.fast_division(&zero), Err(Error::DivisionByZero));
+    assert_eq!(
+        dividend.checked_div(&Polynomial::new_from_i64(&[1, 1])),
+        Ok(Polynomial::new_from_i64(&[3, 2]))
+    );
+}
+
+#[test]
+fn evaluates_at_many_points() {
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let poly = Polynomial::rand(40, &mut rng);
+    let points: Vec<Scalar> = (0..100).map(|_| Scalar::random(&mut rng)).collect();
+
+    let expected: Vec<Scalar> = points.iter().map(|p| poly.evaluate(*p)).collect();
+    assert_eq!(poly.evaluate_many(&points), expected);
+    assert_eq!(poly.evaluate_many(&points[..3]), expected[..3]);
+    assert!(poly.evaluate_many(&[]).is_empty());
+}
+
+#[test]
+fn differentiates() {
+    // (x^3 - 4x^2 + 3x - 1)' = 3x^2 - 8x + 3
+    assert_eq!(
+        Polynomial::new_from_i64(&[-1, 3, -4, 1]).derivative(),
+        Polynomial::new_from_i64(&[3, -8, 3])
+    );
+    assert!(Polynomial::new_from_i64(&[5]).derivative().is_zero());
+}
+
+#[test]
+fn streaming_evaluation_matches_horner() {
+    let poly = Polynomial::new_from_i64(&[-1, 3, -4, 1]);
+    let point = Scalar::from(5_u64);
+    assert_eq!(
+        Polynomial::evaluate_streaming(poly.iter().copied(), point),
+        poly.evaluate(point)
+    );
+    assert_eq!(Polynomial::new(&[]).evaluate(point), Scalar::ZERO);
+}
+
+#[test]
+fn evaluates_batches_at_one_point() {
+    let polynomials = [
+        Polynomial::new_from_i64(&[-1, 3, -4, 1]),
+        Polynomial::new_from_bytes(&[1, 2, 3]),
+        Polynomial::new(&[]),
+        Polynomial::new_from_bytes(&[9]),
+    ];
+    let point = Scalar::from(5_u64);
+    let expected: Vec<Scalar> = polynomials.iter().map(|p| p.evaluate(point)).collect();
+    assert_eq!(Polynomial::evaluate_batch(&polynomials, point), expected);
+    assert!(Polynomial::evaluate_batch(&[], point).is_empty());
+}
+
+#[test]
+fn fast_division_matches_long_division() {
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    for (dividend_degree, divisor_degree) in [(200, 100), (150, 149), (99, 0), (70, 90)] {
+        let dividend = Polynomial::rand(dividend_degree, &mut rng);
+        let divisor = Polynomial::rand(divisor_degree, &mut rng);
+
+        let (quotient, remainder) = dividend.fast_division(&divisor).unwrap();
+        assert_eq!(
+            (quotient.clone(), remainder.clone()),
+            dividend.clone().long_division(divisor.clone()).unwrap()
+        );
+        assert!(remainder.len() < divisor.len());
+        assert_eq!(dividend / divisor, quotient);
+    }
+}
+
+#[test]
+fn fft_multiplication_matches_schoolbook() {
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let a = Polynomial::rand(100, &mut rng);
+    let b = Polynomial::rand(80, &mut rng);
+    let point = Scalar::from(11);
+
+    let product = a.mul_fft(&b);
+    assert_eq!(product.degree(), Some(180));
+    assert_eq!(
+        product.evaluate(point),
+        a.evaluate(point) * b.evaluate(point)
+    );
+    assert_eq!(&a * &b, product);
+}
+
+#[test]
+fn composes_with_iterators_and_numeric_traits() {
+    let polynomial: Polynomial = (1..=3u64).map(Scalar::from).collect();
+    assert_eq!(polynomial, Polynomial::new_from_i64(&[1, 2, 3]));
+
+    let mut extended = polynomial.clone();
+    extended.extend([Scalar::ZERO, Scalar::from(4)]);
+    assert_eq!(extended, Polynomial::new_from_i64(&[1, 2, 3, 0, 4]));
+    extended.extend([Scalar::ZERO]);
+    assert_eq!(extended.len(), 5);
+
+    assert_eq!(
+        &polynomial + &Polynomial::new_from_i64(&[-1, 0, -3, 5]),
+        Polynomial::new_from_i64(&[0, 2, 0, 5])
+    );
+    assert!(Zero::is_zero(
+        &(polynomial.clone() + Polynomial::new_from_i64(&[-1, -2, -3]))
+    ));
+    assert_eq!(&polynomial * &Polynomial::one(), polynomial);
+    assert_eq!(polynomial.clone() + Polynomial::zero(), polynomial);
+}
+
+#[test]
+fn multiplies_modulo_x_n_plus_and_minus_one() {
+    // (1 + 2x + 3x^2)(4 + 5x) = 4 + 13x + 22x^2 + 15x^3, and x^3 wraps to the constant term
+    let a = Polynomial::new_from_i64(&[1, 2, 3]);
+    let b = Polynomial::new_from_i64(&[4, 5]);
+    assert_eq!(
+        a.mul_cyclic(&b, 3).unwrap(),
+        Polynomial::new_from_i64(&[19, 13, 22])
+    );
+    assert_eq!(
+        a.mul_negacyclic(&b, 3).unwrap(),
+        Polynomial::new_from_i64(&[-11, 13, 22])
+    );
+    assert_eq!(
+        Polynomial::new_from_i64(&[1, 2, 3, 4, 5])
+            .reduce_negacyclic(2)
+            .unwrap(),
+        Polynomial::new_from_i64(&[1 - 3 + 5, 2 - 4])
+    );
+    assert_eq!(a.reduce_cyclic(0), Err(Error::DivisionByZero));
+
+    // The FFT paths agree with reducing the full product
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let a = Polynomial::rand(150, &mut rng);
+    let b = Polynomial::rand(100, &mut rng);
+    let product = &a * &b;
+    for n in [64, 128, 100] {
+        assert_eq!(
+            a.mul_cyclic(&b, n).unwrap(),
+            product.reduce_cyclic(n).unwrap()
+        );
+        assert_eq!(
+            a.mul_negacyclic(&b, n).unwrap(),
+            product.reduce_negacyclic(n).unwrap()
+        );
+    }
+}
+
+#[test]
+fn divides_by_any_linear_factor() {
+    let polynomial = Polynomial::new_from_i64(&[4, -3, 0, 7, 2]);
+    for point in [Scalar::ZERO, Scalar::from(5), -Scalar::from(9)] {
+        let (quotient, remainder) = polynomial.divide_by_linear(point);
+        let (expected, _) = polynomial
+            .div_rem(&Polynomial::new(&[-point, Scalar::ONE]))
+            .unwrap();
+        assert_eq!(quotient, expected);
+        assert_eq!(remainder, polynomial.evaluate(point));
+    }
+    assert_eq!(
+        Polynomial::new(&[]).divide_by_linear(Scalar::from(3)),
+        (Polynomial::new(&[]), Scalar::ZERO)
+    );
+}
+
+#[test]
+fn truncating_keeps_the_requested_degree() {
+    use crate::rng::test_rng;
+
+    // Coefficient 2 is zero, so the first three coefficients alone have degree 1
+    let polynomial = Polynomial::new_from_i64(&[1, 2, 0, 4, 5]);
+    let truncated = polynomial.adjusted_to_degree_with_rng(2, &mut test_rng());
+    assert_eq!(truncated.degree(), Some(2));
+    assert_eq!(&truncated.coeffs()[..2], &polynomial.coeffs()[..2]);
+}
+
+#[test]
+fn adjusted_to_degree_leaves_the_original_alone() {
+    use crate::rng::test_rng;
+
+    let polynomial = Polynomial::new_from_i64(&[1, 2, 3]);
+    let padded = polynomial.adjusted_to_degree_with_rng(6, &mut test_rng());
+    assert_eq!(padded.len(), 7);
+    assert_eq!(padded.degree(), Some(6));
+    assert_eq!(&padded.coeffs()[..3], polynomial.coeffs());
+    let truncated = polynomial.adjusted_to_degree(1);
+    assert_eq!(truncated.degree(), Some(1));
+    assert_eq!(truncated, Polynomial::new_from_i64(&[1, 2]));
+    assert_eq!(polynomial.adjusted_to_degree(2).degree(), Some(2));
+    assert_eq!(polynomial, Polynomial::new_from_i64(&[1, 2, 3]));
+}
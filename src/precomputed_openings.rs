@@ -0,0 +1,308 @@
+use crate::domain::EvaluationDomain;
+use crate::error::Error;
+use crate::parallel::map_indexed;
+use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+use crate::polynomials::Polynomial;
+use crate::progress::{NoProgress, Operation, Progress};
+use crate::toeplitz::ToeplitzMatrix;
+use blstrs::{G1Affine, G1Projective, Scalar};
+use group::ff::Field;
+use group::{Curve, Group};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+// Opening proofs for a fixed set of frequently queried points, computed once at commit time and then served by
+// lookup. When the points are exactly the n-th roots of unity, all n openings come from one Toeplitz product and
+// one FFT (FK20), in $O(d \log d + n \log n)$ group operations for degree d. Any other set of points costs one
+// division and MSM per point
+#[derive(Clone, Debug)]
+pub struct PrecomputedOpenings {
+    commitment: G1Projective,
+    // Keyed by the point's canonical bytes, since `Scalar` isn't `Hash`
+    openings: HashMap<[u8; 32], (G1Projective, Scalar)>,
+}
+
+// Serialized size of one entry: the point, the compressed witness and the evaluation
+const ENTRY_SIZE: usize = 32 + 48 + 32;
+
+impl PrecomputedOpenings {
+    pub fn precompute(
+        committer: &GenericPolynomialCommitment,
+        polynomial: &Polynomial,
+        points: &[Scalar],
+    ) -> Result<Self, Error> {
+        PrecomputedOpenings::precompute_with_progress(committer, polynomial, points, &NoProgress)
+    }
+
+    // Openings may finish out of order on the thread pool, so this reports how many are done, not which. The count is
+    // bumped and reported under one lock, so reports from different threads still arrive in increasing order
+    pub fn precompute_with_progress(
+        committer: &GenericPolynomialCommitment,
+        polynomial: &Polynomial,
+        points: &[Scalar],
+        progress: &dyn Progress,
+    ) -> Result<Self, Error> {
+        let commitment = committer.commit(polynomial)?;
+        if let Some(domain) = roots_of_unity(points) {
+            let openings = fk20(committer, polynomial, &domain)?;
+            progress.report(
+                Operation::PrecomputeOpenings,
+                points.len() as u64,
+                points.len() as u64,
+            );
+            return Ok(PrecomputedOpenings {
+                commitment,
+                openings: domain
+                    .elements()
+                    .iter()
+                    .map(|point| point.to_bytes_le())
+                    .zip(openings)
+                    .collect(),
+            });
+        }
+        let done = Mutex::new(0);
+        let computed = map_indexed(points, |_, point| {
+            let opening = committer
+                .create_witness(polynomial, *point)
+                .map(|opening| (point.to_bytes_le(), opening));
+            let mut done = done.lock().unwrap();
+            *done += 1;
+            progress.report(Operation::PrecomputeOpenings, *done, points.len() as u64);
+            opening
+        });
+        let openings = computed.into_iter().collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(PrecomputedOpenings {
+            commitment,
+            openings,
+        })
+    }
+
+    pub fn commitment(&self) -> G1Projective {
+        self.commitment
+    }
+
+    pub fn len(&self) -> usize {
+        self.openings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.openings.is_empty()
+    }
+
+    // The witness and evaluation at `point`, or `None` if it wasn't precomputed
+    pub fn get(&self, point: &Scalar) -> Option<(G1Projective, Scalar)> {
+        self.openings.get(&point.to_bytes_le()).copied()
+    }
+
+    // The compressed commitment, the number of entries as a little-endian u32, then each entry as the point, the
+    // compressed witness and the evaluation. Entries are sorted by point so the encoding is deterministic
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut entries: Vec<_> = self.openings.iter().collect();
+        entries.sort_unstable_by_key(|(point, _)| *point);
+
+        let mut bytes = Vec::with_capacity(48 + 4 + entries.len() * ENTRY_SIZE);
+        bytes.extend_from_slice(&self.commitment.to_affine().to_compressed());
+        bytes.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+        for (point, (witness, evaluation)) in entries {
+            bytes.extend_from_slice(point);
+            bytes.extend_from_slice(&witness.to_affine().to_compressed());
+            bytes.extend_from_slice(&evaluation.to_bytes_le());
+        }
+        bytes
+    }
+
+    // Decoding doesn't check the openings against the commitment; run them through `verify_evaluation` if the bytes
+    // came from somewhere untrusted
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 52 {
+            return Err(Error::InvalidEncoding("too short for precomputed openings"));
+        }
+        let decode_g1 = |chunk: &[u8], index| {
+            Option::<G1Affine>::from(G1Affine::from_compressed(chunk.try_into().unwrap()))
+                .map(G1Projective::from)
+                .ok_or(Error::InvalidPoint { group: "G1", index })
+        };
+        let decode_scalar = |chunk: &[u8], index| {
+            Option::<Scalar>::from(Scalar::from_bytes_le(chunk.try_into().unwrap()))
+                .ok_or(Error::InvalidScalar { index })
+        };
+
+        let commitment = decode_g1(&bytes[..48], 0)?;
+        let count = u32::from_le_bytes(bytes[48..52].try_into().unwrap()) as usize;
+        let data = &bytes[52..];
+        if data.len() != count * ENTRY_SIZE {
+            return Err(Error::InvalidEncoding(
+                "entry count doesn't match the data length",
+            ));
+        }
+        let mut openings = HashMap::with_capacity(count);
+        for (index, entry) in data.chunks_exact(ENTRY_SIZE).enumerate() {
+            let point = decode_scalar(&entry[..32], index)?;
+            let witness = decode_g1(&entry[32..80], index + 1)?;
+            let evaluation = decode_scalar(&entry[80..], index)?;
+            openings.insert(point.to_bytes_le(), (witness, evaluation));
+        }
+        Ok(PrecomputedOpenings {
+            commitment,
+            openings,
+        })
+    }
+}
+
+// The domain whose elements are exactly `points`, in any order
+fn roots_of_unity(points: &[Scalar]) -> Option<EvaluationDomain> {
+    let domain = EvaluationDomain::new(points.len())?;
+    if domain.size() != points.len() {
+        return None;
+    }
+    let requested: HashSet<[u8; 32]> = points.iter().map(|point| point.to_bytes_le()).collect();
+    let elements: HashSet<[u8; 32]> = domain
+        .elements()
+        .iter()
+        .map(|point| point.to_bytes_le())
+        .collect();
+    (requested == elements).then_some(domain)
+}
+
+// The openings at every element of the domain, in order, with the same results and errors as `create_witness`. For
+// $f$ of degree d the witness at z commits to $\sum_i h_i z^i$ with $h_i = \sum_k f_{i+1+k} [\tau^k]_1$. The $h_i$
+// don't depend on z: they're the Toeplitz matrix with first row $f_d, \ldots, f_1$ and $f_d$ on the diagonal times
+// $[\tau^{d-1}]_1, \ldots, [\tau^0]_1$. Evaluating that at every $\omega^j$ is an FFT over G1, after folding the
+// $h_i$ mod n when the domain is smaller than the degree
+fn fk20(
+    committer: &GenericPolynomialCommitment,
+    polynomial: &Polynomial,
+    domain: &EvaluationDomain,
+) -> Result<Vec<(G1Projective, Scalar)>, Error> {
+    let gs = committer.global_parameters()?.gs();
+    let degree = polynomial.degree().ok_or(Error::ZeroPolynomial)?;
+    if degree == 0 {
+        return Err(Error::IdentityWitness);
+    }
+    if degree >= gs.len() {
+        return Err(Error::DegreeTooLarge {
+            max: gs.len() - 1,
+            actual: degree,
+        });
+    }
+    let coefficients = &polynomial.coeffs()[..=degree];
+
+    let mut column = vec![Scalar::ZERO; degree];
+    column[0] = coefficients[degree];
+    let row = coefficients[1..].iter().rev().copied().collect();
+    // Can unwrap since both have length d and start with $f_d$
+    let matrix = ToeplitzMatrix::new(column, row).unwrap();
+    let powers: Vec<G1Projective> = gs[..degree].iter().rev().copied().collect();
+    let quotient_coefficients = matrix.mul_points(&powers)?;
+
+    let n = domain.size();
+    let mut folded_quotient = vec![G1Projective::identity(); n];
+    for (i, point) in quotient_coefficients.iter().enumerate() {
+        folded_quotient[i % n] += point;
+    }
+    let mut folded_polynomial = vec![Scalar::ZERO; n];
+    for (i, coefficient) in coefficients.iter().enumerate() {
+        folded_polynomial[i % n] += coefficient;
+    }
+
+    let witnesses = domain.fft_g1(&folded_quotient);
+    if witnesses
+        .iter()
+        .any(|witness| bool::from(witness.is_identity()))
+    {
+        return Err(Error::IdentityWitness);
+    }
+    Ok(witnesses
+        .into_iter()
+        .zip(domain.fft(&folded_polynomial))
+        .collect())
+}
+
+#[test]
+fn serves_precomputed_openings() {
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(4, &mut test_rng());
+    let polynomial = Polynomial::new_from_i64(&[3, -1, 4, 1, -5]);
+    let points: Vec<Scalar> = (1..=4).map(Scalar::from).collect();
+
+    let precomputed = PrecomputedOpenings::precompute(&committer, &polynomial, &points).unwrap();
+    assert_eq!(precomputed.len(), 4);
+    for point in &points {
+        let (witness, evaluation) = precomputed.get(point).unwrap();
+        assert_eq!(evaluation, polynomial.evaluate(*point));
+        assert_eq!(
+            committer.verify_evaluation(precomputed.commitment(), *point, evaluation, witness),
+            Ok(true)
+        );
+    }
+    assert!(precomputed.get(&Scalar::from(9)).is_none());
+
+    let bytes = precomputed.to_bytes();
+    let decoded = PrecomputedOpenings::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.to_bytes(), bytes);
+    assert_eq!(decoded.get(&points[2]), precomputed.get(&points[2]));
+    assert!(PrecomputedOpenings::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn reports_precomputation_progress() {
+    use crate::rng::test_rng;
+    use std::sync::Mutex;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(3, &mut test_rng());
+    let polynomial = Polynomial::new_from_i64(&[1, 2, 3, 4]);
+    let points: Vec<Scalar> = (1..=5).map(Scalar::from).collect();
+
+    let reports = Mutex::new(vec![]);
+    let record = |operation: Operation, done: u64, total: u64| {
+        assert_eq!((operation, total), (Operation::PrecomputeOpenings, 5));
+        reports.lock().unwrap().push(done);
+    };
+    PrecomputedOpenings::precompute_with_progress(&committer, &polynomial, &points, &record)
+        .unwrap();
+    // In order as reported, even with the openings spread over threads
+    assert_eq!(reports.into_inner().unwrap(), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn precomputes_roots_of_unity_like_single_openings() {
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(6, &mut rng);
+    let polynomial = Polynomial::new_from_i64(&[3, -1, 4, 1, -5, 9, 2]);
+
+    // Domains smaller than, equal to and larger than the number of coefficients
+    for n in [1, 2, 4, 8, 16] {
+        let domain = EvaluationDomain::new(n).unwrap();
+        let mut points = domain.elements();
+        points.reverse();
+        assert!(roots_of_unity(&points).is_some());
+
+        let precomputed =
+            PrecomputedOpenings::precompute(&committer, &polynomial, &points).unwrap();
+        assert_eq!(precomputed.len(), n);
+        for point in &points {
+            assert_eq!(
+                precomputed.get(point),
+                Some(committer.create_witness(&polynomial, *point).unwrap())
+            );
+        }
+    }
+
+    // Three of the four roots, or a duplicate in place of one, take the per-point path
+    let elements = EvaluationDomain::new(4).unwrap().elements();
+    assert!(roots_of_unity(&elements[..3]).is_none());
+    assert!(roots_of_unity(&[elements[0], elements[1], elements[2], elements[2]]).is_none());
+    assert!(roots_of_unity(&[]).is_none());
+
+    let constant = Polynomial::new_from_i64(&[7]);
+    assert_eq!(
+        fk20(&committer, &constant, &EvaluationDomain::new(4).unwrap()),
+        Err(Error::IdentityWitness)
+    );
+}
@@ -0,0 +1,47 @@
+// The types and traits most callers need, in one import: `use polynomial_commitments_scratch::prelude::*`. The curve
+// types are re-exported too, under the names KZG papers use, so a hello-world doesn't need its own blstrs dependency
+pub use crate::batch::BatchItem;
+pub use crate::degree::MaxDegree;
+pub use crate::error::{Error, VerifyError};
+pub use crate::types::{Commitment, FieldElement, Proof};
+pub use crate::verifier_key::VerifierKey;
+pub use blstrs::{G1Projective as G1, G2Projective as G2, Scalar as Fr};
+pub use blstrs::{G1Projective, G2Projective, Scalar};
+pub use group::ff::{Field, PrimeField};
+pub use group::{Curve, Group};
+
+#[cfg(feature = "prover")]
+pub use crate::polynomial_commitments::{
+    GenericPolynomialCommitment, GlobalParameters, PolynomialCommitment,
+};
+#[cfg(feature = "prover")]
+pub use crate::polynomials::Polynomial;
+
+#[cfg(feature = "prover")]
+#[test]
+fn hello_world_needs_only_the_prelude() {
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(2, &mut test_rng());
+    let polynomial = Polynomial::from_field_elements(&[
+        FieldElement::from(1),
+        FieldElement::from(2),
+        FieldElement::from(3),
+    ]);
+    let commitment = committer.commit_opaque(&polynomial).unwrap();
+    let point = FieldElement::from(5);
+    let (proof, evaluation) = committer.prove(&polynomial, point).unwrap();
+
+    let verifier_key: VerifierKey = committer
+        .global_parameters()
+        .unwrap()
+        .verifier_key()
+        .unwrap();
+    assert_eq!(
+        verifier_key.verify_evaluation(&commitment, point, evaluation, &proof),
+        Ok(true)
+    );
+    assert_eq!(Fr::ONE + Fr::ONE, Scalar::from(2));
+    assert_eq!(G1::generator(), G1Projective::generator());
+}
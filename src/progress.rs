@@ -0,0 +1,114 @@
+use crate::error::Error;
+use crate::polynomial_commitments::GlobalParameters;
+use blstrs::{G1Projective, Scalar};
+use group::Group;
+
+// Points per partial MSM when an MSM is split up to report progress or check for cancellation. Large enough that the
+// split costs little over one big MSM
+pub(crate) const MSM_CHUNK_SIZE: usize = 1 << 14;
+
+// The long-running operations that report progress
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    // Units are powers of tau generated, over both groups
+    Setup,
+    // Units are points
+    Msm,
+    // Units are openings
+    PrecomputeOpenings,
+    // Units are bytes of the file
+    FileCommitment,
+}
+
+// Told how far along an operation is, so CLIs and services can show progress for work that takes minutes. `done`
+// never decreases and reaches `total` when the operation finishes. Reports may come from several threads at once
+pub trait Progress: Sync {
+    fn report(&self, operation: Operation, done: u64, total: u64);
+}
+
+impl<F: Fn(Operation, u64, u64) + Sync> Progress for F {
+    fn report(&self, operation: Operation, done: u64, total: u64) {
+        self(operation, done, total)
+    }
+}
+
+// What the operations without a `_with_progress` suffix use
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn report(&self, _operation: Operation, _done: u64, _total: u64) {}
+}
+
+impl GlobalParameters {
+    // `commit_in_g1`, as a sequence of partial MSMs with a report after each
+    pub fn commit_in_g1_with_progress(
+        &self,
+        coefficients: &[Scalar],
+        progress: &dyn Progress,
+    ) -> Result<G1Projective, Error> {
+        if coefficients.len() > self.gs().len() {
+            return Err(Error::DegreeTooLarge {
+                max: self.gs().len() - 1,
+                actual: coefficients.len() - 1,
+            });
+        }
+        let total = coefficients.len() as u64;
+        let mut result = G1Projective::identity();
+        let mut done = 0;
+        for (bases, scalars) in self
+            .gs()
+            .chunks(MSM_CHUNK_SIZE)
+            .zip(coefficients.chunks(MSM_CHUNK_SIZE))
+        {
+            result += G1Projective::multi_exp(&bases[..scalars.len()], scalars);
+            done += scalars.len() as u64;
+            progress.report(Operation::Msm, done, total);
+        }
+        Ok(result)
+    }
+}
+
+#[test]
+fn reports_msm_progress() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::rng::test_rng;
+    use group::ff::Field;
+    use std::sync::Mutex;
+
+    let gp = GenericPolynomialCommitment::new().setup_with_rng(7, &mut test_rng());
+    let coefficients: Vec<Scalar> = (0..8).map(|_| Scalar::random(&mut test_rng())).collect();
+
+    let reports = Mutex::new(vec![]);
+    let record = |operation: Operation, done: u64, total: u64| {
+        reports.lock().unwrap().push((operation, done, total))
+    };
+    assert_eq!(
+        gp.commit_in_g1_with_progress(&coefficients, &record)
+            .unwrap(),
+        gp.commit_in_g1(&coefficients).unwrap()
+    );
+    assert_eq!(reports.into_inner().unwrap(), vec![(Operation::Msm, 8, 8)]);
+}
+
+#[test]
+fn reports_setup_progress() {
+    use crate::generators::Generators;
+    use crate::polynomial_commitments::GenericPolynomialCommitment;
+    use crate::rng::test_rng;
+    use std::sync::Mutex;
+
+    let last = Mutex::new((0, 0));
+    let record = |operation: Operation, done: u64, total: u64| {
+        assert_eq!(operation, Operation::Setup);
+        let mut last = last.lock().unwrap();
+        assert!(done >= last.0);
+        *last = (done, total);
+    };
+    GenericPolynomialCommitment::new().setup_with_progress(
+        4,
+        Generators::standard(),
+        &mut test_rng(),
+        &record,
+    );
+    assert_eq!(last.into_inner().unwrap(), (10, 10));
+}
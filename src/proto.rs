@@ -0,0 +1,212 @@
+use crate::batch::BatchItem;
+use crate::degree::MaxDegree;
+use crate::error::Error;
+use crate::types::{Commitment, FieldElement, Proof, FIELD_ELEMENT_SIZE, PROOF_SIZE};
+use prost::Message;
+
+// The messages of proto/kzg.proto, written out with prost's derive rather than generated, so building doesn't need
+// protoc. Decoding checks everything `Commitment::from_bytes`, `Proof::from_bytes` and `FieldElement::from_bytes`
+// check. Bundles report the index of the opening a bad field belongs to
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommitmentMessage {
+    #[prost(bytes = "vec", tag = "1")]
+    pub point: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub max_degree: u64,
+    #[prost(uint64, optional, tag = "3")]
+    pub domain_size: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProofMessage {
+    #[prost(bytes = "vec", tag = "1")]
+    pub witness: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct OpeningMessage {
+    #[prost(message, optional, tag = "1")]
+    pub commitment: Option<CommitmentMessage>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub point: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub evaluation: Vec<u8>,
+    #[prost(message, optional, tag = "4")]
+    pub proof: Option<ProofMessage>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProofBundleMessage {
+    #[prost(message, repeated, tag = "1")]
+    pub openings: Vec<OpeningMessage>,
+}
+
+impl From<&Commitment> for CommitmentMessage {
+    fn from(commitment: &Commitment) -> Self {
+        CommitmentMessage {
+            point: commitment.point_bytes().to_vec(),
+            max_degree: commitment.max_degree().get() as u64,
+            domain_size: commitment.domain_size().map(|size| size as u64),
+        }
+    }
+}
+
+impl TryFrom<&CommitmentMessage> for Commitment {
+    type Error = Error;
+
+    fn try_from(message: &CommitmentMessage) -> Result<Self, Error> {
+        let point: &[u8; 48] = message.point.as_slice().try_into()?;
+        let commitment =
+            Commitment::from_point_bytes(point, MaxDegree::new(message.max_degree as usize))?;
+        match message.domain_size {
+            None => Ok(commitment),
+            // `from_point_bytes` has no domain, so go through the full encoding to attach one
+            Some(domain_size) => {
+                let mut bytes = commitment.to_bytes();
+                bytes[56..].copy_from_slice(&domain_size.to_le_bytes());
+                Commitment::from_bytes(&bytes)
+            }
+        }
+    }
+}
+
+impl From<&Proof> for ProofMessage {
+    fn from(proof: &Proof) -> Self {
+        ProofMessage {
+            witness: proof.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<&ProofMessage> for Proof {
+    type Error = Error;
+
+    fn try_from(message: &ProofMessage) -> Result<Self, Error> {
+        let witness: &[u8; PROOF_SIZE] = message.witness.as_slice().try_into()?;
+        Proof::from_bytes(witness)
+    }
+}
+
+impl From<&BatchItem> for OpeningMessage {
+    fn from(item: &BatchItem) -> Self {
+        OpeningMessage {
+            commitment: Some((&item.commitment).into()),
+            point: item.point.to_bytes().to_vec(),
+            evaluation: item.evaluation.to_bytes().to_vec(),
+            proof: Some((&item.proof).into()),
+        }
+    }
+}
+
+fn field_element(bytes: &[u8], index: usize) -> Result<FieldElement, Error> {
+    let bytes: &[u8; FIELD_ELEMENT_SIZE] = bytes.try_into()?;
+    FieldElement::from_bytes(bytes).map_err(|_| Error::InvalidScalar { index })
+}
+
+// Points and scalars carry the index of the opening they came from
+fn opening(message: &OpeningMessage, index: usize) -> Result<BatchItem, Error> {
+    let at_index = |error| match error {
+        Error::InvalidPoint { group, .. } => Error::InvalidPoint { group, index },
+        error => error,
+    };
+    let commitment = message
+        .commitment
+        .as_ref()
+        .ok_or(Error::InvalidEncoding("opening has no commitment"))?;
+    let proof = message
+        .proof
+        .as_ref()
+        .ok_or(Error::InvalidEncoding("opening has no proof"))?;
+    Ok(BatchItem {
+        commitment: Commitment::try_from(commitment).map_err(at_index)?,
+        point: field_element(&message.point, index)?,
+        evaluation: field_element(&message.evaluation, index)?,
+        proof: Proof::try_from(proof).map_err(at_index)?,
+    })
+}
+
+fn decode<M: Message + Default>(bytes: &[u8]) -> Result<M, Error> {
+    M::decode(bytes).map_err(|_| Error::InvalidEncoding("malformed protobuf message"))
+}
+
+pub fn commitment_to_proto(commitment: &Commitment) -> Vec<u8> {
+    CommitmentMessage::from(commitment).encode_to_vec()
+}
+
+pub fn commitment_from_proto(bytes: &[u8]) -> Result<Commitment, Error> {
+    Commitment::try_from(&decode::<CommitmentMessage>(bytes)?)
+}
+
+pub fn proof_to_proto(proof: &Proof) -> Vec<u8> {
+    ProofMessage::from(proof).encode_to_vec()
+}
+
+pub fn proof_from_proto(bytes: &[u8]) -> Result<Proof, Error> {
+    Proof::try_from(&decode::<ProofMessage>(bytes)?)
+}
+
+pub fn bundle_to_proto(items: &[BatchItem]) -> Vec<u8> {
+    ProofBundleMessage {
+        openings: items.iter().map(OpeningMessage::from).collect(),
+    }
+    .encode_to_vec()
+}
+
+pub fn bundle_from_proto(bytes: &[u8]) -> Result<Vec<BatchItem>, Error> {
+    decode::<ProofBundleMessage>(bytes)?
+        .openings
+        .iter()
+        .enumerate()
+        .map(|(index, message)| opening(message, index))
+        .collect()
+}
+
+#[test]
+fn round_trips_through_protobuf() {
+    use blstrs::G1Affine;
+
+    let generator = G1Affine::generator().to_compressed();
+    let commitment = Commitment::from_point_bytes(&generator, MaxDegree::new(3)).unwrap();
+    let mut bytes = commitment.to_bytes();
+    bytes[56..].copy_from_slice(&8u64.to_le_bytes());
+    let on_domain = Commitment::from_bytes(&bytes).unwrap();
+    let proof = Proof::from_bytes(&generator).unwrap();
+
+    assert_eq!(
+        commitment_from_proto(&commitment_to_proto(&commitment)),
+        Ok(commitment)
+    );
+    assert_eq!(
+        commitment_from_proto(&commitment_to_proto(&on_domain)),
+        Ok(on_domain)
+    );
+    assert_eq!(proof_from_proto(&proof_to_proto(&proof)), Ok(proof));
+
+    let items: Vec<BatchItem> = (0..3)
+        .map(|i| BatchItem {
+            commitment: if i == 1 { on_domain } else { commitment },
+            point: FieldElement::from(i),
+            evaluation: FieldElement::from(i * i),
+            proof,
+        })
+        .collect();
+    assert_eq!(
+        bundle_from_proto(&bundle_to_proto(&items)),
+        Ok(items.clone())
+    );
+    assert_eq!(bundle_from_proto(&bundle_to_proto(&[])), Ok(vec![]));
+
+    // A scalar that isn't reduced, a point of the wrong length, and bytes that aren't protobuf
+    let mut message = ProofBundleMessage::decode(bundle_to_proto(&items).as_slice()).unwrap();
+    message.openings[2].evaluation = vec![0xff; FIELD_ELEMENT_SIZE];
+    assert_eq!(
+        bundle_from_proto(&message.encode_to_vec()),
+        Err(Error::InvalidScalar { index: 2 })
+    );
+    let message = ProofMessage {
+        witness: generator[..47].to_vec(),
+    };
+    assert!(proof_from_proto(&message.encode_to_vec()).is_err());
+    assert!(commitment_from_proto(&[0xff; 8]).is_err());
+}
@@ -0,0 +1,160 @@
+use crate::constant_time::ct_eq_gt;
+use crate::domain::EvaluationDomain;
+use crate::domain_polynomial::DomainPolynomial;
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::polynomials::Polynomial;
+use crate::scalars::scalars_from_data;
+use crate::types::FIELD_ELEMENT_SIZE;
+use blstrs::{pairing, G1Projective, Scalar};
+use group::ff::Field;
+use group::Curve;
+use std::ops::Range;
+
+// Proofs that positions `range` of a vector committed over an evaluation domain hold given values, with one witness
+// for the whole chunk. Position k is the value at $\omega^k$, so with S those points and I the polynomial
+// interpolating the chunk over them, $p - I$ vanishes on S and the witness is $[(p - I) / Z_S]_1$. The verifier
+// checks $e(C - [I(\tau)]_1, g_2) = e(W, [Z_S(\tau)]_2)$, which needs a power of tau in G2 per position plus one
+impl GenericPolynomialCommitment {
+    // The chunk's values and the witness
+    pub fn open_range(
+        &self,
+        polynomial: &DomainPolynomial,
+        range: Range<usize>,
+    ) -> Result<(Vec<Scalar>, G1Projective), Error> {
+        self.global_parameters()?;
+        check_range(polynomial.domain(), &range)?;
+        let values = polynomial.evaluations()[range.clone()].to_vec();
+        let points = range_points(polynomial.domain(), &range);
+
+        let coefficients = polynomial.coefficients();
+        let interpolant = interpolate(&points, &values)?;
+        let len = coefficients.len().max(interpolant.len());
+        let difference: Vec<Scalar> = (0..len)
+            .map(|i| coefficients.coeff(i) - interpolant.coeff(i))
+            .collect();
+        // Exact, since the difference is zero at every point of S
+        let quotient = Polynomial::new(&difference).checked_div(&Polynomial::vanishing(&points))?;
+        let witness = self.msm(quotient.coeffs())?;
+        Ok((values, witness))
+    }
+
+    // `chunk` is the claimed values as 32-byte little-endian field elements, as `chunk_bytes` encodes them
+    pub fn verify_range(
+        &self,
+        commitment: G1Projective,
+        domain: &EvaluationDomain,
+        range: Range<usize>,
+        chunk: &[u8],
+        witness: G1Projective,
+    ) -> Result<bool, Error> {
+        let global_parameters = self.global_parameters()?;
+        check_range(domain, &range)?;
+        if chunk.len() != range.len() * FIELD_ELEMENT_SIZE {
+            return Err(Error::InvalidEncoding(
+                "chunk length doesn't match the range",
+            ));
+        }
+        let elements: Vec<[u8; FIELD_ELEMENT_SIZE]> = chunk
+            .chunks_exact(FIELD_ELEMENT_SIZE)
+            .map(|bytes| bytes.try_into().unwrap())
+            .collect();
+        let values = scalars_from_data(&elements)?;
+        let points = range_points(domain, &range);
+
+        let interpolant = self.msm(interpolate(&points, &values)?.coeffs())?;
+        let vanishing = global_parameters.commit_in_g2(Polynomial::vanishing(&points).coeffs())?;
+        let lhs = pairing(
+            &(commitment - interpolant).to_affine(),
+            &global_parameters.hs()[0].to_affine(),
+        );
+        let rhs = pairing(&witness.to_affine(), &vanishing.to_affine());
+        Ok(ct_eq_gt(&lhs, &rhs).into())
+    }
+}
+
+// The encoding `verify_range` takes
+pub fn chunk_bytes(values: &[Scalar]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_bytes_le())
+        .collect()
+}
+
+fn check_range(domain: &EvaluationDomain, range: &Range<usize>) -> Result<(), Error> {
+    if range.is_empty() || range.end > domain.size() {
+        return Err(Error::IndexOutOfRange {
+            index: range.end.max(range.start),
+            len: domain.size(),
+        });
+    }
+    Ok(())
+}
+
+fn range_points(domain: &EvaluationDomain, range: &Range<usize>) -> Vec<Scalar> {
+    range.clone().map(|k| domain.element(k)).collect()
+}
+
+// Lagrange interpolation through distinct points: $\sum_k v_k \frac{Z(x) / (x - x_k)}{Z'(x_k)}$, where the
+// denominator is the numerator evaluated at $x_k$. Quadratic, which is fine for chunk-sized ranges
+fn interpolate(points: &[Scalar], values: &[Scalar]) -> Result<Polynomial, Error> {
+    let vanishing = Polynomial::vanishing(points);
+    let mut result = vec![Scalar::ZERO; points.len()];
+    for (point, value) in points.iter().zip(values) {
+        let numerator = vanishing.checked_div(&Polynomial::new(&[-*point, Scalar::ONE]))?;
+        let scale = *value
+            * Option::<Scalar>::from(numerator.evaluate(*point).invert())
+                .ok_or(Error::DivisionByZero)?;
+        for (coefficient, term) in result.iter_mut().zip(numerator.iter()) {
+            *coefficient += scale * term;
+        }
+    }
+    Ok(Polynomial::new(&result))
+}
+
+#[test]
+fn proves_contiguous_chunks_with_one_witness() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(7, &mut rng);
+
+    let domain = EvaluationDomain::new(8).unwrap();
+    let vector: Vec<Scalar> = (0..8).map(|_| Scalar::random(&mut rng)).collect();
+    let polynomial = DomainPolynomial::from_evaluations(domain.clone(), vector.clone()).unwrap();
+    let commitment = committer
+        .global_parameters()
+        .unwrap()
+        .commit_in_g1(polynomial.coefficients().coeffs())
+        .unwrap();
+
+    for range in [2..6, 0..1, 1..8] {
+        let (values, witness) = committer.open_range(&polynomial, range.clone()).unwrap();
+        assert_eq!(values, vector[range.clone()]);
+        let chunk = chunk_bytes(&values);
+        assert_eq!(
+            committer.verify_range(commitment, &domain, range.clone(), &chunk, witness),
+            Ok(true)
+        );
+
+        let mut tampered = chunk.clone();
+        tampered[0] ^= 1;
+        assert_eq!(
+            committer.verify_range(commitment, &domain, range.clone(), &tampered, witness),
+            Ok(false)
+        );
+    }
+
+    // The same values claimed one position over don't verify
+    let (values, witness) = committer.open_range(&polynomial, 2..6).unwrap();
+    assert_eq!(
+        committer.verify_range(commitment, &domain, 3..7, &chunk_bytes(&values), witness),
+        Ok(false)
+    );
+    assert!(committer
+        .verify_range(commitment, &domain, 2..5, &chunk_bytes(&values), witness)
+        .is_err());
+    assert!(committer.open_range(&polynomial, 6..9).is_err());
+}
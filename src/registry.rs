@@ -0,0 +1,117 @@
+use crate::error::Error;
+use crate::polynomial_commitments::{GenericPolynomialCommitment, GlobalParameters};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+// Loaded parameters keyed by max degree, so every committer and verifier in an application shares one copy of each
+// SRS rather than loading its own. Use `ParametersRegistry::global()` for the process-wide instance, or create one
+// and pass it around explicitly
+#[derive(Debug, Default)]
+pub struct ParametersRegistry {
+    entries: Mutex<HashMap<usize, Arc<GlobalParameters>>>,
+}
+
+static GLOBAL_REGISTRY: OnceLock<ParametersRegistry> = OnceLock::new();
+
+impl ParametersRegistry {
+    pub fn new() -> Self {
+        ParametersRegistry::default()
+    }
+
+    pub fn global() -> &'static ParametersRegistry {
+        GLOBAL_REGISTRY.get_or_init(ParametersRegistry::new)
+    }
+
+    pub fn get(&self, max_degree: usize) -> Option<Arc<GlobalParameters>> {
+        self.entries.lock().unwrap().get(&max_degree).cloned()
+    }
+
+    // Registers parameters under their own max degree, replacing whatever was there. Components already holding the
+    // old parameters keep them
+    pub fn insert(&self, global_parameters: GlobalParameters) -> Arc<GlobalParameters> {
+        let max_degree = global_parameters.max_degree().get();
+        let global_parameters = Arc::new(global_parameters);
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(max_degree, global_parameters.clone());
+        global_parameters
+    }
+
+    // Only runs `load` when nothing is registered for `max_degree`. The lock is held while loading, so concurrent
+    // callers wait for the one load rather than racing to do their own
+    pub fn get_or_insert_with(
+        &self,
+        max_degree: usize,
+        load: impl FnOnce() -> Result<GlobalParameters, Error>,
+    ) -> Result<Arc<GlobalParameters>, Error> {
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(global_parameters) = entries.get(&max_degree) {
+            return Ok(global_parameters.clone());
+        }
+        let global_parameters = load()?;
+        let actual = global_parameters.max_degree().get();
+        if actual != max_degree {
+            return Err(Error::DegreeMismatch {
+                expected: max_degree,
+                actual,
+            });
+        }
+        let global_parameters = Arc::new(global_parameters);
+        entries.insert(max_degree, global_parameters.clone());
+        Ok(global_parameters)
+    }
+
+    pub fn remove(&self, max_degree: usize) -> Option<Arc<GlobalParameters>> {
+        self.entries.lock().unwrap().remove(&max_degree)
+    }
+
+    // A committer over the registered parameters for `max_degree`
+    pub fn committer(&self, max_degree: usize) -> Result<GenericPolynomialCommitment, Error> {
+        self.get(max_degree)
+            .map(GenericPolynomialCommitment::with_shared_parameters)
+            .ok_or(Error::SetupIncomplete)
+    }
+}
+
+#[test]
+fn shares_one_copy_of_each_srs() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let registry = ParametersRegistry::new();
+    assert!(registry.committer(3).is_err());
+
+    let mut loads = 0;
+    for _ in 0..2 {
+        registry
+            .get_or_insert_with(3, || {
+                loads += 1;
+                Ok(GenericPolynomialCommitment::new().setup_with_rng(3, &mut test_rng()))
+            })
+            .unwrap();
+    }
+    assert_eq!(loads, 1);
+
+    let a = registry.committer(3).unwrap();
+    let b = registry.committer(3).unwrap();
+    assert!(std::ptr::eq(
+        a.global_parameters().unwrap(),
+        b.global_parameters().unwrap()
+    ));
+
+    // Parameters for another degree can't be registered under this one
+    assert_eq!(
+        registry
+            .get_or_insert_with(5, || Ok(
+                GenericPolynomialCommitment::new().setup_with_rng(3, &mut test_rng())
+            ))
+            .unwrap_err(),
+        Error::DegreeMismatch {
+            expected: 5,
+            actual: 3
+        }
+    );
+    assert!(registry.remove(3).is_some());
+    assert!(registry.get(3).is_none());
+}
@@ -0,0 +1,172 @@
+use crate::error::Error;
+use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+use crate::polynomials::Polynomial;
+use crate::transcript::{Sha256Transcript, Transcript};
+use blstrs::{G1Projective, Scalar};
+use rand::{CryptoRng, RngCore};
+
+// Proof of retrievability: a client that only kept the commitment to its data periodically asks the storage provider
+// to open the data polynomial at an unpredictable point. A provider that threw the data away can't answer. The point
+// is derived from the commitment, the round number and a fresh client nonce, so answers can't be precomputed or
+// replayed from an earlier round
+
+const PROTOCOL: &[u8] = b"KZG_PROOF_OF_RETRIEVABILITY_V1";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Challenge {
+    pub round: u64,
+    pub nonce: [u8; 32],
+}
+
+impl Challenge {
+    // The evaluation point both sides derive from the challenge
+    pub fn point(&self, commitment: &G1Projective) -> Scalar {
+        let mut transcript = Sha256Transcript::new(PROTOCOL);
+        transcript.append_g1(b"commitment", commitment);
+        transcript.append_bytes(b"round", &self.round.to_le_bytes());
+        transcript.append_bytes(b"nonce", &self.nonce);
+        transcript.challenge_scalar(b"point")
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Response {
+    pub evaluation: Scalar,
+    pub witness: G1Projective,
+}
+
+// Holds the data and answers challenges about it
+pub struct StorageProvider {
+    polynomial: Polynomial,
+    commitment: G1Projective,
+}
+
+impl StorageProvider {
+    pub fn new(
+        committer: &GenericPolynomialCommitment,
+        polynomial: Polynomial,
+    ) -> Result<Self, Error> {
+        let commitment = committer.commit(&polynomial)?;
+        Ok(StorageProvider {
+            polynomial,
+            commitment,
+        })
+    }
+
+    pub fn commitment(&self) -> G1Projective {
+        self.commitment
+    }
+
+    pub fn respond(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        challenge: &Challenge,
+    ) -> Result<Response, Error> {
+        let point = challenge.point(&self.commitment);
+        let (witness, evaluation) = committer.create_witness(&self.polynomial, point)?;
+        Ok(Response {
+            evaluation,
+            witness,
+        })
+    }
+}
+
+// Keeps only the commitment, issues challenges and tracks how the provider has done over time
+#[derive(Debug)]
+pub struct StorageClient {
+    commitment: G1Projective,
+    next_round: u64,
+    passed: u64,
+    failed: u64,
+}
+
+impl StorageClient {
+    pub fn new(commitment: G1Projective) -> Self {
+        StorageClient {
+            commitment,
+            next_round: 0,
+            passed: 0,
+            failed: 0,
+        }
+    }
+
+    pub fn issue_challenge<R: RngCore + CryptoRng>(&mut self, rng: &mut R) -> Challenge {
+        let mut nonce = [0; 32];
+        rng.fill_bytes(&mut nonce);
+        let challenge = Challenge {
+            round: self.next_round,
+            nonce,
+        };
+        self.next_round += 1;
+        challenge
+    }
+
+    // Checks the response and records the outcome. Malformed responses (e.g. an identity witness) count as failures
+    pub fn check(
+        &mut self,
+        committer: &GenericPolynomialCommitment,
+        challenge: &Challenge,
+        response: &Response,
+    ) -> Result<bool, Error> {
+        let point = challenge.point(&self.commitment);
+        let valid = match committer.verify_evaluation(
+            self.commitment,
+            point,
+            response.evaluation,
+            response.witness,
+        ) {
+            Ok(valid) => valid,
+            Err(Error::IdentityWitness) => false,
+            Err(error) => return Err(error),
+        };
+        if valid {
+            self.passed += 1;
+        } else {
+            self.failed += 1;
+        }
+        Ok(valid)
+    }
+
+    pub fn passed(&self) -> u64 {
+        self.passed
+    }
+
+    pub fn failed(&self) -> u64 {
+        self.failed
+    }
+}
+
+#[test]
+fn provider_answers_challenges_over_time() {
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(7, &mut rng);
+
+    let provider = StorageProvider::new(&committer, Polynomial::rand(7, &mut rng)).unwrap();
+    let mut client = StorageClient::new(provider.commitment());
+
+    let mut challenges = vec![];
+    for _ in 0..3 {
+        let challenge = client.issue_challenge(&mut rng);
+        let response = provider.respond(&committer, &challenge).unwrap();
+        assert_eq!(client.check(&committer, &challenge, &response), Ok(true));
+        challenges.push((challenge, response));
+    }
+    assert_eq!((client.passed(), client.failed()), (3, 0));
+
+    // Replaying an old response to a new challenge fails
+    let challenge = client.issue_challenge(&mut rng);
+    assert_eq!(
+        client.check(&committer, &challenge, &challenges[0].1),
+        Ok(false)
+    );
+
+    // So does a provider that lost the data and answers from a different polynomial
+    let forger = StorageProvider::new(&committer, Polynomial::rand(7, &mut rng)).unwrap();
+    let challenge = client.issue_challenge(&mut rng);
+    let forged = forger.respond(&committer, &challenge).unwrap();
+    assert_eq!(client.check(&committer, &challenge, &forged), Ok(false));
+    assert_eq!((client.passed(), client.failed()), (3, 2));
+}
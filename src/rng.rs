@@ -0,0 +1,43 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+// Environment variable consulted by `test_rng`, so a failing run can be replayed with the seed it logged
+pub const TEST_SEED_ENV: &str = "KZG_TEST_SEED";
+const DEFAULT_TEST_SEED: u64 = 0x6b7a_6720_7365_6564;
+
+// A deterministic rng for every randomized operation (setup, padding, blinding, challenges) derived from a user
+// provided seed. Pass it to the `*_with_rng` variants to make a run reproducible
+pub fn seeded_rng(seed: [u8; 32]) -> ChaCha20Rng {
+    ChaCha20Rng::from_seed(seed)
+}
+
+pub fn seeded_rng_from_u64(seed: u64) -> ChaCha20Rng {
+    ChaCha20Rng::seed_from_u64(seed)
+}
+
+// The rng used throughout the tests. The seed comes from `KZG_TEST_SEED` when set, otherwise a fixed default, and is
+// logged so it can be included in bug reports
+pub fn test_rng() -> ChaCha20Rng {
+    let seed = std::env::var(TEST_SEED_ENV)
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(DEFAULT_TEST_SEED);
+    log::info!("using {}={}", TEST_SEED_ENV, seed);
+    seeded_rng_from_u64(seed)
+}
+
+#[test]
+fn seeded_rngs_are_reproducible() {
+    use rand::RngCore;
+
+    let mut a = seeded_rng([7; 32]);
+    let mut b = seeded_rng([7; 32]);
+    let mut c = seeded_rng([8; 32]);
+    assert_eq!(a.next_u64(), b.next_u64());
+    assert_ne!(a.next_u64(), c.next_u64());
+
+    assert_eq!(
+        seeded_rng_from_u64(1).next_u64(),
+        seeded_rng_from_u64(1).next_u64()
+    );
+}
@@ -0,0 +1,142 @@
+use crate::error::Error;
+use crate::types::FieldElement;
+use blstrs::Scalar;
+use group::ff::PrimeField;
+use num_bigint::{BigInt, BigUint, Sign};
+
+// The order of the BLS12-381 scalar field, r
+const MODULUS_HEX: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+
+pub fn modulus() -> BigUint {
+    BigUint::parse_bytes(MODULUS_HEX.as_bytes(), 16).unwrap()
+}
+
+// Reduces the value mod r, so any BigUint maps to a field element
+pub fn scalar_from_biguint(value: &BigUint) -> Scalar {
+    let reduced = value % modulus();
+    let mut bytes = [0u8; 32];
+    let le = reduced.to_bytes_le();
+    bytes[..le.len()].copy_from_slice(&le);
+    // Can unwrap since the value was reduced below the modulus
+    Scalar::from_bytes_le(&bytes).unwrap()
+}
+
+// Negative values map to their additive inverses, so -5 becomes r - 5
+pub fn scalar_from_bigint(value: &BigInt) -> Scalar {
+    let magnitude = scalar_from_biguint(value.magnitude());
+    if value.sign() == Sign::Minus {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+pub fn scalar_from_i64(value: i64) -> Scalar {
+    let magnitude = Scalar::from(value.unsigned_abs());
+    if value < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+pub fn scalar_to_biguint(scalar: &Scalar) -> BigUint {
+    BigUint::from_bytes_le(&scalar.to_bytes_le())
+}
+
+// Application data that maps into the field without loss. Integers up to 128 bits always fit, and signed ones map
+// negatives to additive inverses like `scalar_from_i64`. Byte arrays are read little-endian and must already be
+// reduced: reducing them mod r would let two different inputs commit to the same polynomial, so `None` instead
+pub trait IntoScalar: Copy {
+    fn into_scalar(self) -> Option<Scalar>;
+}
+
+macro_rules! unsigned_into_scalar {
+    ($($t:ty),*) => {
+        $(impl IntoScalar for $t {
+            fn into_scalar(self) -> Option<Scalar> {
+                Some(Scalar::from(self as u64))
+            }
+        })*
+    };
+}
+
+unsigned_into_scalar!(bool, u8, u16, u32, u64);
+
+impl IntoScalar for u128 {
+    fn into_scalar(self) -> Option<Scalar> {
+        Some(Scalar::from_u128(self))
+    }
+}
+
+impl IntoScalar for i64 {
+    fn into_scalar(self) -> Option<Scalar> {
+        Some(scalar_from_i64(self))
+    }
+}
+
+impl IntoScalar for [u8; 32] {
+    fn into_scalar(self) -> Option<Scalar> {
+        Scalar::from_bytes_le(&self).into()
+    }
+}
+
+impl IntoScalar for Scalar {
+    fn into_scalar(self) -> Option<Scalar> {
+        Some(self)
+    }
+}
+
+impl IntoScalar for FieldElement {
+    fn into_scalar(self) -> Option<Scalar> {
+        Some(self.inner())
+    }
+}
+
+// Every element or the index of the first that doesn't convert
+pub fn scalars_from_data<T: IntoScalar>(data: &[T]) -> Result<Vec<Scalar>, Error> {
+    data.iter()
+        .enumerate()
+        .map(|(index, value)| value.into_scalar().ok_or(Error::InvalidScalar { index }))
+        .collect()
+}
+
+#[test]
+fn converts_signed_values() {
+    assert_eq!(scalar_from_i64(5), Scalar::from(5));
+    assert_eq!(scalar_from_i64(-5), -Scalar::from(5));
+    assert_eq!(scalar_from_i64(i64::MIN), -Scalar::from(1u64 << 63));
+    assert_eq!(scalar_from_bigint(&BigInt::from(-7)), -Scalar::from(7));
+}
+
+#[test]
+fn reduces_big_values_mod_r() {
+    let r = modulus();
+    assert_eq!(scalar_from_biguint(&r), Scalar::from(0));
+    assert_eq!(scalar_from_biguint(&(&r + 3u32)), Scalar::from(3));
+    assert_eq!(scalar_to_biguint(&-Scalar::from(1)), r - 1u32);
+}
+
+#[test]
+fn converts_typed_data_without_loss() {
+    assert_eq!(
+        scalars_from_data(&[1u32, 2, 3]).unwrap(),
+        vec![Scalar::from(1), Scalar::from(2), Scalar::from(3)]
+    );
+    assert_eq!(
+        scalars_from_data(&[u128::MAX]).unwrap()[0],
+        scalar_from_biguint(&BigUint::from(u128::MAX))
+    );
+    assert_eq!(scalars_from_data(&[-2i64]).unwrap()[0], -Scalar::from(2));
+    assert_eq!(
+        scalars_from_data(&[Scalar::from(9).to_bytes_le()]).unwrap()[0],
+        Scalar::from(9)
+    );
+    // r itself isn't canonical, and the error says which element it was
+    let mut r = [0u8; 32];
+    r.copy_from_slice(&modulus().to_bytes_le());
+    assert_eq!(
+        scalars_from_data(&[[0u8; 32], r]),
+        Err(Error::InvalidScalar { index: 1 })
+    );
+}
@@ -0,0 +1,253 @@
+use crate::error::Error;
+use crate::polynomial_commitments::{GenericPolynomialCommitment, GlobalParameters};
+use crate::polynomials::Polynomial;
+use blstrs::{G1Projective, Scalar};
+use core::sync::atomic::{compiler_fence, Ordering};
+use group::ff::Field;
+use group::Group;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Overwrite secret scalars with zero in a way the compiler can't elide. blstrs doesn't implement `Zeroize` for
+// `Scalar`, so this mirrors what the zeroize crate does for its own types: volatile writes followed by a fence
+pub fn zeroize_scalars(scalars: &mut [Scalar]) {
+    for scalar in scalars.iter_mut() {
+        // Safety: `scalar` is a valid, aligned, exclusive reference for the duration of the write
+        unsafe { core::ptr::write_volatile(scalar, Scalar::ZERO) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+pub fn zeroize_scalar(scalar: &mut Scalar) {
+    zeroize_scalars(core::slice::from_mut(scalar))
+}
+
+// A polynomial whose coefficients are secret, e.g. a dealer's polynomial in VSS. Its coefficients are wiped when it
+// is dropped, and it intentionally doesn't print them
+pub struct SecretPolynomial(Polynomial);
+
+impl SecretPolynomial {
+    pub fn new(polynomial: Polynomial) -> Self {
+        SecretPolynomial(polynomial)
+    }
+
+    pub fn expose_secret(&self) -> &Polynomial {
+        &self.0
+    }
+}
+
+impl Zeroize for SecretPolynomial {
+    fn zeroize(&mut self) {
+        let coefficients = self.0.coeffs_mut();
+        zeroize_scalars(coefficients);
+        coefficients.clear();
+    }
+}
+
+impl Drop for SecretPolynomial {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretPolynomial {}
+
+impl core::fmt::Debug for SecretPolynomial {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("SecretPolynomial(..)")
+    }
+}
+
+// Evaluation and witness generation whose control flow and memory accesses depend only on how many coefficients are
+// stored, never on their values. The usual paths don't promise that: building a `Polynomial` trims trailing zeros,
+// division branches on leading coefficients, and the Pippenger MSM picks buckets by scalar bits. These work on the
+// stored coefficients as they are, divide by $x - z$ with one multiply-add per coefficient, and sum one constant-time
+// scalar multiplication per power of tau, so they're slower. The point is treated as public
+impl SecretPolynomial {
+    // Horner's rule over every stored coefficient
+    pub fn evaluate_ct(&self, point: Scalar) -> Scalar {
+        self.0
+            .coeffs()
+            .iter()
+            .rev()
+            .fold(Scalar::ZERO, |total, coefficient| {
+                total * point + coefficient
+            })
+    }
+}
+
+impl GenericPolynomialCommitment {
+    // `create_witness` for a secret polynomial. Unlike it, a constant polynomial isn't an error but gets the identity
+    // as its witness, since checking would branch on the coefficients
+    pub fn create_witness_ct(
+        &self,
+        polynomial: &SecretPolynomial,
+        point: Scalar,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        let global_parameters = self.global_parameters()?;
+        let coefficients = polynomial.0.coeffs();
+        let quotient_len = coefficients.len().saturating_sub(1);
+        if quotient_len > global_parameters.gs().len() {
+            return Err(Error::DegreeTooLarge {
+                max: global_parameters.gs().len() - 1,
+                actual: quotient_len - 1,
+            });
+        }
+
+        // Synthetic division from the top: $q_{i-1} = p_i + z q_i$, and the last step's value is the remainder $p(z)$
+        let mut quotient = vec![Scalar::ZERO; quotient_len];
+        let mut carry = Scalar::ZERO;
+        for i in (0..coefficients.len()).rev() {
+            carry = coefficients[i] + point * carry;
+            if i > 0 {
+                quotient[i - 1] = carry;
+            }
+        }
+        let witness = global_parameters
+            .gs()
+            .iter()
+            .zip(&quotient)
+            .fold(G1Projective::identity(), |sum, (power, q)| sum + power * q);
+        zeroize_scalars(&mut quotient);
+        Ok((witness, carry))
+    }
+}
+
+impl GlobalParameters {
+    // `commit_in_g1` for secret coefficients, blinded by splitting each into two random-looking shares
+    // $s_i = a_i + (s_i - a_i)$ with $a_i$ uniform, and running the fast MSM on each share. Either MSM on its own sees
+    // only uniform scalars, so what its bucket accesses leak through timing or power says nothing about the secret.
+    // Costs a second MSM
+    pub fn commit_in_g1_blinded<R: RngCore + CryptoRng>(
+        &self,
+        coefficients: &[Scalar],
+        rng: &mut R,
+    ) -> Result<G1Projective, Error> {
+        let mut shares: Vec<Scalar> = coefficients
+            .iter()
+            .map(|_| Scalar::random(&mut *rng))
+            .collect();
+        let mut rest: Vec<Scalar> = coefficients
+            .iter()
+            .zip(&shares)
+            .map(|(coefficient, share)| coefficient - share)
+            .collect();
+        let first = self.commit_in_g1(&shares);
+        let second = self.commit_in_g1(&rest);
+        zeroize_scalars(&mut shares);
+        zeroize_scalars(&mut rest);
+        Ok(first? + second?)
+    }
+}
+
+impl GenericPolynomialCommitment {
+    pub fn commit_blinded<R: RngCore + CryptoRng>(
+        &self,
+        polynomial: &SecretPolynomial,
+        rng: &mut R,
+    ) -> Result<G1Projective, Error> {
+        self.global_parameters()?
+            .commit_in_g1_blinded(polynomial.0.coeffs(), rng)
+    }
+}
+
+#[test]
+fn zeroizes_scalars() {
+    let mut scalars = vec![Scalar::from(7), -Scalar::from(3)];
+    zeroize_scalars(&mut scalars);
+    assert!(scalars.iter().all(|s| bool::from(s.is_zero())));
+}
+
+#[test]
+fn zeroizes_secret_polynomial() {
+    let mut secret = SecretPolynomial::new(Polynomial::new_from_bytes(&[1, 2, 3]));
+    assert_eq!(
+        secret.expose_secret().evaluate(Scalar::from(5)),
+        Scalar::from(86)
+    );
+
+    secret.zeroize();
+    assert!(secret.expose_secret().is_empty());
+    assert_eq!(format!("{:?}", secret), "SecretPolynomial(..)");
+}
+
+#[test]
+fn constant_time_path_matches_the_usual_one() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(4, &mut test_rng());
+    let polynomial = Polynomial::rand(4, &mut test_rng());
+    let secret = SecretPolynomial::new(polynomial.clone());
+    let point = Scalar::from(6);
+
+    assert_eq!(secret.evaluate_ct(point), polynomial.evaluate(point));
+    assert_eq!(
+        committer.create_witness_ct(&secret, point).unwrap(),
+        committer.create_witness(&polynomial, point).unwrap()
+    );
+    let constant = SecretPolynomial::new(Polynomial::new_from_i64(&[4]));
+    assert_eq!(
+        committer.create_witness_ct(&constant, point).unwrap(),
+        (G1Projective::identity(), Scalar::from(4))
+    );
+}
+
+// Timing is too noisy on shared machines to run by default. Run it with `cargo test --release -- --ignored` on a
+// quiet machine: witnesses for an all-zero and a random polynomial of the same length should take the same time
+#[test]
+#[ignore]
+fn constant_time_path_takes_the_same_time_for_any_coefficients() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+    use std::time::{Duration, Instant};
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(63, &mut test_rng());
+    let zeros = SecretPolynomial(Polynomial::from_coeffs_untrimmed(vec![Scalar::ZERO; 64]));
+    let random = SecretPolynomial::new(Polynomial::rand(63, &mut test_rng()));
+    let point = Scalar::from(3);
+
+    // Interleaved, so drift in the machine's speed affects both alike
+    let (mut zeros_time, mut random_time) = (Duration::ZERO, Duration::ZERO);
+    for _ in 0..200 {
+        let start = Instant::now();
+        committer.create_witness_ct(&zeros, point).unwrap();
+        zeros_time += start.elapsed();
+        let start = Instant::now();
+        committer.create_witness_ct(&random, point).unwrap();
+        random_time += start.elapsed();
+    }
+    let ratio = zeros_time.as_secs_f64() / random_time.as_secs_f64();
+    assert!((0.9..1.1).contains(&ratio), "timing ratio {ratio}");
+}
+
+#[test]
+fn blinded_commitments_match_unblinded_ones() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(4, &mut test_rng());
+    let polynomial = Polynomial::rand(4, &mut test_rng());
+    let expected = committer.commit(&polynomial).unwrap();
+
+    let secret = SecretPolynomial::new(polynomial);
+    let mut rng = test_rng();
+    assert_eq!(
+        committer.commit_blinded(&secret, &mut rng).unwrap(),
+        expected
+    );
+    // Fresh shares each time, same commitment
+    assert_eq!(
+        committer.commit_blinded(&secret, &mut rng).unwrap(),
+        expected
+    );
+    assert!(committer
+        .commit_blinded(
+            &SecretPolynomial::new(Polynomial::rand(5, &mut rng)),
+            &mut rng
+        )
+        .is_err());
+}
@@ -0,0 +1,380 @@
+use crate::error::Error;
+use crate::lagrange::LagrangeBasis;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use blstrs::{G1Projective, Scalar};
+use group::ff::{BatchInvert, Field};
+use std::collections::BTreeMap;
+
+// A vector over a large logical domain where only a few positions are set and every other one holds a default
+// value, as in a rollup's state diff. Position i is the value at $\omega^i$ of
+// $p(x) = d + \sum_{i \in S} (v_i - d) L_i(x)$, where S are the set positions and $L_i$ the Lagrange polynomials of
+// the domain. Nothing is ever interpolated: with the $[L_i(\tau)]_1$ precomputed once, committing costs one MSM over
+// the set positions
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SparseVector {
+    len: usize,
+    default: Scalar,
+    entries: BTreeMap<usize, Scalar>,
+}
+
+impl SparseVector {
+    pub fn new(len: usize, default: Scalar) -> Self {
+        SparseVector {
+            len,
+            default,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn default_value(&self) -> Scalar {
+        self.default
+    }
+
+    // Setting a position back to the default clears it
+    pub fn set(&mut self, index: usize, value: Scalar) -> Result<(), Error> {
+        if index >= self.len {
+            return Err(Error::IndexOutOfRange {
+                index,
+                len: self.len,
+            });
+        }
+        if value == self.default {
+            self.entries.remove(&index);
+        } else {
+            self.entries.insert(index, value);
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, index: usize) -> Option<Scalar> {
+        if index >= self.len {
+            return None;
+        }
+        Some(*self.entries.get(&index).unwrap_or(&self.default))
+    }
+
+    // The positions holding something other than the default, in order
+    pub fn entries(&self) -> impl Iterator<Item = (usize, Scalar)> + '_ {
+        self.entries.iter().map(|(index, value)| (*index, *value))
+    }
+}
+
+// That a position holds the vector's default, i.e. that nothing was ever set there. Its own type so an exclusion
+// proof can't be passed off as, or mistaken for, an opening of some value
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AbsenceProof {
+    pub index: usize,
+    pub witness: G1Projective,
+}
+
+// The Lagrange basis in G1 for one domain, computed from the powers of tau
+pub struct SparseVectorKey<'a> {
+    committer: &'a GenericPolynomialCommitment,
+    basis: LagrangeBasis,
+    base: G1Projective,
+}
+
+impl<'a> SparseVectorKey<'a> {
+    // `size` must be a power of two no larger than the number of powers of tau. Takes $O(n \log n)$ group operations
+    pub fn new(committer: &'a GenericPolynomialCommitment, size: usize) -> Result<Self, Error> {
+        let global_parameters = committer.global_parameters()?;
+        let basis = global_parameters.lagrange_basis(size)?;
+        Ok(SparseVectorKey {
+            committer,
+            basis,
+            base: global_parameters.gs()[0],
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.basis.domain().size()
+    }
+
+    // The domain element position `index` is opened at
+    pub fn point(&self, index: usize) -> Scalar {
+        self.basis.domain().element(index)
+    }
+
+    fn check_len(&self, vector: &SparseVector) -> Result<(), Error> {
+        if vector.len() != self.size() {
+            return Err(Error::DegreeMismatch {
+                expected: self.size(),
+                actual: vector.len(),
+            });
+        }
+        Ok(())
+    }
+
+    // Since the Lagrange polynomials sum to one, the default contributes $d [1]_1$, the first power of tau
+    pub fn commit(&self, vector: &SparseVector) -> Result<G1Projective, Error> {
+        self.check_len(vector)?;
+        let (bases, scalars): (Vec<G1Projective>, Vec<Scalar>) = vector
+            .entries()
+            .map(|(index, value)| (self.basis.points()[index], value - vector.default))
+            .chain([(self.base, vector.default)])
+            .unzip();
+        Ok(G1Projective::multi_exp(&bases, &scalars))
+    }
+
+    // Applies a state diff to a commitment without the rest of the vector: changing position i from `old` to `new`
+    // adds $(new - old) [L_i(\tau)]_1$
+    pub fn update_commitment(
+        &self,
+        commitment: G1Projective,
+        index: usize,
+        old: Scalar,
+        new: Scalar,
+    ) -> Result<G1Projective, Error> {
+        let basis = self
+            .basis
+            .points()
+            .get(index)
+            .ok_or(Error::IndexOutOfRange {
+                index,
+                len: self.size(),
+            })?;
+        Ok(commitment + basis * (new - old))
+    }
+
+    // `update_commitment` for many positions at once, as one MSM. Updates to the same position combine, so a diff
+    // may list a position more than once as long as each entry's `old` is the value the previous one left
+    pub fn update_batch(
+        &self,
+        commitment: G1Projective,
+        updates: &[(usize, Scalar, Scalar)],
+    ) -> Result<G1Projective, Error> {
+        let mut deltas = BTreeMap::new();
+        for (index, old, new) in updates {
+            if *index >= self.size() {
+                return Err(Error::IndexOutOfRange {
+                    index: *index,
+                    len: self.size(),
+                });
+            }
+            *deltas.entry(*index).or_insert(Scalar::ZERO) += new - old;
+        }
+        let (bases, scalars): (Vec<G1Projective>, Vec<Scalar>) = deltas
+            .into_iter()
+            .map(|(index, delta)| (self.basis.points()[index], delta))
+            .unzip();
+        Ok(commitment + G1Projective::multi_exp(&bases, &scalars))
+    }
+
+    // The witness and value at `index`. The quotient of each set position i by $x - \omega^j$ is, for $i \ne j$,
+    // $\frac{L_i(x) - \omega^{i - j} L_j(x)}{\omega^i - \omega^j}$, so opening a default position costs one MSM over the
+    // set positions. Opening a set position also needs $\frac{L_j(x) - 1}{x - \omega^j} = -\sum_{k \ne j} \frac{L_k(x)}{x - \omega^j}$,
+    // which makes it an MSM over the whole domain
+    pub fn open(
+        &self,
+        vector: &SparseVector,
+        index: usize,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        self.check_len(vector)?;
+        let value = vector.get(index).ok_or(Error::IndexOutOfRange {
+            index,
+            len: self.size(),
+        })?;
+        let point = self.point(index);
+        let point_inv = point.invert().unwrap();
+
+        // Each term is (index k, scale a) standing for $a \frac{L_k(x) - \omega^{k - j} L_j(x)}{\omega^k - \omega^j}$
+        let mut terms: Vec<(usize, Scalar)> = vector
+            .entries()
+            .filter(|(i, _)| *i != index)
+            .map(|(i, v)| (i, v - vector.default))
+            .collect();
+        if let Some(own) = vector.entries.get(&index) {
+            let scale = vector.default - own;
+            terms.extend((0..self.size()).filter(|k| *k != index).map(|k| (k, scale)));
+        }
+
+        let elements: Vec<Scalar> = terms.iter().map(|(k, _)| self.point(*k)).collect();
+        let mut denominators: Vec<Scalar> =
+            elements.iter().map(|element| element - point).collect();
+        denominators.iter_mut().batch_invert();
+
+        let mut scalars = BTreeMap::new();
+        let mut own_scalar = Scalar::ZERO;
+        for (((k, scale), element), denominator) in terms.iter().zip(&elements).zip(&denominators) {
+            let a = scale * denominator;
+            *scalars.entry(*k).or_insert(Scalar::ZERO) += a;
+            own_scalar -= a * element * point_inv;
+        }
+        scalars.insert(index, own_scalar);
+        let (bases, scalars): (Vec<G1Projective>, Vec<Scalar>) = scalars
+            .into_iter()
+            .map(|(k, scalar)| (self.basis.points()[k], scalar))
+            .unzip();
+        Ok((G1Projective::multi_exp(&bases, &scalars), value))
+    }
+
+    pub fn verify(
+        &self,
+        commitment: G1Projective,
+        index: usize,
+        value: Scalar,
+        witness: G1Projective,
+    ) -> Result<bool, Error> {
+        if index >= self.size() {
+            return Err(Error::IndexOutOfRange {
+                index,
+                len: self.size(),
+            });
+        }
+        self.committer
+            .check_opening(commitment, self.point(index), value, witness)
+    }
+
+    // Errs with `NotDefault` if the position is set. Only costs an MSM over the set positions
+    pub fn prove_absent(&self, vector: &SparseVector, index: usize) -> Result<AbsenceProof, Error> {
+        if vector.entries.contains_key(&index) {
+            return Err(Error::NotDefault { index });
+        }
+        let (witness, _) = self.open(vector, index)?;
+        Ok(AbsenceProof { index, witness })
+    }
+
+    // The verifier supplies the default, since the commitment doesn't fix it
+    pub fn verify_absent(
+        &self,
+        commitment: G1Projective,
+        default: Scalar,
+        proof: &AbsenceProof,
+    ) -> Result<bool, Error> {
+        self.verify(commitment, proof.index, default, proof.witness)
+    }
+}
+
+#[test]
+fn commits_and_opens_sparse_vectors() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(15, &mut test_rng());
+    let key = SparseVectorKey::new(&committer, 16).unwrap();
+
+    let mut vector = SparseVector::new(16, Scalar::from(7));
+    vector.set(2, Scalar::from(100)).unwrap();
+    vector.set(11, Scalar::from(5)).unwrap();
+    let commitment = key.commit(&vector).unwrap();
+
+    // Same commitment as interpolating every position densely
+    let evaluations: Vec<Scalar> = (0..16).map(|i| vector.get(i).unwrap()).collect();
+    let coefficients = key.basis.domain().ifft(&evaluations);
+    assert_eq!(
+        commitment,
+        committer
+            .global_parameters()
+            .unwrap()
+            .commit_in_g1(&coefficients)
+            .unwrap()
+    );
+
+    // Set and default positions
+    for index in [2, 11, 0, 15] {
+        let (witness, value) = key.open(&vector, index).unwrap();
+        assert_eq!(value, vector.get(index).unwrap());
+        assert!(key.verify(commitment, index, value, witness).unwrap());
+        assert!(!key
+            .verify(commitment, index, value + Scalar::ONE, witness)
+            .unwrap());
+    }
+    assert!(key.open(&vector, 16).is_err());
+}
+
+#[test]
+fn updates_commitments_from_diffs() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(7, &mut test_rng());
+    let key = SparseVectorKey::new(&committer, 8).unwrap();
+
+    let mut vector = SparseVector::new(8, Scalar::ZERO);
+    let mut commitment = key.commit(&vector).unwrap();
+    for (index, value) in [(3, Scalar::from(9)), (3, Scalar::from(4)), (6, Scalar::ONE)] {
+        let old = vector.get(index).unwrap();
+        vector.set(index, value).unwrap();
+        commitment = key
+            .update_commitment(commitment, index, old, value)
+            .unwrap();
+    }
+    assert_eq!(commitment, key.commit(&vector).unwrap());
+    assert_eq!(vector.entries().count(), 2);
+}
+
+#[test]
+fn proves_positions_are_unset() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(7, &mut test_rng());
+    let key = SparseVectorKey::new(&committer, 8).unwrap();
+
+    let mut vector = SparseVector::new(8, Scalar::ZERO);
+    vector.set(5, Scalar::from(3)).unwrap();
+    let commitment = key.commit(&vector).unwrap();
+
+    let proof = key.prove_absent(&vector, 4).unwrap();
+    assert_eq!(
+        key.verify_absent(commitment, Scalar::ZERO, &proof),
+        Ok(true)
+    );
+    assert_eq!(
+        key.verify_absent(commitment, Scalar::ONE, &proof),
+        Ok(false)
+    );
+    // Moving the proof to the set position doesn't work either
+    let moved = AbsenceProof { index: 5, ..proof };
+    assert_eq!(
+        key.verify_absent(commitment, Scalar::ZERO, &moved),
+        Ok(false)
+    );
+    assert_eq!(
+        key.prove_absent(&vector, 5),
+        Err(Error::NotDefault { index: 5 })
+    );
+}
+
+#[test]
+fn applies_batches_of_updates_with_one_msm() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(7, &mut test_rng());
+    let key = SparseVectorKey::new(&committer, 8).unwrap();
+
+    let mut vector = SparseVector::new(8, Scalar::from(2));
+    vector.set(1, Scalar::from(10)).unwrap();
+    let commitment = key.commit(&vector).unwrap();
+
+    let updates = [
+        (1, Scalar::from(10), Scalar::from(11)),
+        (4, Scalar::from(2), Scalar::from(40)),
+        (4, Scalar::from(40), Scalar::from(41)),
+        (7, Scalar::from(2), Scalar::from(70)),
+    ];
+    for (index, _, new) in updates {
+        vector.set(index, new).unwrap();
+    }
+    assert_eq!(
+        key.update_batch(commitment, &updates).unwrap(),
+        key.commit(&vector).unwrap()
+    );
+    assert_eq!(key.update_batch(commitment, &[]).unwrap(), commitment);
+    assert!(key
+        .update_batch(commitment, &[(8, Scalar::ZERO, Scalar::ONE)])
+        .is_err());
+}
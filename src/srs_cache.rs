@@ -0,0 +1,138 @@
+use crate::error::Error;
+use crate::polynomial_commitments::GlobalParameters;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+// Environment variable overriding where cached parameters are kept
+pub const SRS_CACHE_DIR_ENV: &str = "KZG_SRS_CACHE_DIR";
+
+// Keeps generated or imported global parameters on disk, keyed by max degree, so tools don't redo setup on every run.
+// Files use the `GlobalParameters::to_bytes` format, so the fingerprint is checked every time they are loaded
+#[derive(Clone, Debug)]
+pub struct SrsCache {
+    dir: PathBuf,
+}
+
+impl SrsCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        SrsCache { dir: dir.into() }
+    }
+
+    // `$KZG_SRS_CACHE_DIR` if set, otherwise `~/.cache/kzg-polynomial-commitments`, falling back to the temp dir when
+    // there's no home directory
+    pub fn default_location() -> Self {
+        if let Some(dir) = std::env::var_os(SRS_CACHE_DIR_ENV) {
+            return SrsCache::new(dir);
+        }
+        let base = std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".cache"))
+            .unwrap_or_else(std::env::temp_dir);
+        SrsCache::new(base.join("kzg-polynomial-commitments"))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path_for(&self, max_degree: usize) -> PathBuf {
+        self.dir.join(format!("srs-{}.bin", max_degree))
+    }
+
+    // Load cached parameters for `max_degree`. `Ok(None)` if nothing has been cached yet
+    pub fn load(&self, max_degree: usize) -> Result<Option<GlobalParameters>, Error> {
+        match fs::read(self.path_for(max_degree)) {
+            Ok(bytes) => GlobalParameters::from_bytes(&bytes).map(Some),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // Write to a temporary file first and rename it into place, so a crash never leaves a truncated cache entry
+    pub fn store(
+        &self,
+        max_degree: usize,
+        global_parameters: &GlobalParameters,
+    ) -> Result<(), Error> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path_for(max_degree);
+        let tmp_path = path.with_extension("bin.tmp");
+        fs::write(&tmp_path, global_parameters.to_bytes())?;
+        Ok(fs::rename(&tmp_path, &path)?)
+    }
+
+    // Only runs `generate` when there's no usable cache entry. A corrupt entry is replaced rather than returned
+    pub fn get_or_insert_with(
+        &self,
+        max_degree: usize,
+        generate: impl FnOnce() -> GlobalParameters,
+    ) -> Result<GlobalParameters, Error> {
+        match self.load(max_degree) {
+            Ok(Some(global_parameters)) => return Ok(global_parameters),
+            Ok(None) => {}
+            Err(Error::Io(kind)) => return Err(Error::Io(kind)),
+            Err(e) => log::warn!(
+                "discarding cached parameters at {:?}: {:?}",
+                self.path_for(max_degree),
+                e
+            ),
+        }
+        let global_parameters = generate();
+        self.store(max_degree, &global_parameters)?;
+        Ok(global_parameters)
+    }
+}
+
+#[cfg(test)]
+fn temp_cache(name: &str) -> SrsCache {
+    let dir = std::env::temp_dir().join(format!("kzg-srs-cache-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    SrsCache::new(dir)
+}
+
+#[test]
+fn caches_and_reloads_parameters() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::rng::test_rng;
+
+    let cache = temp_cache("reload");
+    assert!(cache.load(4).unwrap().is_none());
+
+    let mut generated = 0;
+    let first = cache
+        .get_or_insert_with(4, || {
+            generated += 1;
+            GenericPolynomialCommitment::new().setup_with_rng(3, &mut test_rng())
+        })
+        .unwrap();
+    let second = cache
+        .get_or_insert_with(4, || {
+            generated += 1;
+            GenericPolynomialCommitment::new().setup_with_rng(3, &mut test_rng())
+        })
+        .unwrap();
+
+    assert_eq!(generated, 1);
+    assert_eq!(first.fingerprint(), second.fingerprint());
+    let _ = fs::remove_dir_all(cache.dir());
+}
+
+#[test]
+fn rejects_corrupt_cache_entry() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::rng::test_rng;
+
+    let cache = temp_cache("corrupt");
+    let gp = GenericPolynomialCommitment::new().setup_with_rng(2, &mut test_rng());
+    cache.store(3, &gp).unwrap();
+
+    let mut bytes = fs::read(cache.path_for(3)).unwrap();
+    bytes[12] ^= 1;
+    fs::write(cache.path_for(3), bytes).unwrap();
+
+    assert_eq!(cache.load(3).unwrap_err(), Error::FingerprintMismatch);
+    let regenerated = cache.get_or_insert_with(3, || gp.clone()).unwrap();
+    assert_eq!(regenerated.fingerprint(), gp.fingerprint());
+    assert!(cache.load(3).unwrap().is_some());
+    let _ = fs::remove_dir_all(cache.dir());
+}
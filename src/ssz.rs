@@ -0,0 +1,206 @@
+#[cfg(feature = "prover")]
+use crate::blob::Blob;
+use crate::degree::MaxDegree;
+use crate::error::Error;
+use crate::types::{Commitment, Proof};
+use sha2::{Digest, Sha256};
+
+// SSZ encodings of the consensus-spec KZG types, so commitments and proofs drop into beacon-chain containers.
+// `KZGCommitment` and `KZGProof` are `Bytes48`, a fixed-size byte vector, so their SSZ form is just the compressed
+// point. A list of them, like a block body's `blob_kzg_commitments`, is the elements concatenated with no length
+// prefix, since the enclosing container's offsets delimit it. A `Blob` is a fixed-size byte vector too. SSZ carries
+// no metadata, so decoded commitments take the degree of the parameters they were made with from the caller
+
+pub const BYTES48_SSZ_SIZE: usize = 48;
+// `MAX_BLOB_COMMITMENTS_PER_BLOCK` from the Deneb spec, the limit of `blob_kzg_commitments`
+pub const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize = 4096;
+
+fn bytes48(bytes: &[u8]) -> Result<&[u8; 48], Error> {
+    bytes
+        .try_into()
+        .map_err(|_| Error::InvalidEncoding("SSZ Bytes48 must be exactly 48 bytes"))
+}
+
+pub fn commitment_to_ssz(commitment: &Commitment) -> [u8; BYTES48_SSZ_SIZE] {
+    commitment.point_bytes()
+}
+
+pub fn commitment_from_ssz(bytes: &[u8], max_degree: MaxDegree) -> Result<Commitment, Error> {
+    Commitment::from_point_bytes(bytes48(bytes)?, max_degree)
+}
+
+pub fn proof_to_ssz(proof: &Proof) -> [u8; BYTES48_SSZ_SIZE] {
+    proof.to_bytes()
+}
+
+pub fn proof_from_ssz(bytes: &[u8]) -> Result<Proof, Error> {
+    Proof::from_bytes(bytes48(bytes)?)
+}
+
+// `List[KZGCommitment, limit]`
+pub fn commitments_to_ssz(commitments: &[Commitment]) -> Vec<u8> {
+    commitments.iter().flat_map(commitment_to_ssz).collect()
+}
+
+// Errs on a length that isn't a whole number of commitments or exceeds `limit`, or with the index of the first
+// invalid point
+pub fn commitments_from_ssz(
+    bytes: &[u8],
+    max_degree: MaxDegree,
+    limit: usize,
+) -> Result<Vec<Commitment>, Error> {
+    if bytes.len() % BYTES48_SSZ_SIZE != 0 {
+        return Err(Error::InvalidEncoding(
+            "SSZ commitment list isn't a multiple of 48 bytes",
+        ));
+    }
+    if bytes.len() / BYTES48_SSZ_SIZE > limit {
+        return Err(Error::InvalidEncoding(
+            "SSZ commitment list exceeds its limit",
+        ));
+    }
+    bytes
+        .chunks_exact(BYTES48_SSZ_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            commitment_from_ssz(chunk, max_degree).map_err(|error| match error {
+                Error::InvalidPoint { group, .. } => Error::InvalidPoint { group, index },
+                error => error,
+            })
+        })
+        .collect()
+}
+
+// `hash_tree_root` of a `Bytes48`: packed into two 32-byte chunks, the second zero padded, and merkleized
+pub fn bytes48_hash_tree_root(bytes: &[u8; BYTES48_SSZ_SIZE]) -> [u8; 32] {
+    let mut chunks = [0u8; 64];
+    chunks[..BYTES48_SSZ_SIZE].copy_from_slice(bytes);
+    Sha256::digest(chunks).into()
+}
+
+// `Blob` is `ByteVector[BYTES_PER_BLOB]`, so like `Bytes48` its SSZ form is just the bytes
+#[cfg(feature = "prover")]
+pub fn blob_to_ssz(blob: &Blob) -> Vec<u8> {
+    blob.to_bytes()
+}
+
+// Errs on any length but `BYTES_PER_BLOB`, or with the index of the first element that isn't reduced mod r
+#[cfg(feature = "prover")]
+pub fn blob_from_ssz(bytes: &[u8]) -> Result<Blob, Error> {
+    Blob::from_bytes(bytes)
+}
+
+// `hash_tree_root` of a `Blob`: its 4096 32-byte chunks, one per field element, merkleized in a tree of depth 12
+#[cfg(feature = "prover")]
+pub fn blob_hash_tree_root(blob: &Blob) -> [u8; 32] {
+    let mut layer: Vec<[u8; 32]> = blob
+        .to_bytes()
+        .chunks_exact(32)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect();
+    // 4096 is a power of two, so every layer pairs up
+    while layer.len() > 1 {
+        layer = layer
+            .chunks_exact(2)
+            .map(|pair| {
+                Sha256::new()
+                    .chain_update(pair[0])
+                    .chain_update(pair[1])
+                    .finalize()
+                    .into()
+            })
+            .collect();
+    }
+    layer[0]
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn round_trips_through_ssz() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+    use crate::types::FieldElement;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let max_degree = committer.setup_with_rng(2, &mut test_rng()).max_degree();
+    let commitments: Vec<Commitment> = (0..3)
+        .map(|i| {
+            let polynomial = Polynomial::new_from_i64(&[i, 2, 1]);
+            committer.commit_opaque(&polynomial).unwrap()
+        })
+        .collect();
+    let (proof, _) = committer
+        .prove(&Polynomial::new_from_i64(&[1, 2, 1]), FieldElement::from(5))
+        .unwrap();
+
+    assert_eq!(
+        commitment_from_ssz(&commitment_to_ssz(&commitments[0]), max_degree),
+        Ok(commitments[0])
+    );
+    assert_eq!(proof_from_ssz(&proof_to_ssz(&proof)), Ok(proof));
+
+    let list = commitments_to_ssz(&commitments);
+    assert_eq!(list.len(), 3 * 48);
+    assert_eq!(
+        commitments_from_ssz(&list, max_degree, MAX_BLOB_COMMITMENTS_PER_BLOCK),
+        Ok(commitments.clone())
+    );
+    assert!(commitments_from_ssz(&list, max_degree, 2).is_err());
+    assert!(commitments_from_ssz(&list[..100], max_degree, 4).is_err());
+
+    let mut corrupted = list.clone();
+    corrupted[48..96].fill(0);
+    assert_eq!(
+        commitments_from_ssz(&corrupted, max_degree, 4),
+        Err(Error::InvalidPoint {
+            group: "G1",
+            index: 1
+        })
+    );
+}
+
+#[test]
+fn merkleizes_bytes48() {
+    // Two chunks: the 48 bytes and 16 zeros of padding, hashed together
+    let bytes = [7u8; 48];
+    let mut expected = Sha256::new();
+    expected.update(bytes);
+    expected.update([0u8; 16]);
+    assert_eq!(
+        bytes48_hash_tree_root(&bytes),
+        <[u8; 32]>::from(expected.finalize())
+    );
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn encodes_and_merkleizes_blobs() {
+    use crate::blob::{BYTES_PER_BLOB, FIELD_ELEMENTS_PER_BLOB};
+
+    let mut bytes = vec![0u8; BYTES_PER_BLOB];
+    for (i, chunk) in bytes.chunks_exact_mut(32).enumerate() {
+        chunk[30..].copy_from_slice(&(i as u16).to_be_bytes());
+    }
+    let blob = blob_from_ssz(&bytes).unwrap();
+    assert_eq!(blob_to_ssz(&blob), bytes);
+    assert!(blob_from_ssz(&bytes[..BYTES_PER_BLOB - 1]).is_err());
+    bytes[..32].fill(0xff);
+    assert_eq!(
+        blob_from_ssz(&bytes).err(),
+        Some(Error::InvalidScalar { index: 0 })
+    );
+
+    // The zero blob's root is the zero hash at depth 12
+    let zero = blob_from_ssz(&[0; BYTES_PER_BLOB]).unwrap();
+    let mut expected = [0u8; 32];
+    for _ in 0..FIELD_ELEMENTS_PER_BLOB.trailing_zeros() {
+        expected = Sha256::new()
+            .chain_update(expected)
+            .chain_update(expected)
+            .finalize()
+            .into();
+    }
+    assert_eq!(blob_hash_tree_root(&zero), expected);
+    assert_ne!(blob_hash_tree_root(&blob), expected);
+}
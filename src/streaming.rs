@@ -0,0 +1,147 @@
+use crate::error::Error;
+use blstrs::{G1Projective, Scalar};
+use group::ff::Field;
+use group::Group;
+
+// A prover for machines that can't hold the whole polynomial and SRS at once, in the spirit of Gemini's elastic
+// prover. Coefficients (lowest degree first) and powers of tau are read as streams that can be restarted, e.g. by
+// reopening a file, and only `chunk_size` of each are held at a time. Committing is one pass. Opening is two: the
+// first evaluates $p(z)$, and the second recovers the quotient coefficients bottom-up from
+// $q_i = (q_{i-1} - p_i) / z$ with $q_{-1} = p(z)$, feeding them into the MSM a chunk at a time. With a chunk of
+// $\sqrt n$ that's $O(\sqrt n)$ memory for the price of a second pass
+pub struct StreamingProver<C, P> {
+    coefficients: C,
+    powers: P,
+    chunk_size: usize,
+}
+
+// A chunk size of about $\sqrt n$ for n coefficients, which balances memory against the number of partial MSMs
+pub fn sqrt_chunk_size(n: usize) -> usize {
+    ((n as f64).sqrt().ceil() as usize).max(1)
+}
+
+impl<C, CI, P, PI> StreamingProver<C, P>
+where
+    C: Fn() -> CI,
+    CI: Iterator<Item = Scalar>,
+    P: Fn() -> PI,
+    PI: Iterator<Item = G1Projective>,
+{
+    // `coefficients` and `powers` start a fresh pass each time they're called
+    pub fn new(coefficients: C, powers: P, chunk_size: usize) -> Self {
+        StreamingProver {
+            coefficients,
+            powers,
+            chunk_size: chunk_size.max(1),
+        }
+    }
+
+    // $\sum s_i [\tau^i]_1$ over a stream of scalars, one chunk at a time
+    fn msm(&self, scalars: impl Iterator<Item = Scalar>) -> Result<G1Projective, Error> {
+        let mut powers = (self.powers)();
+        let mut result = G1Projective::identity();
+        let mut bases = Vec::with_capacity(self.chunk_size);
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        let mut index = 0;
+        for scalar in scalars {
+            let power = powers.next().ok_or(Error::DegreeTooLarge {
+                max: index.max(1) - 1,
+                actual: index,
+            })?;
+            bases.push(power);
+            chunk.push(scalar);
+            index += 1;
+            if chunk.len() == self.chunk_size {
+                result += G1Projective::multi_exp(&bases, &chunk);
+                bases.clear();
+                chunk.clear();
+            }
+        }
+        if !chunk.is_empty() {
+            result += G1Projective::multi_exp(&bases, &chunk);
+        }
+        Ok(result)
+    }
+
+    // Any degree the powers cover. The zero polynomial commits to the identity
+    pub fn commit(&self) -> Result<G1Projective, Error> {
+        self.msm((self.coefficients)())
+    }
+
+    // The witness and evaluation at `point`
+    pub fn open(&self, point: Scalar) -> Result<(G1Projective, Scalar), Error> {
+        // First pass: $p(z)$, low to high with a running power of z
+        let mut evaluation = Scalar::ZERO;
+        let mut power = Scalar::ONE;
+        let mut len = 0;
+        for coefficient in (self.coefficients)() {
+            evaluation += coefficient * power;
+            power *= point;
+            len += 1;
+        }
+        if len < 2 {
+            return Ok((G1Projective::identity(), evaluation));
+        }
+
+        // Second pass: the quotient has one coefficient fewer than the polynomial
+        let quotient: Box<dyn Iterator<Item = Scalar>> =
+            match Option::<Scalar>::from(point.invert()) {
+                // At zero the quotient is the polynomial shifted down
+                None => Box::new((self.coefficients)().skip(1)),
+                Some(point_inv) => {
+                    let mut previous = evaluation;
+                    Box::new((self.coefficients)().take(len - 1).map(move |coefficient| {
+                        previous = (previous - coefficient) * point_inv;
+                        previous
+                    }))
+                }
+            };
+        Ok((self.msm(quotient)?, evaluation))
+    }
+}
+
+#[test]
+fn matches_the_in_memory_prover() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let gp = committer.setup_with_rng(9, &mut test_rng());
+    let polynomial = Polynomial::rand(9, &mut test_rng());
+
+    for chunk_size in [1, 3, sqrt_chunk_size(10), 64] {
+        let prover = StreamingProver::new(
+            || polynomial.iter().copied(),
+            || gp.gs().iter().copied(),
+            chunk_size,
+        );
+        assert_eq!(
+            prover.commit().unwrap(),
+            committer.commit(&polynomial).unwrap()
+        );
+        for point in [Scalar::ZERO, Scalar::ONE, Scalar::from(7)] {
+            let (witness, evaluation) = prover.open(point).unwrap();
+            assert_eq!(evaluation, polynomial.evaluate(point));
+            assert!(committer
+                .verify_evaluation(
+                    committer.commit(&polynomial).unwrap(),
+                    point,
+                    evaluation,
+                    witness
+                )
+                .unwrap());
+        }
+    }
+
+    // More coefficients than powers of tau
+    let too_long = StreamingProver::new(
+        || polynomial.iter().copied().chain([Scalar::ONE]),
+        || gp.gs().iter().copied(),
+        4,
+    );
+    assert_eq!(
+        too_long.commit(),
+        Err(Error::DegreeTooLarge { max: 9, actual: 10 })
+    );
+}
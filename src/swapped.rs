@@ -0,0 +1,120 @@
+use crate::constant_time::ct_eq_gt;
+use crate::error::Error;
+use crate::polynomial_commitments::{
+    GenericPolynomialCommitment, GlobalParameters, PolynomialCommitment,
+};
+use crate::polynomials::Polynomial;
+use blstrs::{pairing, G1Projective, G2Projective, Scalar};
+use group::{Curve, Group};
+use rand::{CryptoRng, RngCore};
+
+// KZG with the groups swapped: commitments are $[p(\tau)]_2$ in G2 and witnesses stay in G1, so the check becomes
+// $e(w, [\tau]_2 - z \cdot h) = e(g, C - y \cdot h)$. For protocols that need the commitment on the G2 side of a
+// pairing to compose with their other checks. Uses the same global parameters as `GenericPolynomialCommitment`
+#[derive(Debug)]
+pub struct SwappedPolynomialCommitment {
+    inner: GenericPolynomialCommitment,
+}
+
+impl SwappedPolynomialCommitment {
+    pub fn new() -> Self {
+        SwappedPolynomialCommitment {
+            inner: GenericPolynomialCommitment::new(),
+        }
+    }
+
+    pub fn with_global_parameters(global_parameters: GlobalParameters) -> Self {
+        SwappedPolynomialCommitment {
+            inner: GenericPolynomialCommitment::with_global_parameters(global_parameters),
+        }
+    }
+
+    pub fn setup_with_rng<R: RngCore + CryptoRng>(
+        &mut self,
+        d: usize,
+        rng: &mut R,
+    ) -> GlobalParameters {
+        self.inner.setup_with_rng(d, rng)
+    }
+
+    // Same degree requirement as `commit`, but against the G2 powers
+    pub fn commit(&self, polynomial: &Polynomial) -> Result<G2Projective, Error> {
+        let global_parameters = self.inner.global_parameters()?;
+        let expected = global_parameters.hs().len() - 1;
+        match polynomial.degree() {
+            None => Err(Error::ZeroPolynomial),
+            Some(degree) if degree != expected => Err(Error::IncorrectDegree {
+                expected,
+                actual: degree,
+            }),
+            Some(_) => global_parameters.commit_in_g2(polynomial.coeffs()),
+        }
+    }
+
+    // The witness is the usual G1 witness
+    pub fn create_witness(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        self.inner.create_witness(polynomial, point)
+    }
+
+    pub fn verify_evaluation(
+        &self,
+        committed_polynomial: G2Projective,
+        point: Scalar,
+        evaluation: Scalar,
+        witness: G1Projective,
+    ) -> Result<bool, Error> {
+        let global_parameters = self.inner.global_parameters()?;
+        if global_parameters.hs().len() < 2 {
+            return Err(Error::InvalidParameters("verification needs tau in G2"));
+        }
+        if bool::from(committed_polynomial.is_identity()) {
+            return Err(Error::IdentityCommitment);
+        }
+        if bool::from(witness.is_identity()) {
+            return Err(Error::IdentityWitness);
+        }
+
+        let g2 = global_parameters.hs()[0];
+        let lhs = pairing(
+            &witness.to_affine(),
+            &(global_parameters.hs()[1] - g2 * point).to_affine(),
+        );
+        let rhs = pairing(
+            &global_parameters.gs()[0].to_affine(),
+            &(committed_polynomial - g2 * evaluation).to_affine(),
+        );
+        Ok(ct_eq_gt(&lhs, &rhs).into())
+    }
+}
+
+#[test]
+fn commits_in_g2_and_verifies() {
+    use crate::rng::test_rng;
+
+    let mut committer = SwappedPolynomialCommitment::new();
+    committer.setup_with_rng(3, &mut test_rng());
+    let polynomial = Polynomial::new_from_i64(&[-1, 3, -4, 1]);
+    let point = Scalar::from(5);
+
+    let commitment = committer.commit(&polynomial).unwrap();
+    let (witness, evaluation) = committer.create_witness(&polynomial, point).unwrap();
+    assert_eq!(
+        committer.verify_evaluation(commitment, point, evaluation, witness),
+        Ok(true)
+    );
+    assert_eq!(
+        committer.verify_evaluation(commitment, point, evaluation + Scalar::from(1), witness),
+        Ok(false)
+    );
+    assert_eq!(
+        committer.commit(&Polynomial::new_from_i64(&[1, 2])),
+        Err(Error::IncorrectDegree {
+            expected: 3,
+            actual: 1
+        })
+    );
+}
@@ -0,0 +1,167 @@
+use crate::error::Error;
+use crate::polynomial_commitments::GenericPolynomialCommitment;
+use crate::polynomials::Polynomial;
+use crate::secret::{zeroize_scalars, SecretPolynomial};
+use blstrs::{G1Projective, Scalar};
+use group::ff::Field;
+use rand::{CryptoRng, RngCore};
+
+// Distributed opening proofs. Every coefficient of the committed polynomial is Shamir-shared, so party j holds the
+// polynomial $p_j$ whose coefficients are its shares. The quotient $(p(x) - p(z)) / (x - z)$ is linear in p, so each
+// party opens its own share polynomial, and any `threshold` partial openings combine with Lagrange coefficients at zero
+// into the witness and evaluation for p itself. No party ever sees p
+
+pub struct PolynomialShare {
+    // The party's evaluation point for the sharing, never zero
+    index: u64,
+    polynomial: SecretPolynomial,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PartialOpening {
+    pub index: u64,
+    pub witness: G1Projective,
+    pub evaluation: Scalar,
+}
+
+// Dealer side: split a polynomial between `parties` parties numbered 1 to `parties` so that any `threshold` of them
+// can open it
+pub fn share_polynomial<R: RngCore + CryptoRng>(
+    polynomial: &Polynomial,
+    threshold: usize,
+    parties: u64,
+    rng: &mut R,
+) -> Result<Vec<PolynomialShare>, Error> {
+    if threshold == 0 || threshold as u64 > parties {
+        return Err(Error::InvalidShares(
+            "threshold must be between one and the number of parties",
+        ));
+    }
+    // One sharing polynomial per coefficient, with the coefficient as its constant term
+    let mut sharings: Vec<Polynomial> = polynomial
+        .iter()
+        .map(|coefficient| {
+            let mut sharing = vec![*coefficient];
+            sharing.extend((1..threshold).map(|_| Scalar::random(&mut *rng)));
+            Polynomial::from_coeffs_untrimmed(sharing)
+        })
+        .collect();
+
+    let shares = (1..=parties)
+        .map(|index| {
+            let x = Scalar::from(index);
+            let coefficients: Vec<Scalar> =
+                sharings.iter().map(|sharing| sharing.evaluate(x)).collect();
+            PolynomialShare {
+                index,
+                polynomial: SecretPolynomial::new(Polynomial::new(&coefficients)),
+            }
+        })
+        .collect();
+    for sharing in sharings.iter_mut() {
+        zeroize_scalars(sharing.coeffs_mut());
+    }
+    Ok(shares)
+}
+
+impl PolynomialShare {
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    // This party's contribution to an opening at `point`
+    pub fn partial_open(
+        &self,
+        committer: &GenericPolynomialCommitment,
+        point: Scalar,
+    ) -> Result<PartialOpening, Error> {
+        let (witness, evaluation) = committer.open(self.polynomial.expose_secret(), point)?;
+        Ok(PartialOpening {
+            index: self.index,
+            witness,
+            evaluation,
+        })
+    }
+}
+
+// Combines partial openings from distinct parties into a witness and evaluation for the shared polynomial. With fewer
+// than the threshold the result is garbage and fails verification
+pub fn combine_partial_openings(
+    partials: &[PartialOpening],
+) -> Result<(G1Projective, Scalar), Error> {
+    if partials.is_empty() {
+        return Err(Error::InvalidShares("no partial openings to combine"));
+    }
+    for (i, partial) in partials.iter().enumerate() {
+        if partial.index == 0 || partials[..i].iter().any(|p| p.index == partial.index) {
+            return Err(Error::InvalidShares(
+                "party indices must be nonzero and distinct",
+            ));
+        }
+    }
+
+    // $\lambda_j = \prod_{k \ne j} x_k / (x_k - x_j)$
+    let xs: Vec<Scalar> = partials.iter().map(|p| Scalar::from(p.index)).collect();
+    let lagrange: Vec<Scalar> = xs
+        .iter()
+        .enumerate()
+        .map(|(j, x_j)| {
+            let (numerator, denominator) = xs
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| *k != j)
+                .fold((Scalar::ONE, Scalar::ONE), |(num, den), (_, x_k)| {
+                    (num * x_k, den * (*x_k - x_j))
+                });
+            // Can unwrap since the indices are distinct
+            numerator * Option::<Scalar>::from(denominator.invert()).unwrap()
+        })
+        .collect();
+
+    let witnesses: Vec<G1Projective> = partials.iter().map(|p| p.witness).collect();
+    let witness = G1Projective::multi_exp(&witnesses, &lagrange);
+    let evaluation = partials
+        .iter()
+        .zip(&lagrange)
+        .map(|(p, lambda)| p.evaluation * lambda)
+        .sum();
+    Ok((witness, evaluation))
+}
+
+#[test]
+fn threshold_of_parties_can_open() {
+    use crate::polynomial_commitments::PolynomialCommitment;
+    use crate::rng::test_rng;
+
+    let mut rng = test_rng();
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(5, &mut rng);
+
+    let polynomial = Polynomial::rand(5, &mut rng);
+    let commitment = committer.commit(&polynomial).unwrap();
+    let shares = share_polynomial(&polynomial, 3, 5, &mut rng).unwrap();
+    let point = Scalar::from(11);
+
+    let partials: Vec<PartialOpening> = [0, 2, 4]
+        .iter()
+        .map(|&i| shares[i].partial_open(&committer, point).unwrap())
+        .collect();
+    let (witness, evaluation) = combine_partial_openings(&partials).unwrap();
+    assert_eq!(evaluation, polynomial.evaluate(point));
+    assert_eq!(
+        committer.verify_evaluation(commitment, point, evaluation, witness),
+        Ok(true)
+    );
+
+    // Two parties aren't enough
+    let (witness, evaluation) = combine_partial_openings(&partials[..2]).unwrap();
+    assert_ne!(evaluation, polynomial.evaluate(point));
+    assert_eq!(
+        committer.verify_evaluation(commitment, point, polynomial.evaluate(point), witness),
+        Ok(false)
+    );
+
+    let duplicated = [partials[0], partials[0]];
+    assert!(combine_partial_openings(&duplicated).is_err());
+    assert!(share_polynomial(&polynomial, 6, 5, &mut rng).is_err());
+}
@@ -0,0 +1,122 @@
+use crate::domain::EvaluationDomain;
+use crate::error::Error;
+use blstrs::{G1Projective, Scalar};
+use group::ff::Field;
+
+// An n×n Toeplitz matrix of scalars, $T_{ij} = t_{i-j}$, times a vector of G1 points in $O(n \log n)$. FK20 and
+// several batch-proof algorithms reduce to this with the points being the powers of tau. T embeds in a circulant of
+// size $m \ge 2n - 1$ whose first column is $t_0, \ldots, t_{n-1}$, zeros, then $t_{-(n-1)}, \ldots, t_{-1}$, and a
+// circulant is diagonalised by the FFT: $C x = \mathrm{IFFT}(\mathrm{FFT}(c) \odot \mathrm{FFT}(x))$. The first n
+// entries of C times x padded with zeros are T x
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ToeplitzMatrix {
+    // The first column, $t_0, \ldots, t_{n-1}$, and the first row, $t_0, t_{-1}, \ldots, t_{-(n-1)}$
+    column: Vec<Scalar>,
+    row: Vec<Scalar>,
+}
+
+// The FFT of a point vector padded for one matrix size, so a fixed vector like the SRS is only transformed once
+#[derive(Clone, Debug)]
+pub struct PreparedPoints {
+    len: usize,
+    domain: EvaluationDomain,
+    evaluations: Vec<G1Projective>,
+}
+
+fn circulant_domain(n: usize) -> Result<EvaluationDomain, Error> {
+    EvaluationDomain::new(2 * n - 1).ok_or(Error::InvalidParameters(
+        "Toeplitz matrix is too large for an evaluation domain",
+    ))
+}
+
+impl ToeplitzMatrix {
+    // `None` unless both are the same nonzero length and agree on the diagonal
+    pub fn new(column: Vec<Scalar>, row: Vec<Scalar>) -> Option<Self> {
+        if column.is_empty() || column.len() != row.len() || column[0] != row[0] {
+            return None;
+        }
+        Some(ToeplitzMatrix { column, row })
+    }
+
+    pub fn size(&self) -> usize {
+        self.column.len()
+    }
+
+    pub fn mul_points(&self, points: &[G1Projective]) -> Result<Vec<G1Projective>, Error> {
+        self.mul_prepared(&PreparedPoints::new(points)?)
+    }
+
+    pub fn mul_prepared(&self, points: &PreparedPoints) -> Result<Vec<G1Projective>, Error> {
+        let n = self.size();
+        if points.len != n {
+            return Err(Error::DegreeMismatch {
+                expected: n,
+                actual: points.len,
+            });
+        }
+        let domain = &points.domain;
+        let mut circulant = vec![Scalar::ZERO; domain.size()];
+        circulant[..n].copy_from_slice(&self.column);
+        for (k, entry) in self.row.iter().enumerate().skip(1) {
+            circulant[domain.size() - k] = *entry;
+        }
+        let products: Vec<G1Projective> = domain
+            .fft(&circulant)
+            .iter()
+            .zip(&points.evaluations)
+            .map(|(scalar, point)| point * scalar)
+            .collect();
+        let mut result = domain.ifft_g1(&products);
+        result.truncate(n);
+        Ok(result)
+    }
+}
+
+impl PreparedPoints {
+    pub fn new(points: &[G1Projective]) -> Result<Self, Error> {
+        if points.is_empty() {
+            return Err(Error::InvalidParameters("no points to multiply"));
+        }
+        let domain = circulant_domain(points.len())?;
+        Ok(PreparedPoints {
+            len: points.len(),
+            evaluations: domain.fft_g1(points),
+            domain,
+        })
+    }
+}
+
+#[test]
+fn matches_the_direct_product() {
+    use crate::rng::test_rng;
+    use group::Group;
+
+    let mut rng = test_rng();
+    for n in [1, 3, 4, 7] {
+        let column: Vec<Scalar> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+        let mut row: Vec<Scalar> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+        row[0] = column[0];
+        let matrix = ToeplitzMatrix::new(column.clone(), row.clone()).unwrap();
+
+        // Points with known discrete logs, so the expected product can be computed in the exponent
+        let logs: Vec<Scalar> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+        let points: Vec<G1Projective> = logs
+            .iter()
+            .map(|log| G1Projective::generator() * log)
+            .collect();
+        let expected: Vec<G1Projective> = (0..n)
+            .map(|i| {
+                let entry = |j: usize| if i >= j { column[i - j] } else { row[j - i] };
+                G1Projective::generator() * (0..n).map(|j| entry(j) * logs[j]).sum::<Scalar>()
+            })
+            .collect();
+
+        assert_eq!(matrix.mul_points(&points).unwrap(), expected);
+        let prepared = PreparedPoints::new(&points).unwrap();
+        assert_eq!(matrix.mul_prepared(&prepared).unwrap(), expected);
+    }
+
+    let matrix = ToeplitzMatrix::new(vec![Scalar::ONE; 3], vec![Scalar::ONE; 3]).unwrap();
+    assert!(matrix.mul_points(&[G1Projective::generator(); 2]).is_err());
+    assert!(ToeplitzMatrix::new(vec![Scalar::ONE], vec![Scalar::ZERO]).is_none());
+}
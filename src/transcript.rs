@@ -0,0 +1,226 @@
+use crate::error::Error;
+use crate::scalars::scalar_from_biguint;
+use blstrs::{G1Projective, Scalar};
+use group::Curve;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+
+// Domain separation tag for challenges squeezed out of a `Sha256Transcript`
+const CHALLENGE_DST: &[u8] = b"KZG_TRANSCRIPT_CHALLENGE_V1";
+
+// A Fiat-Shamir transcript. Prover and verifier absorb the same public values in the same order and derive challenges
+// from everything absorbed so far. Transcripts start from a protocol label and every message carries its own label,
+// so a proof made for one application or proof type can't be replayed as a proof of another
+pub trait Transcript {
+    fn append_bytes(&mut self, label: &'static [u8], bytes: &[u8]);
+
+    // Must depend on everything absorbed so far, and must itself be absorbed so later challenges depend on it
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar;
+
+    // Scalars are absorbed as 32 little-endian bytes
+    fn append_scalar(&mut self, label: &'static [u8], scalar: &Scalar) {
+        self.append_bytes(label, &scalar.to_bytes_le());
+    }
+
+    // Points are absorbed in their 48-byte compressed form
+    fn append_g1(&mut self, label: &'static [u8], point: &G1Projective) {
+        self.append_bytes(label, &point.to_affine().to_compressed());
+    }
+}
+
+// The transcript protocols in this crate use unless told otherwise
+pub type DefaultTranscript = Sha256Transcript;
+
+// A transcript built from nothing but SHA-256, so other implementations can reproduce it without a STROBE or merlin
+// port. With `u64le(n)` the 8-byte little-endian encoding of n:
+//
+// - the state is a running SHA-256 hash. `new(protocol)` starts it and absorbs ("protocol", protocol)
+// - absorbing (label, message) feeds `u64le(len(label)) || label || u64le(len(message)) || message` into the hash
+// - `challenge_scalar(label)` absorbs ("challenge", label), takes the digest d of the state so far without resetting
+//   it, outputs `hash_to_scalar("KZG_TRANSCRIPT_CHALLENGE_V1", d)`, then absorbs ("challenge digest", d)
+#[derive(Clone)]
+pub struct Sha256Transcript {
+    state: Sha256,
+}
+
+impl Sha256Transcript {
+    // The protocol label must be non-empty; it's a fixed string, so an empty one is a bug in the caller
+    pub fn new(protocol: &'static [u8]) -> Self {
+        assert!(!protocol.is_empty(), "a protocol label is required");
+        let mut transcript = Sha256Transcript {
+            state: Sha256::new(),
+        };
+        transcript.absorb(b"protocol", protocol);
+        transcript
+    }
+
+    fn absorb(&mut self, label: &[u8], bytes: &[u8]) {
+        self.state.update((label.len() as u64).to_le_bytes());
+        self.state.update(label);
+        self.state.update((bytes.len() as u64).to_le_bytes());
+        self.state.update(bytes);
+    }
+}
+
+impl Transcript for Sha256Transcript {
+    fn append_bytes(&mut self, label: &'static [u8], bytes: &[u8]) {
+        self.absorb(label, bytes);
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar {
+        self.absorb(b"challenge", label);
+        let digest = self.state.clone().finalize();
+        // Can unwrap since the tag isn't empty
+        let challenge = hash_to_scalar(CHALLENGE_DST, &digest).unwrap();
+        self.absorb(b"challenge digest", &digest);
+        challenge
+    }
+}
+
+// Hashes a message to a scalar under a domain separation tag, which must be unique to the caller's purpose. Errs if
+// the tag is empty. The output is `SHA-256(u64le(len(dst)) || dst || message || i)` for i = 0 and 1, concatenated and
+// read as a 512-bit little-endian integer mod r, so the result is statistically close to uniform
+pub fn hash_to_scalar(dst: &[u8], message: &[u8]) -> Result<Scalar, Error> {
+    if dst.is_empty() {
+        return Err(Error::InvalidParameters(
+            "a domain separation tag is required",
+        ));
+    }
+    let mut wide = Vec::with_capacity(64);
+    for i in 0..2u8 {
+        let block = Sha256::new()
+            .chain_update((dst.len() as u64).to_le_bytes())
+            .chain_update(dst)
+            .chain_update(message)
+            .chain_update([i])
+            .finalize();
+        wide.extend_from_slice(&block);
+    }
+    Ok(scalar_from_biguint(&BigUint::from_bytes_le(&wide)))
+}
+
+// A claim that the polynomial committed to by `commitment` evaluates to `evaluation` at `point`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OpeningClaim {
+    pub commitment: G1Projective,
+    pub point: Scalar,
+    pub evaluation: Scalar,
+}
+
+// The challenge for batching or aggregating openings. Batched verifiers are only sound with "strong" Fiat-Shamir: if
+// the challenge were derived before a commitment, point or evaluation was fixed, a prover could pick that value after
+// seeing the challenge and make a false claim cancel out. This absorbs every claim in full before squeezing, so
+// protocols should derive their challenges through here rather than calling `challenge_scalar` directly
+pub fn opening_challenge<T: Transcript>(transcript: &mut T, claims: &[OpeningClaim]) -> Scalar {
+    transcript.append_bytes(b"claims", &(claims.len() as u64).to_le_bytes());
+    for claim in claims {
+        transcript.append_g1(b"commitment", &claim.commitment);
+        transcript.append_scalar(b"point", &claim.point);
+        transcript.append_scalar(b"evaluation", &claim.evaluation);
+    }
+    transcript.challenge_scalar(b"opening challenge")
+}
+
+#[test]
+fn challenges_are_deterministic_and_chained() {
+    let mut a = Sha256Transcript::new(b"test");
+    let mut b = Sha256Transcript::new(b"test");
+    a.append_scalar(b"x", &Scalar::from(7));
+    b.append_scalar(b"x", &Scalar::from(7));
+    let first = a.challenge_scalar(b"c");
+    assert_eq!(first, b.challenge_scalar(b"c"));
+
+    // Squeezing again gives a fresh challenge
+    assert_ne!(first, a.challenge_scalar(b"c"));
+}
+
+#[test]
+fn sha256_transcript_matches_its_specification() {
+    use crate::scalars::scalar_to_biguint;
+
+    // Computed independently from the description above
+    let mut transcript = Sha256Transcript::new(b"test");
+    transcript.append_scalar(b"x", &Scalar::from(7));
+    let expected = BigUint::parse_bytes(
+        b"344e50086e2c9ea06da0a93d11023ea46463384f3c9f2b1420e3f880aded7b78",
+        16,
+    )
+    .unwrap();
+    assert_eq!(
+        scalar_to_biguint(&transcript.challenge_scalar(b"c")),
+        expected
+    );
+}
+
+#[test]
+fn labels_separate_domains() {
+    let challenge = |protocol: &'static [u8], label: &'static [u8]| {
+        let mut transcript = Sha256Transcript::new(protocol);
+        transcript.append_scalar(label, &Scalar::from(7));
+        transcript.challenge_scalar(b"c")
+    };
+    assert_ne!(challenge(b"app one", b"x"), challenge(b"app two", b"x"));
+    assert_ne!(challenge(b"app one", b"x"), challenge(b"app one", b"y"));
+
+    assert_ne!(
+        hash_to_scalar(b"one", b"message"),
+        hash_to_scalar(b"two", b"message")
+    );
+    assert_eq!(
+        hash_to_scalar(b"one", b"message"),
+        hash_to_scalar(b"one", b"message")
+    );
+    assert_eq!(
+        hash_to_scalar(b"", b"message"),
+        Err(Error::InvalidParameters(
+            "a domain separation tag is required"
+        ))
+    );
+}
+
+#[test]
+fn opening_challenge_binds_every_claimed_value() {
+    use group::Group;
+
+    let claim = OpeningClaim {
+        commitment: G1Projective::generator(),
+        point: Scalar::from(3),
+        evaluation: Scalar::from(5),
+    };
+    let challenge = opening_challenge(&mut Sha256Transcript::new(b"test"), &[claim]);
+    assert_eq!(
+        challenge,
+        opening_challenge(&mut Sha256Transcript::new(b"test"), &[claim])
+    );
+
+    let altered = [
+        OpeningClaim {
+            commitment: G1Projective::generator().double(),
+            ..claim
+        },
+        OpeningClaim {
+            point: Scalar::from(4),
+            ..claim
+        },
+        OpeningClaim {
+            evaluation: Scalar::from(6),
+            ..claim
+        },
+    ];
+    for other in altered {
+        assert_ne!(
+            challenge,
+            opening_challenge(&mut Sha256Transcript::new(b"test"), &[other])
+        );
+    }
+    assert_ne!(
+        challenge,
+        opening_challenge(&mut Sha256Transcript::new(b"test"), &[claim, claim])
+    );
+}
+
+#[test]
+#[should_panic(expected = "a protocol label is required")]
+fn rejects_an_empty_protocol_label() {
+    Sha256Transcript::new(b"");
+}
@@ -0,0 +1,327 @@
+use crate::degree::MaxDegree;
+#[cfg(feature = "prover")]
+use crate::domain_polynomial::DomainPolynomial;
+use crate::error::Error;
+#[cfg(feature = "prover")]
+use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+#[cfg(feature = "prover")]
+use crate::polynomials::Polynomial;
+use blstrs::{G1Affine, G1Projective, Scalar};
+use group::Curve;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// Types for callers who shouldn't need a version-matched blstrs: commitments, proofs and field elements are opaque
+// and only cross the API as this crate's own types or as bytes. Nothing here promises which curve library is behind
+// them, so the backend can change without breaking code written against these
+
+// The compressed point, the max degree as a little-endian u64, then the domain size as a little-endian u64 with
+// `u64::MAX` for no domain
+pub const COMMITMENT_SIZE: usize = 48 + 8 + 8;
+pub const PROOF_SIZE: usize = 48;
+pub const FIELD_ELEMENT_SIZE: usize = 32;
+
+// An element of the BLS12-381 scalar field
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FieldElement(Scalar);
+
+impl FieldElement {
+    pub fn zero() -> Self {
+        FieldElement(Scalar::from(0))
+    }
+
+    pub fn one() -> Self {
+        FieldElement(Scalar::from(1))
+    }
+
+    // 32 bytes little-endian
+    pub fn to_bytes(&self) -> [u8; FIELD_ELEMENT_SIZE] {
+        self.0.to_bytes_le()
+    }
+
+    // Rejects values that aren't reduced mod r
+    pub fn from_bytes(bytes: &[u8; FIELD_ELEMENT_SIZE]) -> Result<Self, Error> {
+        Option::<Scalar>::from(Scalar::from_bytes_le(bytes))
+            .map(FieldElement)
+            .ok_or(Error::InvalidScalar { index: 0 })
+    }
+
+    pub(crate) fn inner(&self) -> Scalar {
+        self.0
+    }
+
+    pub(crate) fn from_inner(scalar: Scalar) -> Self {
+        FieldElement(scalar)
+    }
+}
+
+impl From<u64> for FieldElement {
+    fn from(value: u64) -> Self {
+        FieldElement(Scalar::from(value))
+    }
+}
+
+impl Add for FieldElement {
+    type Output = FieldElement;
+
+    fn add(self, other: FieldElement) -> FieldElement {
+        FieldElement(self.0 + other.0)
+    }
+}
+
+impl Sub for FieldElement {
+    type Output = FieldElement;
+
+    fn sub(self, other: FieldElement) -> FieldElement {
+        FieldElement(self.0 - other.0)
+    }
+}
+
+impl Mul for FieldElement {
+    type Output = FieldElement;
+
+    fn mul(self, other: FieldElement) -> FieldElement {
+        FieldElement(self.0 * other.0)
+    }
+}
+
+impl Neg for FieldElement {
+    type Output = FieldElement;
+
+    fn neg(self) -> FieldElement {
+        FieldElement(-self.0)
+    }
+}
+
+fn g1_to_bytes(point: &G1Projective) -> [u8; 48] {
+    point.to_affine().to_compressed()
+}
+
+fn g1_from_bytes(bytes: &[u8; 48]) -> Result<G1Projective, Error> {
+    Option::<G1Affine>::from(G1Affine::from_compressed(bytes))
+        .map(G1Projective::from)
+        .ok_or(Error::InvalidPoint {
+            group: "G1",
+            index: 0,
+        })
+}
+
+const NO_DOMAIN: u64 = u64::MAX;
+
+// Carries the degree of the parameters it was made with, and the size of the evaluation domain when the committed
+// polynomial was defined by its values over one, so verifying it against a key for other parameters or another
+// domain is an error rather than a silent `false`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Commitment {
+    point: G1Projective,
+    max_degree: MaxDegree,
+    domain_size: Option<usize>,
+}
+
+impl Commitment {
+    pub fn max_degree(&self) -> MaxDegree {
+        self.max_degree
+    }
+
+    pub fn domain_size(&self) -> Option<usize> {
+        self.domain_size
+    }
+
+    // Just the point, compressed in the ZCash format, for interop with libraries that don't know the metadata
+    pub fn point_bytes(&self) -> [u8; 48] {
+        g1_to_bytes(&self.point)
+    }
+
+    // For points from encodings that carry no metadata, with the degree of the parameters they're known to be for
+    pub fn from_point_bytes(bytes: &[u8; 48], max_degree: MaxDegree) -> Result<Self, Error> {
+        Ok(Commitment {
+            point: g1_from_bytes(bytes)?,
+            max_degree,
+            domain_size: None,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; COMMITMENT_SIZE] {
+        let mut bytes = [0u8; COMMITMENT_SIZE];
+        bytes[..48].copy_from_slice(&self.point_bytes());
+        bytes[48..56].copy_from_slice(&(self.max_degree.get() as u64).to_le_bytes());
+        let domain_size = self.domain_size.map_or(NO_DOMAIN, |size| size as u64);
+        bytes[56..].copy_from_slice(&domain_size.to_le_bytes());
+        bytes
+    }
+
+    // Rejects points that aren't on the curve or not in the prime order subgroup
+    pub fn from_bytes(bytes: &[u8; COMMITMENT_SIZE]) -> Result<Self, Error> {
+        let point = g1_from_bytes(bytes[..48].try_into().unwrap())?;
+        let max_degree = u64::from_le_bytes(bytes[48..56].try_into().unwrap());
+        let domain_size = u64::from_le_bytes(bytes[56..].try_into().unwrap());
+        Ok(Commitment {
+            point,
+            max_degree: MaxDegree::new(max_degree as usize),
+            domain_size: (domain_size != NO_DOMAIN).then_some(domain_size as usize),
+        })
+    }
+
+    pub(crate) fn inner(&self) -> G1Projective {
+        self.point
+    }
+
+    pub(crate) fn from_parts(
+        point: G1Projective,
+        max_degree: MaxDegree,
+        domain_size: Option<usize>,
+    ) -> Self {
+        Commitment {
+            point,
+            max_degree,
+            domain_size,
+        }
+    }
+
+    // The same metadata on another point
+    pub(crate) fn with_point(self, point: G1Projective) -> Self {
+        Commitment { point, ..self }
+    }
+}
+
+// The witness for one opening. The value it proves travels alongside as a `FieldElement`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Proof(G1Projective);
+
+impl Proof {
+    pub fn to_bytes(&self) -> [u8; PROOF_SIZE] {
+        g1_to_bytes(&self.0)
+    }
+
+    pub fn from_bytes(bytes: &[u8; PROOF_SIZE]) -> Result<Self, Error> {
+        g1_from_bytes(bytes).map(Proof)
+    }
+
+    pub(crate) fn inner(&self) -> G1Projective {
+        self.0
+    }
+
+    pub(crate) fn from_inner(point: G1Projective) -> Self {
+        Proof(point)
+    }
+}
+
+#[cfg(feature = "prover")]
+impl Polynomial {
+    // Coefficients lowest degree first
+    pub fn from_field_elements(coefficients: &[FieldElement]) -> Self {
+        let coefficients: Vec<Scalar> = coefficients.iter().map(FieldElement::inner).collect();
+        Polynomial::new(&coefficients)
+    }
+
+    pub fn evaluate_at(&self, point: FieldElement) -> FieldElement {
+        FieldElement(self.evaluate(point.inner()))
+    }
+}
+
+#[cfg(feature = "prover")]
+// The scheme in terms of the opaque types. Same checks and errors as the `PolynomialCommitment` methods they wrap
+impl GenericPolynomialCommitment {
+    pub fn commit_opaque(&self, polynomial: &Polynomial) -> Result<Commitment, Error> {
+        Ok(Commitment {
+            point: self.commit(polynomial)?,
+            max_degree: self.max_degree()?,
+            domain_size: None,
+        })
+    }
+
+    // Records the polynomial's domain in the commitment, so it only verifies against a key for that domain
+    pub fn commit_on_domain(&self, polynomial: &DomainPolynomial) -> Result<Commitment, Error> {
+        Ok(Commitment {
+            domain_size: Some(polynomial.domain().size()),
+            ..self.commit_opaque(polynomial.coefficients())?
+        })
+    }
+
+    pub fn prove(
+        &self,
+        polynomial: &Polynomial,
+        point: FieldElement,
+    ) -> Result<(Proof, FieldElement), Error> {
+        let (witness, evaluation) = self.create_witness(polynomial, point.inner())?;
+        Ok((Proof(witness), FieldElement(evaluation)))
+    }
+
+    // Through the parameters' verifier key, so it accepts exactly the commitments that key does. That key has no
+    // domain; commitments made over one verify against `verifier_key().with_domain_size(..)` instead
+    pub fn verify(
+        &self,
+        commitment: &Commitment,
+        point: FieldElement,
+        evaluation: FieldElement,
+        proof: &Proof,
+    ) -> Result<bool, Error> {
+        self.global_parameters()?
+            .verifier_key()?
+            .verify_evaluation(commitment, point, evaluation, proof)
+    }
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn commits_and_verifies_through_opaque_types() {
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    committer.setup_with_rng(2, &mut test_rng());
+
+    let polynomial = Polynomial::from_field_elements(&[
+        FieldElement::from(3),
+        -FieldElement::one(),
+        FieldElement::from(2),
+    ]);
+    let point = FieldElement::from(4);
+    let commitment = committer.commit_opaque(&polynomial).unwrap();
+    let (proof, evaluation) = committer.prove(&polynomial, point).unwrap();
+    assert_eq!(evaluation, FieldElement::from(3 - 4 + 2 * 16));
+    assert_eq!(evaluation, polynomial.evaluate_at(point));
+
+    // Everything survives a trip through bytes
+    let commitment = Commitment::from_bytes(&commitment.to_bytes()).unwrap();
+    let proof = Proof::from_bytes(&proof.to_bytes()).unwrap();
+    let evaluation = FieldElement::from_bytes(&evaluation.to_bytes()).unwrap();
+    assert!(committer
+        .verify(&commitment, point, evaluation, &proof)
+        .unwrap());
+    assert!(!committer
+        .verify(&commitment, point, evaluation + FieldElement::one(), &proof)
+        .unwrap());
+
+    // A commitment over a domain needs a key for that domain, here as through `VerifierKey`
+    let on_domain = Commitment {
+        domain_size: Some(4),
+        ..commitment
+    };
+    let mismatch = Error::DomainMismatch {
+        expected: None,
+        actual: Some(4),
+    };
+    assert_eq!(
+        committer
+            .verify(&on_domain, point, evaluation, &proof)
+            .unwrap_err(),
+        mismatch
+    );
+    let key = committer
+        .global_parameters()
+        .unwrap()
+        .verifier_key()
+        .unwrap();
+    assert_eq!(
+        key.verify_evaluation(&on_domain, point, evaluation, &proof)
+            .unwrap_err(),
+        mismatch
+    );
+}
+
+#[test]
+fn rejects_malformed_bytes() {
+    assert!(FieldElement::from_bytes(&[0xff; FIELD_ELEMENT_SIZE]).is_err());
+    assert!(Commitment::from_bytes(&[0xff; COMMITMENT_SIZE]).is_err());
+    assert!(Commitment::from_bytes(&[0; COMMITMENT_SIZE]).is_err());
+    assert!(Proof::from_bytes(&[0; PROOF_SIZE]).is_err());
+}
@@ -0,0 +1,272 @@
+use crate::constant_time::ct_eq_gt;
+use crate::degree::MaxDegree;
+#[cfg(feature = "prover")]
+use crate::domain::EvaluationDomain;
+use crate::error::{Error, VerifyError};
+#[cfg(feature = "prover")]
+use crate::polynomial_commitments::GlobalParameters;
+use crate::types::{Commitment, FieldElement, Proof};
+use blstrs::{pairing, G1Affine, G1Projective, G2Affine, G2Projective};
+use group::{Curve, Group};
+
+pub const VERIFIER_KEY_SIZE: usize = 48 + 96 + 96 + 8 + 8;
+
+// The part of the global parameters a verifier needs, tagged with the degree of the parameters it came from and
+// optionally an evaluation domain. Commitments made for other parameters or another domain fail with
+// `DegreeMismatch` or `DomainMismatch` instead of verifying as false, which would look just like a bad proof
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VerifierKey {
+    g: G1Projective,
+    h: G2Projective,
+    h_tau: G2Projective,
+    max_degree: MaxDegree,
+    domain_size: Option<usize>,
+}
+
+#[cfg(feature = "prover")]
+impl GlobalParameters {
+    pub fn verifier_key(&self) -> Result<VerifierKey, Error> {
+        if self.hs().len() < 2 {
+            return Err(Error::InvalidParameters("verification needs tau in G2"));
+        }
+        Ok(VerifierKey {
+            g: self.gs()[0],
+            h: self.hs()[0],
+            h_tau: self.hs()[1],
+            max_degree: self.max_degree(),
+            domain_size: None,
+        })
+    }
+}
+
+impl VerifierKey {
+    // A key that only accepts commitments made over `domain`
+    #[cfg(feature = "prover")]
+    pub fn for_domain(self, domain: &EvaluationDomain) -> Self {
+        self.with_domain_size(domain.size())
+    }
+
+    pub fn with_domain_size(self, domain_size: usize) -> Self {
+        VerifierKey {
+            domain_size: Some(domain_size),
+            ..self
+        }
+    }
+
+    // The compressed G1 generator, G2 generator and $[\tau]_2$, then the max degree and the domain size as
+    // little-endian u64s with `u64::MAX` for no domain. Light clients ship this instead of the parameters
+    pub fn to_bytes(&self) -> [u8; VERIFIER_KEY_SIZE] {
+        let mut bytes = [0u8; VERIFIER_KEY_SIZE];
+        bytes[..48].copy_from_slice(&self.g.to_affine().to_compressed());
+        bytes[48..144].copy_from_slice(&self.h.to_affine().to_compressed());
+        bytes[144..240].copy_from_slice(&self.h_tau.to_affine().to_compressed());
+        bytes[240..248].copy_from_slice(&(self.max_degree.get() as u64).to_le_bytes());
+        let domain_size = self.domain_size.map_or(u64::MAX, |size| size as u64);
+        bytes[248..].copy_from_slice(&domain_size.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8; VERIFIER_KEY_SIZE]) -> Result<Self, Error> {
+        let g =
+            Option::<G1Affine>::from(G1Affine::from_compressed(bytes[..48].try_into().unwrap()))
+                .ok_or(Error::InvalidPoint {
+                    group: "G1",
+                    index: 0,
+                })?;
+        let g2_at = |index: usize| {
+            let start = 48 + 96 * index;
+            Option::<G2Affine>::from(G2Affine::from_compressed(
+                bytes[start..start + 96].try_into().unwrap(),
+            ))
+            .ok_or(Error::InvalidPoint { group: "G2", index })
+        };
+        let (h, h_tau) = (g2_at(0)?, g2_at(1)?);
+        let max_degree = u64::from_le_bytes(bytes[240..248].try_into().unwrap());
+        let domain_size = u64::from_le_bytes(bytes[248..].try_into().unwrap());
+        Ok(VerifierKey {
+            g: g.into(),
+            h: h.into(),
+            h_tau: h_tau.into(),
+            max_degree: MaxDegree::new(max_degree as usize),
+            domain_size: (domain_size != u64::MAX).then_some(domain_size as usize),
+        })
+    }
+
+    pub fn max_degree(&self) -> MaxDegree {
+        self.max_degree
+    }
+
+    pub fn domain_size(&self) -> Option<usize> {
+        self.domain_size
+    }
+
+    pub(crate) fn g(&self) -> G1Projective {
+        self.g
+    }
+
+    pub(crate) fn h(&self) -> G2Projective {
+        self.h
+    }
+
+    pub(crate) fn h_tau(&self) -> G2Projective {
+        self.h_tau
+    }
+
+    // Checks the commitment was made for this key before any pairing is computed
+    pub fn check_compatible(&self, commitment: &Commitment) -> Result<(), Error> {
+        self.max_degree.check(commitment.max_degree())?;
+        if self.domain_size != commitment.domain_size() {
+            return Err(Error::DomainMismatch {
+                expected: self.domain_size,
+                actual: commitment.domain_size(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn verify_evaluation(
+        &self,
+        commitment: &Commitment,
+        point: FieldElement,
+        evaluation: FieldElement,
+        proof: &Proof,
+    ) -> Result<bool, Error> {
+        self.check_compatible(commitment)?;
+        if bool::from(commitment.inner().is_identity()) {
+            return Err(Error::IdentityCommitment);
+        }
+        if bool::from(proof.inner().is_identity()) {
+            return Err(Error::IdentityWitness);
+        }
+
+        // $e(C - [v]_1, h) = e(W, [\tau]_2 - [z]_2)$
+        let lhs = pairing(
+            &(commitment.inner() - self.g * evaluation.inner()).to_affine(),
+            &self.h.to_affine(),
+        );
+        let rhs = pairing(
+            &proof.inner().to_affine(),
+            &(self.h_tau - self.h * point.inner()).to_affine(),
+        );
+        Ok(ct_eq_gt(&lhs, &rhs).into())
+    }
+}
+
+impl Commitment {
+    // `VerifierKey::verify_evaluation` from the commitment's side, with a reason when the opening isn't accepted
+    pub fn verify(
+        &self,
+        verifier_key: &VerifierKey,
+        point: FieldElement,
+        evaluation: FieldElement,
+        proof: &Proof,
+    ) -> Result<(), VerifyError> {
+        if verifier_key.verify_evaluation(self, point, evaluation, proof)? {
+            Ok(())
+        } else {
+            Err(VerifyError::Rejected)
+        }
+    }
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn rejects_commitments_for_other_parameters() {
+    use crate::domain_polynomial::DomainPolynomial;
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let gp = committer.setup_with_rng(3, &mut test_rng());
+    let key = gp.verifier_key().unwrap();
+    let polynomial = Polynomial::new_from_i64(&[1, 2, 3, 4]);
+    let point = FieldElement::from(9);
+
+    let commitment = committer.commit_opaque(&polynomial).unwrap();
+    let (proof, evaluation) = committer.prove(&polynomial, point).unwrap();
+    assert!(key
+        .verify_evaluation(&commitment, point, evaluation, &proof)
+        .unwrap());
+    assert!(!key
+        .verify_evaluation(&commitment, point, evaluation + FieldElement::one(), &proof)
+        .unwrap());
+
+    // A key for other parameters
+    let mut other = GenericPolynomialCommitment::new();
+    let other_key = other
+        .setup_with_rng(4, &mut test_rng())
+        .verifier_key()
+        .unwrap();
+    assert_eq!(
+        other_key.verify_evaluation(&commitment, point, evaluation, &proof),
+        Err(Error::DegreeMismatch {
+            expected: 4,
+            actual: 3
+        })
+    );
+
+    // A commitment over a domain only verifies with a key for that domain
+    let domain = EvaluationDomain::new(4).unwrap();
+    let on_domain = committer
+        .commit_on_domain(&DomainPolynomial::from_coefficients(domain.clone(), polynomial).unwrap())
+        .unwrap();
+    assert_eq!(
+        key.verify_evaluation(&on_domain, point, evaluation, &proof),
+        Err(Error::DomainMismatch {
+            expected: None,
+            actual: Some(4)
+        })
+    );
+    assert!(key
+        .for_domain(&domain)
+        .verify_evaluation(&on_domain, point, evaluation, &proof)
+        .unwrap());
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn round_trips_verifier_keys() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::rng::test_rng;
+
+    let key = GenericPolynomialCommitment::new()
+        .setup_with_rng(3, &mut test_rng())
+        .verifier_key()
+        .unwrap();
+    for key in [key, key.with_domain_size(16)] {
+        assert_eq!(VerifierKey::from_bytes(&key.to_bytes()).unwrap(), key);
+    }
+    assert!(VerifierKey::from_bytes(&[0; VERIFIER_KEY_SIZE]).is_err());
+}
+
+#[cfg(feature = "prover")]
+#[test]
+fn verify_says_why_an_opening_fails() {
+    use crate::polynomial_commitments::{GenericPolynomialCommitment, PolynomialCommitment};
+    use crate::polynomials::Polynomial;
+    use crate::rng::test_rng;
+
+    let mut committer = GenericPolynomialCommitment::new();
+    let key = committer
+        .setup_with_rng(3, &mut test_rng())
+        .verifier_key()
+        .unwrap();
+    let polynomial = Polynomial::new_from_i64(&[1, 2, 3, 4]);
+    let point = FieldElement::from(9);
+    let commitment = committer.commit_opaque(&polynomial).unwrap();
+    let (proof, evaluation) = committer.prove(&polynomial, point).unwrap();
+
+    assert_eq!(commitment.verify(&key, point, evaluation, &proof), Ok(()));
+    assert_eq!(
+        commitment.verify(&key, point, evaluation + FieldElement::one(), &proof),
+        Err(VerifyError::Rejected)
+    );
+    assert_eq!(
+        commitment.verify(&key.with_domain_size(4), point, evaluation, &proof),
+        Err(VerifyError::Invalid(Error::DomainMismatch {
+            expected: Some(4),
+            actual: None
+        }))
+    );
+}
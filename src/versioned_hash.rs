@@ -0,0 +1,48 @@
+use crate::types::Commitment;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+// EIP-4844 refers to blobs by versioned hash rather than by commitment: the SHA-256 of the compressed commitment
+// with its first byte replaced by the version. Blob transactions carry these, and the execution layer checks each
+// one against the commitment the consensus layer supplies
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+pub fn kzg_to_versioned_hash(commitment: &Commitment) -> [u8; 32] {
+    let mut hash: [u8; 32] = Sha256::digest(commitment.point_bytes()).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+// False for hashes with any other version byte
+pub fn commitment_matches_versioned_hash(
+    commitment: &Commitment,
+    versioned_hash: &[u8; 32],
+) -> bool {
+    kzg_to_versioned_hash(commitment)
+        .ct_eq(versioned_hash)
+        .into()
+}
+
+#[test]
+fn hashes_commitments_like_eip_4844() {
+    use crate::degree::MaxDegree;
+
+    // The commitment to the all-zero blob is the identity, whose versioned hash is widely published
+    let mut identity = [0u8; 48];
+    identity[0] = 0xc0;
+    let commitment = Commitment::from_point_bytes(&identity, MaxDegree::new(4095)).unwrap();
+    let hash = kzg_to_versioned_hash(&commitment);
+    let hex: String = hash.iter().map(|byte| format!("{byte:02x}")).collect();
+    assert_eq!(
+        hex,
+        "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+    );
+    assert!(commitment_matches_versioned_hash(&commitment, &hash));
+
+    let mut other_version = hash;
+    other_version[0] = 0x02;
+    assert!(!commitment_matches_versioned_hash(
+        &commitment,
+        &other_version
+    ));
+}
@@ -0,0 +1,57 @@
+// The conformance suite as a third-party backend sees it: through the public paths only
+use polynomial_commitments_scratch::conformance::{run_conformance, ConformanceFailure};
+use polynomial_commitments_scratch::prelude::*;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{CryptoRng, RngCore, SeedableRng};
+
+// A backend defined outside the crate, delegating to the KZG one
+struct Wrapped(GenericPolynomialCommitment);
+
+impl PolynomialCommitment for Wrapped {
+    type Parameters = GlobalParameters;
+    type Commitment = G1Projective;
+    type Witness = G1Projective;
+
+    fn setup_with_rng<R: RngCore + CryptoRng>(
+        &mut self,
+        d: usize,
+        rng: &mut R,
+    ) -> GlobalParameters {
+        self.0.setup_with_rng(d, rng)
+    }
+
+    fn commit(&self, polynomial: &Polynomial) -> Result<G1Projective, Error> {
+        self.0.commit(polynomial)
+    }
+
+    fn create_witness(
+        &self,
+        polynomial: &Polynomial,
+        point: Scalar,
+    ) -> Result<(G1Projective, Scalar), Error> {
+        self.0.create_witness(polynomial, point)
+    }
+
+    fn verify_evaluation(
+        &self,
+        commitment: G1Projective,
+        point: Scalar,
+        evaluation: Scalar,
+        witness: G1Projective,
+    ) -> Result<bool, Error> {
+        self.0
+            .verify_evaluation(commitment, point, evaluation, witness)
+    }
+}
+
+#[test]
+fn an_external_backend_runs_the_suite() {
+    let mut rng = ChaCha20Rng::seed_from_u64(973);
+    let result: Result<(), ConformanceFailure> = run_conformance(
+        &mut Wrapped(GenericPolynomialCommitment::new()),
+        5,
+        2,
+        &mut rng,
+    );
+    assert_eq!(result, Ok(()));
+}